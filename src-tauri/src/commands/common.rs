@@ -1,7 +1,9 @@
 use std::collections::hash_map::DefaultHasher;
 use std::fs;
 use std::hash::{Hash, Hasher};
+use std::io::Read;
 use std::path::{Path, PathBuf};
+use sha2::{Digest, Sha256};
 
 pub fn generate_id(path: &str) -> String {
     let mut hasher = DefaultHasher::new();
@@ -9,6 +11,25 @@ pub fn generate_id(path: &str) -> String {
     format!("{:x}", hasher.finish())
 }
 
+/// SHA-256 du contenu d'un fichier, en hexadecimal
+pub fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = fs::File::open(path)
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
 pub fn is_text_file(path: &Path) -> bool {
     if let Some(ext) = path.extension() {
         let ext = ext.to_string_lossy().to_lowercase();
@@ -74,6 +95,88 @@ pub fn validate_path(path_str: &str) -> Result<PathBuf, String> {
     Ok(canonical)
 }
 
+/// Longueur maximale (octets) d'un nom nettoye : laisse la place a un suffixe
+/// " (n)" et a ".meta.json" sous la limite de 255 octets des systemes de fichiers
+const MAX_FILE_NAME_BYTES: usize = 200;
+
+/// Au-dela, l'extension n'en est pas une et peut etre tronquee avec le reste
+const MAX_EXTENSION_BYTES: usize = 16;
+
+/// Plus long prefixe de `s` d'au plus `max` octets, sans couper un caractere
+fn truncate_bytes(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        return s;
+    }
+    let mut end = max;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+/// Nettoie un nom de fichier fourni par l'utilisateur ou un document source :
+/// supprime les separateurs de chemin, caracteres reserves et ".." initiaux,
+/// et tronque les noms trop longs en conservant l'extension
+pub fn sanitize_file_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+
+    let cleaned = cleaned.trim().trim_start_matches('.').trim();
+    if cleaned.is_empty() {
+        return "sans-titre".to_string();
+    }
+    if cleaned.len() <= MAX_FILE_NAME_BYTES {
+        return cleaned.to_string();
+    }
+
+    let ext = Path::new(cleaned)
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .filter(|e| e.len() <= MAX_EXTENSION_BYTES)
+        .unwrap_or_default();
+    let stem = &cleaned[..cleaned.len() - ext.len()];
+    format!(
+        "{}{}",
+        truncate_bytes(stem, MAX_FILE_NAME_BYTES - ext.len()).trim_end(),
+        ext
+    )
+}
+
+/// "nom.ext" pour n = 1, sinon "nom (n).ext"
+pub fn numbered_file_name(file_name: &str, n: usize) -> String {
+    if n <= 1 {
+        return file_name.to_string();
+    }
+
+    let file_path = Path::new(file_name);
+    let stem = file_path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| file_name.to_string());
+    let ext = file_path.extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+
+    format!("{} ({}){}", stem, n, ext)
+}
+
+/// Retourne un chemin libre dans `dir` : "nom.ext", puis "nom (2).ext", "nom (3).ext"...
+pub fn unique_path(dir: &Path, file_name: &str) -> PathBuf {
+    let mut n = 1;
+    loop {
+        let candidate = dir.join(numbered_file_name(file_name, n));
+        if !candidate.exists() {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
 /// Same as validate_path but for two paths (source + destination operations)
 pub fn validate_two_paths(path1: &str, path2: &str) -> Result<(PathBuf, PathBuf), String> {
     let p1 = validate_path(path1)?;
@@ -87,3 +190,105 @@ pub fn chrono_now() -> String {
     let duration = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
     format!("{}", duration.as_secs())
 }
+
+/// Horodatage courant au format ISO 8601 (UTC)
+pub fn chrono_iso8601_now() -> String {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let duration = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    let secs = duration.as_secs();
+    // Format ISO 8601 simplifie
+    time_to_iso8601(secs)
+}
+
+pub fn time_to_iso8601(epoch_secs: u64) -> String {
+    // Conversion simplifiee epoch -> ISO 8601
+    let days = epoch_secs / 86400;
+    let time_of_day = epoch_secs % 86400;
+    let hours = time_of_day / 3600;
+    let minutes = (time_of_day % 3600) / 60;
+    let seconds = time_of_day % 60;
+
+    // Calcul de la date depuis epoch (1970-01-01)
+    let mut y = 1970i64;
+    let mut remaining_days = days as i64;
+
+    loop {
+        let days_in_year = if is_leap_year(y) { 366 } else { 365 };
+        if remaining_days < days_in_year {
+            break;
+        }
+        remaining_days -= days_in_year;
+        y += 1;
+    }
+
+    let days_in_months: [i64; 12] = if is_leap_year(y) {
+        [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
+    } else {
+        [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
+    };
+
+    let mut m = 0;
+    for i in 0..12 {
+        if remaining_days < days_in_months[i] {
+            m = i;
+            break;
+        }
+        remaining_days -= days_in_months[i];
+    }
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        y,
+        m + 1,
+        remaining_days + 1,
+        hours,
+        minutes,
+        seconds
+    )
+}
+
+pub fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_file_name_strips_separators_and_leading_dots() {
+        assert_eq!(sanitize_file_name("../../evil.md"), "_.._evil.md");
+        assert_eq!(sanitize_file_name("Conclusions: v2.docx"), "Conclusions_ v2.docx");
+        assert_eq!(sanitize_file_name(" .cache "), "cache");
+        assert_eq!(sanitize_file_name("a\u{0}b\n.txt"), "a_b_.txt");
+        assert_eq!(sanitize_file_name(".."), "sans-titre");
+        assert_eq!(sanitize_file_name(""), "sans-titre");
+    }
+
+    #[test]
+    fn sanitize_file_name_truncates_long_names_and_keeps_the_extension() {
+        let name = sanitize_file_name(&format!("{}.docx", "é".repeat(300)));
+        assert!(name.len() <= MAX_FILE_NAME_BYTES);
+        assert!(name.ends_with("é.docx"));
+
+        let no_extension = sanitize_file_name(&format!("a.{}", "b".repeat(300)));
+        assert_eq!(no_extension.len(), MAX_FILE_NAME_BYTES);
+    }
+
+    #[test]
+    fn unique_path_numbers_taken_names() {
+        let dir = std::env::temp_dir().join(format!("citadelle-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        assert_eq!(unique_path(&dir, "acte.docx"), dir.join("acte.docx"));
+        fs::write(dir.join("acte.docx"), "").unwrap();
+        assert_eq!(unique_path(&dir, "acte.docx"), dir.join("acte (2).docx"));
+        fs::write(dir.join("acte (2).docx"), "").unwrap();
+        assert_eq!(unique_path(&dir, "acte.docx"), dir.join("acte (3).docx"));
+
+        fs::write(dir.join("LISEZMOI"), "").unwrap();
+        assert_eq!(unique_path(&dir, "LISEZMOI"), dir.join("LISEZMOI (2)"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
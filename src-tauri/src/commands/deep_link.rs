@@ -0,0 +1,448 @@
+//! Liens citadelle:// : analyse, transfert a l'instance deja lancee et
+//! distribution au frontend sous forme d'evenements types.
+//!
+//! Sous Linux et Windows, cliquer un lien lance un nouveau processus avec l'URL
+//! en argument ; ce processus la transmet a l'instance en cours par une socket
+//! locale puis se termine.
+//!
+//! Hors perimetre : sous macOS, les liens cliques pendant l'execution arrivent
+//! par Apple Event, que Tauri 1 n'expose pas, et ne sont pas recus ; seul le
+//! lien de lancement y est pris en charge. Pour une instance deja lancee,
+//! GoldoCab passe par les demandes deposees dans 03_HANDOFF/inbox.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, State};
+use uuid::Uuid;
+
+use super::common::{get_citadelle_dir, validate_path};
+use super::goldocab_sessions::{get_goldocab_handoff_dir, read_session};
+
+pub const DEEP_LINK_SCHEME: &str = "citadelle://";
+
+pub const DEEP_LINK_OPEN_EVENT: &str = "deep-link-open";
+pub const DEEP_LINK_SESSION_EVENT: &str = "deep-link-session";
+pub const DEEP_LINK_NOTE_EVENT: &str = "deep-link-note";
+pub const DEEP_LINK_REJECTED_EVENT: &str = "deep-link-rejected";
+/// Le serveur local n'a pas demarre : les liens des instances lancees ensuite
+/// seront ouverts par celles-ci plutot que transmis
+pub const DEEP_LINK_UNAVAILABLE_EVENT: &str = "deep-link-unavailable";
+
+const MAX_URL_LENGTH: usize = 4096;
+const MAX_ID_LENGTH: usize = 64;
+
+/// Adresse et jeton de l'instance en cours, dans ~/.citadelle
+const INSTANCE_FILE_NAME: &str = "deep-link.json";
+
+const FORWARD_TIMEOUT: Duration = Duration::from_millis(500);
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeepLinkOpenMode {
+    /// Session d'edition GoldoCab sur une copie de travail
+    Document,
+    /// Note partagee, ouverte directement dans l'editeur
+    Note,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "route", rename_all = "snake_case")]
+pub enum DeepLinkRoute {
+    /// citadelle://open?path=...&dossierId=...&dossierName=...&mode=document|note
+    Open {
+        path: String,
+        dossier_id: Option<String>,
+        dossier_name: Option<String>,
+        mode: DeepLinkOpenMode,
+    },
+    /// citadelle://session/<id>
+    Session { session_id: String },
+    /// citadelle://note/<id>
+    Note { note_id: String },
+}
+
+impl DeepLinkRoute {
+    fn event_name(&self) -> &'static str {
+        match self {
+            DeepLinkRoute::Open { .. } => DEEP_LINK_OPEN_EVENT,
+            DeepLinkRoute::Session { .. } => DEEP_LINK_SESSION_EVENT,
+            DeepLinkRoute::Note { .. } => DEEP_LINK_NOTE_EVENT,
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+pub struct DeepLinkRejection {
+    pub url: String,
+    pub error: String,
+}
+
+struct PendingLinks {
+    /// Vrai une fois que le frontend ecoute les evenements
+    ready: bool,
+    urls: Vec<String>,
+    /// Echec du demarrage du serveur, signale au frontend quand il ecoute
+    server_error: Option<String>,
+}
+
+/// Liens recus avant que le frontend ecoute les evenements
+pub struct DeepLinkState {
+    pending: Mutex<PendingLinks>,
+}
+
+impl DeepLinkState {
+    pub fn new(startup_link: Option<String>) -> Self {
+        DeepLinkState {
+            pending: Mutex::new(PendingLinks {
+                ready: false,
+                urls: startup_link.into_iter().collect(),
+                server_error: None,
+            }),
+        }
+    }
+
+    /// Garde l'erreur de `start_deep_link_server` pour `flush_pending_deep_links`
+    pub fn report_server_error(&self, error: String) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.server_error = Some(error);
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct InstanceFile {
+    port: u16,
+    token: String,
+    pid: u32,
+}
+
+fn decode_component(value: &str) -> Result<String, String> {
+    // Meme convention que URLSearchParams : '+' vaut une espace
+    urlencoding::decode(&value.replace('+', " "))
+        .map(|v| v.into_owned())
+        .map_err(|_| format!("Encodage invalide: {}", value))
+}
+
+fn parse_query(query: &str) -> Result<HashMap<String, String>, String> {
+    let mut params = HashMap::new();
+
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let key = decode_component(key)?;
+        let value = decode_component(value)?;
+        if params.insert(key.clone(), value).is_some() {
+            return Err(format!("Parametre en double: {}", key));
+        }
+    }
+
+    Ok(params)
+}
+
+fn parse_id(kind: &str, value: &str) -> Result<String, String> {
+    let id = decode_component(value)?;
+    let valid = !id.is_empty()
+        && id.len() <= MAX_ID_LENGTH
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if !valid {
+        return Err(format!("Identifiant de {} invalide: {}", kind, id));
+    }
+    Ok(id)
+}
+
+fn non_empty(params: &mut HashMap<String, String>, key: &str) -> Option<String> {
+    params
+        .remove(key)
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Analyse une URL citadelle:// ; toute route inconnue ou mal formee est refusee
+pub fn parse_deep_link(url: &str) -> Result<DeepLinkRoute, String> {
+    let url = url.trim();
+    if url.len() > MAX_URL_LENGTH {
+        return Err("Lien trop long".to_string());
+    }
+
+    let rest = url
+        .get(..DEEP_LINK_SCHEME.len())
+        .filter(|scheme| scheme.eq_ignore_ascii_case(DEEP_LINK_SCHEME))
+        .map(|_| &url[DEEP_LINK_SCHEME.len()..])
+        .ok_or_else(|| format!("Lien non citadelle://: {}", url))?;
+
+    let rest = rest.split('#').next().unwrap_or("");
+    let (route, query) = rest.split_once('?').unwrap_or((rest, ""));
+    let segments: Vec<&str> = route.trim_end_matches('/').split('/').collect();
+    let mut params = parse_query(query)?;
+
+    match segments.as_slice() {
+        [host] if host.eq_ignore_ascii_case("open") => {
+            let path = non_empty(&mut params, "path")
+                .ok_or("Parametre path manquant")?;
+            if !Path::new(&path).is_absolute() {
+                return Err(format!("Chemin non absolu: {}", path));
+            }
+
+            let mode = match non_empty(&mut params, "mode").as_deref() {
+                None | Some("document") => DeepLinkOpenMode::Document,
+                Some("note") => DeepLinkOpenMode::Note,
+                Some(other) => return Err(format!("Mode inconnu: {}", other)),
+            };
+
+            Ok(DeepLinkRoute::Open {
+                path,
+                dossier_id: non_empty(&mut params, "dossierId"),
+                dossier_name: non_empty(&mut params, "dossierName"),
+                mode,
+            })
+        }
+        [host, id] if host.eq_ignore_ascii_case("session") => Ok(DeepLinkRoute::Session {
+            session_id: parse_id("session", id)?,
+        }),
+        [host, id] if host.eq_ignore_ascii_case("note") => Ok(DeepLinkRoute::Note {
+            note_id: parse_id("note", id)?,
+        }),
+        _ => Err(format!("Route citadelle:// inconnue: {}", route)),
+    }
+}
+
+/// Verifie que la cible du lien existe avant de le transmettre au frontend
+fn check_target(route: &DeepLinkRoute) -> Result<(), String> {
+    match route {
+        DeepLinkRoute::Open { path, .. } => {
+            if !validate_path(path)?.is_file() {
+                return Err(format!("Fichier introuvable: {}", path));
+            }
+        }
+        DeepLinkRoute::Session { session_id } => {
+            read_session(&get_goldocab_handoff_dir()?, session_id)?;
+        }
+        // Les notes sont retrouvees par le frontend a partir de leur frontmatter
+        DeepLinkRoute::Note { .. } => {}
+    }
+    Ok(())
+}
+
+fn dispatch(app: &tauri::AppHandle, url: &str) -> Result<(), String> {
+    let result = parse_deep_link(url).and_then(|route| check_target(&route).map(|_| route));
+
+    match &result {
+        Ok(route) => {
+            let _ = app.emit_all(route.event_name(), route.clone());
+        }
+        Err(error) => {
+            let _ = app.emit_all(
+                DEEP_LINK_REJECTED_EVENT,
+                DeepLinkRejection { url: url.to_string(), error: error.clone() },
+            );
+        }
+    }
+
+    result.map(|_| ())
+}
+
+/// Distribue un lien, ou le met de cote tant que le frontend n'est pas pret
+fn receive(app: &tauri::AppHandle, url: String) -> Result<(), String> {
+    {
+        let state = app.state::<DeepLinkState>();
+        let mut pending = state.pending.lock().map_err(|_| "Etat des liens indisponible")?;
+        if !pending.ready {
+            parse_deep_link(&url)?;
+            pending.urls.push(url);
+            return Ok(());
+        }
+    }
+
+    if let Some(window) = app.get_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    dispatch(app, &url)
+}
+
+/// Premier argument citadelle:// de la ligne de commande (pas forcement le premier)
+pub fn deep_link_from_args(args: &[String]) -> Option<String> {
+    args.iter()
+        .skip(1)
+        .find(|arg| {
+            arg.get(..DEEP_LINK_SCHEME.len())
+                .map(|scheme| scheme.eq_ignore_ascii_case(DEEP_LINK_SCHEME))
+                .unwrap_or(false)
+        })
+        .cloned()
+}
+
+fn instance_file_path() -> PathBuf {
+    get_citadelle_dir().join(INSTANCE_FILE_NAME)
+}
+
+/// Transmet le lien a une instance deja lancee ; renvoie vrai si elle l'a recu,
+/// auquel cas ce processus n'a plus qu'a se terminer
+pub fn forward_deep_link(url: &str) -> bool {
+    let Some(instance) = fs::read_to_string(instance_file_path())
+        .ok()
+        .and_then(|content| serde_json::from_str::<InstanceFile>(&content).ok())
+    else {
+        return false;
+    };
+
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, instance.port));
+    let Ok(mut stream) = TcpStream::connect_timeout(&addr, FORWARD_TIMEOUT) else {
+        // Fichier laisse par une instance qui a plante
+        return false;
+    };
+    let _ = stream.set_read_timeout(Some(CONNECTION_TIMEOUT));
+
+    if writeln!(stream, "{}\n{}", instance.token, url).is_err() {
+        return false;
+    }
+
+    let mut reply = String::new();
+    if BufReader::new(stream).read_line(&mut reply).is_err() {
+        return false;
+    }
+    // Un lien refuse a quand meme ete traite par l'instance en cours
+    reply.starts_with("ok") || reply.starts_with("rejected")
+}
+
+fn write_instance_file(instance: &InstanceFile) -> Result<(), String> {
+    let path = instance_file_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Erreur creation ~/.citadelle: {}", e))?;
+    }
+    let json = serde_json::to_string(instance).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| format!("Erreur ecriture {}: {}", INSTANCE_FILE_NAME, e))?;
+
+    // Le jeton empeche un autre utilisateur de la machine d'injecter des liens
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o600));
+    }
+    Ok(())
+}
+
+fn handle_connection(app: &tauri::AppHandle, stream: TcpStream, token: &str) {
+    let _ = stream.set_read_timeout(Some(CONNECTION_TIMEOUT));
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+    let mut lines = BufReader::new(stream).lines();
+
+    let received_token = lines.next().and_then(|l| l.ok()).unwrap_or_default();
+    if received_token != token {
+        let _ = writeln!(writer, "denied");
+        return;
+    }
+
+    let url = lines.next().and_then(|l| l.ok()).unwrap_or_default();
+    let reply = match receive(app, url) {
+        Ok(()) => "ok".to_string(),
+        Err(e) => format!("rejected: {}", e),
+    };
+    let _ = writeln!(writer, "{}", reply);
+}
+
+/// Ecoute les liens transmis par les instances suivantes (single-instance)
+pub fn start_deep_link_server(app: tauri::AppHandle) -> Result<(), String> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .map_err(|e| format!("Socket locale indisponible: {}", e))?;
+    let addr = listener
+        .local_addr()
+        .map_err(|e| format!("Socket locale indisponible: {}", e))?;
+
+    let token = Uuid::new_v4().to_string();
+    let instance = InstanceFile {
+        port: addr.port(),
+        token: token.clone(),
+        pid: std::process::id(),
+    };
+    write_instance_file(&instance)?;
+
+    thread::spawn(move || {
+        for stream in listener.incoming().filter_map(|s| s.ok()) {
+            handle_connection(&app, stream, &token);
+        }
+    });
+    Ok(())
+}
+
+/// Appele par le frontend une fois ses ecouteurs en place : distribue les liens
+/// recus entre-temps (dont celui du lancement)
+#[tauri::command]
+pub async fn flush_pending_deep_links(
+    app: tauri::AppHandle,
+    state: State<'_, DeepLinkState>,
+) -> Result<usize, String> {
+    let (pending, server_error) = {
+        let mut pending = state.pending.lock().map_err(|_| "Etat des liens indisponible")?;
+        pending.ready = true;
+        (std::mem::take(&mut pending.urls), pending.server_error.take())
+    };
+
+    if let Some(error) = server_error {
+        let _ = app.emit_all(DEEP_LINK_UNAVAILABLE_EVENT, error);
+    }
+
+    let count = pending.len();
+    for url in pending {
+        let _ = dispatch(&app, &url);
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_open_link() {
+        let route = parse_deep_link(
+            "citadelle://open?path=%2FUsers%2Fme%2Fconclusions.docx&dossierId=42&dossierName=Dupont+c%2F+Martin",
+        )
+        .unwrap();
+
+        assert_eq!(
+            route,
+            DeepLinkRoute::Open {
+                path: "/Users/me/conclusions.docx".to_string(),
+                dossier_id: Some("42".to_string()),
+                dossier_name: Some("Dupont c/ Martin".to_string()),
+                mode: DeepLinkOpenMode::Document,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_session_and_note_links() {
+        assert_eq!(
+            parse_deep_link("CITADELLE://session/abc-123/").unwrap(),
+            DeepLinkRoute::Session { session_id: "abc-123".to_string() }
+        );
+        assert_eq!(
+            parse_deep_link("citadelle://note/n_1#ancre").unwrap(),
+            DeepLinkRoute::Note { note_id: "n_1".to_string() }
+        );
+    }
+
+    #[test]
+    fn parse_rejects_malformed_links() {
+        assert!(parse_deep_link("https://open?path=/tmp/a").is_err());
+        assert!(parse_deep_link("citadelle://open?path=relatif.docx").is_err());
+        assert!(parse_deep_link("citadelle://open").is_err());
+        assert!(parse_deep_link("citadelle://open?path=/a&path=/b").is_err());
+        assert!(parse_deep_link("citadelle://open?path=/a&mode=autre").is_err());
+        assert!(parse_deep_link("citadelle://session/..%2Fetc").is_err());
+        assert!(parse_deep_link("citadelle://inconnu").is_err());
+        assert!(parse_deep_link(&format!("citadelle://note/{}", "a".repeat(MAX_URL_LENGTH))).is_err());
+    }
+}
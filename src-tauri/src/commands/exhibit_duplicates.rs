@@ -0,0 +1,367 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::common::sha256_file;
+use super::exhibits::{collect_exhibit_files, validate_exhibit_dir, ExhibitFile};
+
+/// Distance de Hamming maximale par defaut entre deux empreintes d'image
+const DEFAULT_IMAGE_THRESHOLD: u32 = 6;
+
+/// En dessous de ce nombre de caracteres, le texte extrait d'un PDF n'est pas
+/// assez discriminant (scan sans OCR, page de garde...)
+const MIN_TEXT_LENGTH: usize = 50;
+
+#[derive(Serialize, Clone)]
+pub struct DuplicateFile {
+    pub name: String,
+    pub path: String,
+    pub size: u64,
+}
+
+#[derive(Serialize)]
+pub struct DuplicateGroup {
+    /// "exact", "same_text" ou "similar_image"
+    pub kind: String,
+    /// SHA-256 du contenu (exact) ou du texte normalise (same_text)
+    pub hash: Option<String>,
+    /// Distance maximale observee dans le groupe (similar_image)
+    pub distance: Option<u32>,
+    pub files: Vec<DuplicateFile>,
+}
+
+#[derive(Serialize)]
+pub struct DuplicateScanResult {
+    pub scanned: usize,
+    pub groups: Vec<DuplicateGroup>,
+}
+
+fn is_pdf(path: &Path) -> bool {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase() == "pdf")
+        .unwrap_or(false)
+}
+
+fn is_image(path: &Path) -> bool {
+    if let Some(ext) = path.extension() {
+        let ext = ext.to_string_lossy().to_lowercase();
+        matches!(ext.as_str(), "jpg" | "jpeg" | "png" | "gif" | "tiff" | "bmp" | "webp")
+    } else {
+        false
+    }
+}
+
+/// Texte d'un PDF normalise (minuscules, espaces compactes) puis hache
+fn pdf_text_hash(path: &Path) -> Option<String> {
+    let bytes = fs::read(path).ok()?;
+
+    // pdf-extract peut paniquer sur des PDF mal formes
+    let text = std::panic::catch_unwind(|| pdf_extract::extract_text_from_mem(&bytes))
+        .ok()?
+        .ok()?;
+
+    let normalized = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+
+    if normalized.chars().count() < MIN_TEXT_LENGTH {
+        return None;
+    }
+
+    Some(format!("{:x}", Sha256::digest(normalized.as_bytes())))
+}
+
+/// Empreinte perceptuelle (dHash 64 bits) : compare la luminosite de pixels
+/// voisins sur une vignette 9x8 en niveaux de gris
+fn image_dhash(path: &Path) -> Option<u64> {
+    let img = image::open(path).ok()?;
+    let small = img
+        .resize_exact(9, 8, image::imageops::FilterType::Triangle)
+        .to_luma8();
+
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            let left = small.get_pixel(x, y)[0];
+            let right = small.get_pixel(x + 1, y)[0];
+            hash <<= 1;
+            if left > right {
+                hash |= 1;
+            }
+        }
+    }
+
+    Some(hash)
+}
+
+fn to_duplicate_file(file: &ExhibitFile) -> DuplicateFile {
+    DuplicateFile {
+        name: file.name.clone(),
+        path: file.path.clone(),
+        size: fs::metadata(&file.path).map(|m| m.len()).unwrap_or(0),
+    }
+}
+
+fn find_root(parents: &mut [usize], i: usize) -> usize {
+    let mut root = i;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    parents[i] = root;
+    root
+}
+
+#[tauri::command]
+pub async fn find_duplicate_exhibits(
+    path: String,
+    image_threshold: Option<u32>,
+) -> Result<DuplicateScanResult, String> {
+    let dir_path = validate_exhibit_dir(&path)?;
+    let files = collect_exhibit_files(&dir_path)?;
+    let threshold = image_threshold.unwrap_or(DEFAULT_IMAGE_THRESHOLD);
+
+    // Hachage, extraction de texte et decodage d'images : hors du runtime async
+    tauri::async_runtime::spawn_blocking(move || scan_duplicates(&files, threshold))
+        .await
+        .map_err(|e| format!("Erreur recherche de doublons: {}", e))
+}
+
+/// Doublons exacts, PDF au meme texte et images proches parmi `files`
+fn scan_duplicates(files: &[ExhibitFile], threshold: u32) -> DuplicateScanResult {
+    let mut groups = Vec::new();
+
+    // 1. Doublons exacts par hash du contenu
+    let mut by_hash: HashMap<String, Vec<&ExhibitFile>> = HashMap::new();
+    for file in files {
+        if let Ok(hash) = sha256_file(Path::new(&file.path)) {
+            by_hash.entry(hash).or_default().push(file);
+        }
+    }
+
+    // Un seul representant par contenu pour la detection approchee
+    let mut representatives: Vec<&ExhibitFile> = Vec::new();
+    for (hash, members) in &by_hash {
+        representatives.push(members[0]);
+        if members.len() > 1 {
+            groups.push(DuplicateGroup {
+                kind: "exact".to_string(),
+                hash: Some(hash.clone()),
+                distance: None,
+                files: members.iter().map(|f| to_duplicate_file(f)).collect(),
+            });
+        }
+    }
+    representatives.sort_by_key(|f| f.name.to_lowercase());
+
+    // 2. PDF au texte identique
+    let mut by_text: HashMap<String, Vec<&ExhibitFile>> = HashMap::new();
+    for file in representatives.iter().filter(|f| is_pdf(Path::new(&f.path))) {
+        if let Some(hash) = pdf_text_hash(Path::new(&file.path)) {
+            by_text.entry(hash).or_default().push(file);
+        }
+    }
+
+    for (hash, members) in by_text {
+        if members.len() > 1 {
+            groups.push(DuplicateGroup {
+                kind: "same_text".to_string(),
+                hash: Some(hash),
+                distance: None,
+                files: members.iter().map(|f| to_duplicate_file(f)).collect(),
+            });
+        }
+    }
+
+    // 3. Images proches, regroupees par union-find sur la distance de Hamming
+    let hashed_images: Vec<(&ExhibitFile, u64)> = representatives
+        .iter()
+        .filter(|f| is_image(Path::new(&f.path)))
+        .filter_map(|f| image_dhash(Path::new(&f.path)).map(|h| (*f, h)))
+        .collect();
+
+    let mut parents: Vec<usize> = (0..hashed_images.len()).collect();
+    for i in 0..hashed_images.len() {
+        for j in (i + 1)..hashed_images.len() {
+            if (hashed_images[i].1 ^ hashed_images[j].1).count_ones() <= threshold {
+                let root_i = find_root(&mut parents, i);
+                let root_j = find_root(&mut parents, j);
+                if root_i != root_j {
+                    parents[root_j] = root_i;
+                }
+            }
+        }
+    }
+
+    let mut clusters: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..hashed_images.len() {
+        let root = find_root(&mut parents, i);
+        clusters.entry(root).or_default().push(i);
+    }
+
+    for members in clusters.values() {
+        if members.len() < 2 {
+            continue;
+        }
+
+        let mut max_distance = 0;
+        for (k, &a) in members.iter().enumerate() {
+            for &b in &members[(k + 1)..] {
+                let d = (hashed_images[a].1 ^ hashed_images[b].1).count_ones();
+                max_distance = max_distance.max(d);
+            }
+        }
+
+        groups.push(DuplicateGroup {
+            kind: "similar_image".to_string(),
+            hash: None,
+            distance: Some(max_distance),
+            files: members.iter().map(|&i| to_duplicate_file(hashed_images[i].0)).collect(),
+        });
+    }
+
+    // Ordre stable pour l'affichage : exacts d'abord, puis par premier nom
+    let kind_rank = |kind: &str| match kind {
+        "exact" => 0,
+        "same_text" => 1,
+        _ => 2,
+    };
+    for group in &mut groups {
+        group.files.sort_by_key(|f| f.name.to_lowercase());
+    }
+    groups.sort_by(|a, b| {
+        kind_rank(&a.kind).cmp(&kind_rank(&b.kind)).then_with(|| {
+            let a_name = a.files.first().map(|f| f.name.to_lowercase()).unwrap_or_default();
+            let b_name = b.files.first().map(|f| f.name.to_lowercase()).unwrap_or_default();
+            a_name.cmp(&b_name)
+        })
+    });
+
+    DuplicateScanResult {
+        scanned: files.len(),
+        groups,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("citadelle-duplicates-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// PDF minimal d'une page affichant `text` ; `producer` change les octets
+    /// du fichier sans changer son texte
+    fn write_pdf(path: &Path, text: &str, producer: &str) {
+        let stream = format!("BT /F1 12 Tf 72 720 Td ({}) Tj ET", text);
+        let objects = [
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Contents 4 0 R \
+             /Resources << /Font << /F1 5 0 R >> >> >>"
+                .to_string(),
+            format!("<< /Length {} >>\nstream\n{}\nendstream", stream.len(), stream),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+            format!("<< /Producer ({}) >>", producer),
+        ];
+
+        let mut pdf = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::new();
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).into_bytes());
+        }
+        let xref = pdf.len();
+        pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+        for offset in offsets {
+            pdf.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+        }
+        pdf.extend(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R /Info 6 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref
+            )
+            .into_bytes(),
+        );
+        fs::write(path, pdf).unwrap();
+    }
+
+    fn gradient(path: &Path, reversed: bool, marked: bool) {
+        let mut img = image::GrayImage::from_fn(64, 64, |x, _| {
+            let value = if reversed { 255 - x * 4 } else { x * 4 };
+            image::Luma([value as u8])
+        });
+        if marked {
+            img.put_pixel(0, 0, image::Luma([128]));
+        }
+        img.save(path).unwrap();
+    }
+
+    fn names(group: &DuplicateGroup) -> Vec<&str> {
+        group.files.iter().map(|f| f.name.as_str()).collect()
+    }
+
+    #[test]
+    fn groups_exact_copies_same_text_and_similar_images() {
+        let dir = temp_dir();
+        fs::write(dir.join("a.txt"), "meme contenu").unwrap();
+        fs::write(dir.join("b.txt"), "meme contenu").unwrap();
+        fs::write(dir.join("c.txt"), "autre contenu").unwrap();
+
+        let text = "Conclusions recapitulatives pour Madame Martin devant le tribunal judiciaire";
+        write_pdf(&dir.join("scan 1.pdf"), text, "Scanner");
+        write_pdf(&dir.join("scan 2.pdf"), text, "Imprimante");
+        write_pdf(&dir.join("autre.pdf"), "Assignation devant le tribunal de commerce de Paris du 12 mars", "Scanner");
+
+        gradient(&dir.join("photo.png"), false, false);
+        gradient(&dir.join("photo retouchee.png"), false, true);
+        gradient(&dir.join("inverse.png"), true, false);
+
+        let files = collect_exhibit_files(&dir).unwrap();
+        let result = scan_duplicates(&files, DEFAULT_IMAGE_THRESHOLD);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(result.scanned, 9);
+        let kinds: Vec<&str> = result.groups.iter().map(|g| g.kind.as_str()).collect();
+        assert_eq!(kinds, ["exact", "same_text", "similar_image"]);
+
+        assert_eq!(names(&result.groups[0]), ["a.txt", "b.txt"]);
+        assert_eq!(names(&result.groups[1]), ["scan 1.pdf", "scan 2.pdf"]);
+        assert_eq!(names(&result.groups[2]), ["photo retouchee.png", "photo.png"]);
+        assert!(result.groups[2].distance.unwrap() <= DEFAULT_IMAGE_THRESHOLD);
+    }
+
+    #[test]
+    fn exact_copies_are_not_reported_again_as_similar() {
+        let dir = temp_dir();
+        gradient(&dir.join("photo.png"), false, false);
+        fs::copy(dir.join("photo.png"), dir.join("copie.png")).unwrap();
+
+        let files = collect_exhibit_files(&dir).unwrap();
+        let result = scan_duplicates(&files, DEFAULT_IMAGE_THRESHOLD);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(result.groups.len(), 1);
+        assert_eq!(result.groups[0].kind, "exact");
+    }
+
+    #[test]
+    fn short_pdf_text_is_not_compared() {
+        let dir = temp_dir();
+        write_pdf(&dir.join("garde 1.pdf"), "Piece 1", "Scanner");
+        write_pdf(&dir.join("garde 2.pdf"), "Piece 1", "Imprimante");
+
+        let files = collect_exhibit_files(&dir).unwrap();
+        let result = scan_duplicates(&files, DEFAULT_IMAGE_THRESHOLD);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(result.groups.is_empty());
+    }
+}
@@ -0,0 +1,685 @@
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use lopdf::content::{Content, Operation};
+use lopdf::{dictionary, Document, Object, Stream, StringFormat};
+use mail_parser::{Address, MessageParser, MimeHeaders};
+use serde::Serialize;
+
+use super::common::{sanitize_file_name, time_to_iso8601, unique_path, validate_path};
+use super::exhibits::ExhibitFile;
+
+#[derive(Serialize, Clone)]
+pub struct EmailAddress {
+    pub name: Option<String>,
+    pub address: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct EmailAttachmentInfo {
+    pub index: usize,
+    pub file_name: String,
+    pub content_type: Option<String>,
+    pub size: u64,
+}
+
+#[derive(Serialize, Clone)]
+pub struct EmailExhibit {
+    pub path: String,
+    /// "eml" ou "msg"
+    pub format: String,
+    pub from: Option<EmailAddress>,
+    pub to: Vec<EmailAddress>,
+    pub cc: Vec<EmailAddress>,
+    /// Date d'envoi au format ISO 8601
+    pub date: Option<String>,
+    pub subject: Option<String>,
+    pub body_text: String,
+    pub attachments: Vec<EmailAttachmentInfo>,
+}
+
+/// Courriel analyse avec le contenu binaire des pieces jointes
+struct ParsedEmail {
+    exhibit: EmailExhibit,
+    attachment_data: Vec<Vec<u8>>,
+}
+
+fn email_format(path: &Path) -> Option<String> {
+    let ext = path.extension()?.to_string_lossy().to_lowercase();
+    match ext.as_str() {
+        "eml" | "msg" => Some(ext),
+        _ => None,
+    }
+}
+
+fn parse_email_file(path: &Path) -> Result<ParsedEmail, String> {
+    match email_format(path).as_deref() {
+        Some("eml") => parse_eml(path),
+        Some("msg") => parse_msg(path),
+        _ => Err("Le fichier n'est pas un courriel (.eml ou .msg)".to_string()),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// .eml (RFC 5322 / MIME)
+// ---------------------------------------------------------------------------
+
+fn eml_addresses(address: Option<&Address>) -> Vec<EmailAddress> {
+    address
+        .map(|a| {
+            a.iter()
+                .map(|addr| EmailAddress {
+                    name: addr.name().map(|n| n.to_string()),
+                    address: addr.address().map(|a| a.to_string()),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_eml(path: &Path) -> Result<ParsedEmail, String> {
+    let raw = fs::read(path)
+        .map_err(|e| format!("Impossible de lire le courriel: {}", e))?;
+
+    let message = MessageParser::default()
+        .parse(&raw[..])
+        .ok_or("Courriel illisible ou mal forme")?;
+
+    let body_text = message
+        .body_text(0)
+        .map(|b| b.to_string())
+        .unwrap_or_default();
+
+    let mut attachments = Vec::new();
+    let mut attachment_data = Vec::new();
+
+    for (index, part) in message.attachments().enumerate() {
+        let content_type = part.content_type().map(|ct| match ct.subtype() {
+            Some(sub) => format!("{}/{}", ct.ctype(), sub),
+            None => ct.ctype().to_string(),
+        });
+        let file_name = part
+            .attachment_name()
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| format!("piece-jointe-{}", index + 1));
+        let data = part.contents().to_vec();
+
+        attachments.push(EmailAttachmentInfo {
+            index,
+            file_name,
+            content_type,
+            size: data.len() as u64,
+        });
+        attachment_data.push(data);
+    }
+
+    Ok(ParsedEmail {
+        exhibit: EmailExhibit {
+            path: path.to_string_lossy().to_string(),
+            format: "eml".to_string(),
+            from: eml_addresses(message.from()).into_iter().next(),
+            to: eml_addresses(message.to()),
+            cc: eml_addresses(message.cc()),
+            date: message.date().map(|d| d.to_rfc3339()),
+            subject: message.subject().map(|s| s.to_string()),
+            body_text,
+            attachments,
+        },
+        attachment_data,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// .msg (Outlook, fichier compose OLE / MS-OXMSG)
+// ---------------------------------------------------------------------------
+
+const PROP_SUBJECT: u16 = 0x0037;
+const PROP_CLIENT_SUBMIT_TIME: u16 = 0x0039;
+const PROP_SENDER_NAME: u16 = 0x0C1A;
+const PROP_SENDER_EMAIL: u16 = 0x0C1F;
+const PROP_RECIPIENT_TYPE: u16 = 0x0C15;
+const PROP_DELIVERY_TIME: u16 = 0x0E06;
+const PROP_BODY: u16 = 0x1000;
+const PROP_BODY_HTML: u16 = 0x1013;
+const PROP_DISPLAY_NAME: u16 = 0x3001;
+const PROP_EMAIL_ADDRESS: u16 = 0x3003;
+const PROP_ATTACH_DATA: u16 = 0x3701;
+const PROP_ATTACH_FILENAME: u16 = 0x3704;
+const PROP_ATTACH_LONG_FILENAME: u16 = 0x3707;
+const PROP_ATTACH_MIME_TAG: u16 = 0x370E;
+const PROP_SMTP_ADDRESS: u16 = 0x39FE;
+const PROP_SENDER_SMTP_ADDRESS: u16 = 0x5D01;
+
+const PT_LONG: u16 = 0x0003;
+const PT_SYSTIME: u16 = 0x0040;
+
+/// Secondes entre 1601-01-01 (FILETIME) et 1970-01-01
+const FILETIME_UNIX_OFFSET: u64 = 11_644_473_600;
+
+type MsgFile = cfb::CompoundFile<fs::File>;
+
+fn read_msg_stream(msg: &mut MsgFile, path: &str) -> Option<Vec<u8>> {
+    let mut stream = msg.open_stream(path).ok()?;
+    let mut data = Vec::new();
+    stream.read_to_end(&mut data).ok()?;
+    Some(data)
+}
+
+/// Lit une propriete chaine, en Unicode (001F) ou en 8 bits (001E)
+fn read_msg_string(msg: &mut MsgFile, storage: &str, prop: u16) -> Option<String> {
+    if let Some(data) = read_msg_stream(msg, &format!("{}/__substg1.0_{:04X}001F", storage, prop)) {
+        let units: Vec<u16> = data
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        let value = String::from_utf16_lossy(&units).trim_end_matches('\0').to_string();
+        return Some(value).filter(|v| !v.is_empty());
+    }
+
+    read_msg_stream(msg, &format!("{}/__substg1.0_{:04X}001E", storage, prop))
+        .map(|data| String::from_utf8_lossy(&data).trim_end_matches('\0').to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn read_msg_binary(msg: &mut MsgFile, storage: &str, prop: u16) -> Option<Vec<u8>> {
+    read_msg_stream(msg, &format!("{}/__substg1.0_{:04X}0102", storage, prop))
+}
+
+/// Recherche une propriete de taille fixe dans le flux "__properties_version1.0".
+/// `header_len` vaut 32 pour le message racine et 8 pour les destinataires et
+/// pieces jointes.
+fn read_msg_fixed(
+    msg: &mut MsgFile,
+    storage: &str,
+    header_len: usize,
+    prop: u16,
+    prop_type: u16,
+) -> Option<[u8; 8]> {
+    let data = read_msg_stream(msg, &format!("{}/__properties_version1.0", storage))?;
+
+    data.get(header_len..)?
+        .chunks_exact(16)
+        .find(|entry| {
+            let tag = u32::from_le_bytes([entry[0], entry[1], entry[2], entry[3]]);
+            (tag >> 16) as u16 == prop && (tag & 0xFFFF) as u16 == prop_type
+        })
+        .map(|entry| {
+            let mut value = [0u8; 8];
+            value.copy_from_slice(&entry[8..16]);
+            value
+        })
+}
+
+fn filetime_to_iso8601(value: [u8; 8]) -> Option<String> {
+    let ticks = u64::from_le_bytes(value);
+    let secs = (ticks / 10_000_000).checked_sub(FILETIME_UNIX_OFFSET)?;
+    Some(time_to_iso8601(secs))
+}
+
+fn msg_storages(msg: &MsgFile, prefix: &str) -> Vec<String> {
+    let mut names: Vec<String> = msg
+        .read_root_storage()
+        .filter(|e| e.is_storage() && e.name().starts_with(prefix))
+        .map(|e| format!("/{}", e.name()))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Conversion HTML -> texte minimale, pour les messages sans corps texte
+fn html_to_text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    let mut tag = String::new();
+
+    for c in html.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                tag.clear();
+            }
+            '>' if in_tag => {
+                in_tag = false;
+                // Nom exact : "<pre>" ou "<param>" ne sont pas des "<p>"
+                let name = tag
+                    .trim_start_matches('/')
+                    .split(|c: char| c.is_whitespace() || c == '/')
+                    .next()
+                    .unwrap_or("")
+                    .to_lowercase();
+                if matches!(name.as_str(), "br" | "p" | "div" | "tr") {
+                    text.push('\n');
+                }
+            }
+            _ if in_tag => tag.push(c),
+            _ => text.push(c),
+        }
+    }
+
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn parse_msg(path: &Path) -> Result<ParsedEmail, String> {
+    let mut msg = cfb::open(path)
+        .map_err(|e| format!("Impossible de lire le courriel Outlook: {}", e))?;
+    let root = "";
+
+    let subject = read_msg_string(&mut msg, root, PROP_SUBJECT);
+    let sender_address = read_msg_string(&mut msg, root, PROP_SENDER_SMTP_ADDRESS)
+        .or_else(|| read_msg_string(&mut msg, root, PROP_SENDER_EMAIL));
+    let sender_name = read_msg_string(&mut msg, root, PROP_SENDER_NAME);
+    let from = if sender_name.is_some() || sender_address.is_some() {
+        Some(EmailAddress { name: sender_name, address: sender_address })
+    } else {
+        None
+    };
+
+    let date = read_msg_fixed(&mut msg, root, 32, PROP_CLIENT_SUBMIT_TIME, PT_SYSTIME)
+        .or_else(|| read_msg_fixed(&mut msg, root, 32, PROP_DELIVERY_TIME, PT_SYSTIME))
+        .and_then(filetime_to_iso8601);
+
+    let body_text = read_msg_string(&mut msg, root, PROP_BODY)
+        .or_else(|| {
+            read_msg_binary(&mut msg, root, PROP_BODY_HTML)
+                .map(|html| html_to_text(&String::from_utf8_lossy(&html)))
+        })
+        .unwrap_or_default();
+
+    // Destinataires : type 1 = A, 2 = Cc, 3 = Cci (ignore)
+    let mut to = Vec::new();
+    let mut cc = Vec::new();
+    for storage in msg_storages(&msg, "__recip_version1.0_") {
+        let recipient = EmailAddress {
+            name: read_msg_string(&mut msg, &storage, PROP_DISPLAY_NAME),
+            address: read_msg_string(&mut msg, &storage, PROP_SMTP_ADDRESS)
+                .or_else(|| read_msg_string(&mut msg, &storage, PROP_EMAIL_ADDRESS)),
+        };
+        let recipient_type = read_msg_fixed(&mut msg, &storage, 8, PROP_RECIPIENT_TYPE, PT_LONG)
+            .map(|v| u32::from_le_bytes([v[0], v[1], v[2], v[3]]))
+            .unwrap_or(1);
+
+        match recipient_type {
+            1 => to.push(recipient),
+            2 => cc.push(recipient),
+            _ => {}
+        }
+    }
+
+    let mut attachments = Vec::new();
+    let mut attachment_data = Vec::new();
+    for (index, storage) in msg_storages(&msg, "__attach_version1.0_").into_iter().enumerate() {
+        // Les messages incorpores (storage et non flux binaire) ne sont pas extraits
+        let data = read_msg_binary(&mut msg, &storage, PROP_ATTACH_DATA).unwrap_or_default();
+        let file_name = read_msg_string(&mut msg, &storage, PROP_ATTACH_LONG_FILENAME)
+            .or_else(|| read_msg_string(&mut msg, &storage, PROP_ATTACH_FILENAME))
+            .or_else(|| read_msg_string(&mut msg, &storage, PROP_DISPLAY_NAME))
+            .unwrap_or_else(|| format!("piece-jointe-{}", index + 1));
+
+        attachments.push(EmailAttachmentInfo {
+            index,
+            file_name,
+            content_type: read_msg_string(&mut msg, &storage, PROP_ATTACH_MIME_TAG),
+            size: data.len() as u64,
+        });
+        attachment_data.push(data);
+    }
+
+    Ok(ParsedEmail {
+        exhibit: EmailExhibit {
+            path: path.to_string_lossy().to_string(),
+            format: "msg".to_string(),
+            from,
+            to,
+            cc,
+            date,
+            subject,
+            body_text,
+            attachments,
+        },
+        attachment_data,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Rendu PDF
+// ---------------------------------------------------------------------------
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 56.0;
+const FONT_SIZE: f32 = 10.0;
+const LINE_HEIGHT: f32 = 13.0;
+const LABEL_WIDTH: f32 = 90.0;
+/// Largeur moyenne d'un caractere Helvetica, en fraction du corps
+const AVG_CHAR_WIDTH: f32 = 0.52;
+
+/// Encodage WinAnsi des polices standard PDF ; les caracteres hors table
+/// sont remplaces par "?"
+fn to_win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            '\u{20}'..='\u{7E}' | '\u{A0}'..='\u{FF}' => c as u8,
+            '€' => 0x80,
+            '‚' => 0x82,
+            '„' => 0x84,
+            '…' => 0x85,
+            'Œ' => 0x8C,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            'œ' => 0x9C,
+            '\t' => b' ',
+            _ => b'?',
+        })
+        .collect()
+}
+
+fn wrap_text(text: &str, width: f32) -> Vec<String> {
+    let max_chars = ((width / (FONT_SIZE * AVG_CHAR_WIDTH)) as usize).max(10);
+    let mut lines = Vec::new();
+
+    for paragraph in text.lines() {
+        let mut current = String::new();
+        for word in paragraph.split_whitespace() {
+            let word_len = word.chars().count();
+            let current_len = current.chars().count();
+
+            if current_len > 0 && current_len + 1 + word_len > max_chars {
+                lines.push(std::mem::take(&mut current));
+            }
+
+            if word_len > max_chars {
+                // Mot trop long (URL...) : coupe franche
+                let chars: Vec<char> = word.chars().collect();
+                for chunk in chars.chunks(max_chars) {
+                    if !current.is_empty() {
+                        lines.push(std::mem::take(&mut current));
+                    }
+                    current = chunk.iter().collect();
+                }
+                continue;
+            }
+
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+        lines.push(current);
+    }
+
+    lines
+}
+
+fn format_addresses(addresses: &[EmailAddress]) -> String {
+    addresses
+        .iter()
+        .map(|a| match (&a.name, &a.address) {
+            (Some(name), Some(address)) if name != address => format!("{} <{}>", name, address),
+            (_, Some(address)) => address.clone(),
+            (Some(name), None) => name.clone(),
+            (None, None) => String::new(),
+        })
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// "2026-03-12T14:05:00+01:00" -> "12/03/2026 14:05"
+fn format_email_date(iso: &str) -> String {
+    if iso.len() >= 16 && iso.is_char_boundary(16) {
+        format!("{}/{}/{} {}", &iso[8..10], &iso[5..7], &iso[0..4], &iso[11..16])
+    } else {
+        iso.to_string()
+    }
+}
+
+enum PdfLine {
+    Header { label: String, value: String },
+    Rule,
+    Body(String),
+}
+
+fn text_op(font: &str, x: f32, y: f32, text: &str) -> Vec<Operation> {
+    vec![
+        Operation::new("BT", vec![]),
+        Operation::new("Tf", vec![font.into(), FONT_SIZE.into()]),
+        Operation::new("Td", vec![x.into(), y.into()]),
+        Operation::new("Tj", vec![Object::String(to_win_ansi(text), StringFormat::Literal)]),
+        Operation::new("ET", vec![]),
+    ]
+}
+
+fn write_email_pdf(email: &EmailExhibit, output: &Path) -> Result<(), String> {
+    let value_width = PAGE_WIDTH - 2.0 * MARGIN - LABEL_WIDTH;
+    let body_width = PAGE_WIDTH - 2.0 * MARGIN;
+
+    // Bloc d'en-tete standard
+    let mut header: Vec<(&str, String)> = vec![
+        ("De :", email.from.as_ref().map(|f| format_addresses(std::slice::from_ref(f))).unwrap_or_default()),
+        ("À :", format_addresses(&email.to)),
+    ];
+    if !email.cc.is_empty() {
+        header.push(("Cc :", format_addresses(&email.cc)));
+    }
+    header.push(("Date :", email.date.as_deref().map(format_email_date).unwrap_or_default()));
+    header.push(("Objet :", email.subject.clone().unwrap_or_default()));
+    if !email.attachments.is_empty() {
+        let names: Vec<&str> = email.attachments.iter().map(|a| a.file_name.as_str()).collect();
+        header.push(("Pièces jointes :", names.join(", ")));
+    }
+
+    let mut lines = Vec::new();
+    for (label, value) in header {
+        for (i, wrapped) in wrap_text(&value, value_width).into_iter().enumerate() {
+            lines.push(PdfLine::Header {
+                label: if i == 0 { label.to_string() } else { String::new() },
+                value: wrapped,
+            });
+        }
+    }
+    lines.push(PdfLine::Rule);
+    for wrapped in wrap_text(&email.body_text, body_width) {
+        lines.push(PdfLine::Body(wrapped));
+    }
+
+    // Pagination
+    let lines_per_page = ((PAGE_HEIGHT - 2.0 * MARGIN) / LINE_HEIGHT) as usize;
+    let pages: Vec<&[PdfLine]> = lines.chunks(lines_per_page.max(1)).collect();
+    let page_count = pages.len();
+
+    let mut doc = Document::with_version("1.5");
+    let pages_id = doc.new_object_id();
+    let font_regular = doc.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type1",
+        "BaseFont" => "Helvetica",
+        "Encoding" => "WinAnsiEncoding",
+    });
+    let font_bold = doc.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type1",
+        "BaseFont" => "Helvetica-Bold",
+        "Encoding" => "WinAnsiEncoding",
+    });
+    let resources_id = doc.add_object(dictionary! {
+        "Font" => dictionary! {
+            "F1" => font_regular,
+            "F2" => font_bold,
+        },
+    });
+
+    let mut kids = Vec::new();
+    for (page_index, page_lines) in pages.iter().enumerate() {
+        let mut operations = Vec::new();
+        let mut y = PAGE_HEIGHT - MARGIN - FONT_SIZE;
+
+        for line in page_lines.iter() {
+            match line {
+                PdfLine::Header { label, value } => {
+                    if !label.is_empty() {
+                        operations.extend(text_op("F2", MARGIN, y, label));
+                    }
+                    operations.extend(text_op("F1", MARGIN + LABEL_WIDTH, y, value));
+                }
+                PdfLine::Rule => {
+                    let rule_y = y + FONT_SIZE / 2.0;
+                    operations.push(Operation::new("w", vec![0.5.into()]));
+                    operations.push(Operation::new("m", vec![MARGIN.into(), rule_y.into()]));
+                    operations.push(Operation::new("l", vec![(PAGE_WIDTH - MARGIN).into(), rule_y.into()]));
+                    operations.push(Operation::new("S", vec![]));
+                }
+                PdfLine::Body(text) => {
+                    operations.extend(text_op("F1", MARGIN, y, text));
+                }
+            }
+            y -= LINE_HEIGHT;
+        }
+
+        let footer = format!("Page {} / {}", page_index + 1, page_count);
+        operations.extend(text_op("F1", PAGE_WIDTH - MARGIN - 60.0, MARGIN / 2.0, &footer));
+
+        let content = Content { operations };
+        let encoded = content.encode()
+            .map_err(|e| format!("Erreur generation PDF: {}", e))?;
+        let content_id = doc.add_object(Stream::new(dictionary! {}, encoded));
+        let page_id = doc.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Contents" => content_id,
+        });
+        kids.push(page_id.into());
+    }
+
+    doc.objects.insert(pages_id, Object::Dictionary(dictionary! {
+        "Type" => "Pages",
+        "Kids" => kids,
+        "Count" => page_count as i64,
+        "Resources" => resources_id,
+        "MediaBox" => vec![0.into(), 0.into(), PAGE_WIDTH.into(), PAGE_HEIGHT.into()],
+    }));
+    let catalog_id = doc.add_object(dictionary! {
+        "Type" => "Catalog",
+        "Pages" => pages_id,
+    });
+    doc.trailer.set("Root", catalog_id);
+    doc.compress();
+
+    doc.save(output)
+        .map_err(|e| format!("Impossible d'ecrire le PDF: {}", e))?;
+
+    Ok(())
+}
+
+fn email_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "courriel".to_string())
+}
+
+fn output_dir_for(email_path: &Path, output_dir: Option<String>) -> Result<PathBuf, String> {
+    match output_dir {
+        Some(dir) => {
+            let dir = validate_path(&dir)?;
+            if !dir.is_dir() {
+                return Err("Le chemin n'est pas un dossier".to_string());
+            }
+            Ok(dir)
+        }
+        None => email_path
+            .parent()
+            .map(|p| p.to_path_buf())
+            .ok_or_else(|| "Dossier du courriel introuvable".to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn parse_email_exhibit(path: String) -> Result<EmailExhibit, String> {
+    let email_path = validate_path(&path)?;
+    Ok(parse_email_file(&email_path)?.exhibit)
+}
+
+/// Extrait les pieces jointes comme pieces distinctes, nommees
+/// "<courriel> - <piece jointe>" dans le dossier du courriel par defaut
+#[tauri::command]
+pub async fn extract_email_attachments(
+    path: String,
+    output_dir: Option<String>,
+    indices: Option<Vec<usize>>,
+) -> Result<Vec<ExhibitFile>, String> {
+    let email_path = validate_path(&path)?;
+    let parsed = parse_email_file(&email_path)?;
+    let target_dir = output_dir_for(&email_path, output_dir)?;
+    let stem = email_stem(&email_path);
+
+    let mut extracted = Vec::new();
+    for (info, data) in parsed.exhibit.attachments.iter().zip(parsed.attachment_data.iter()) {
+        if let Some(ref wanted) = indices {
+            if !wanted.contains(&info.index) {
+                continue;
+            }
+        }
+        if data.is_empty() {
+            continue;
+        }
+
+        let file_name = sanitize_file_name(&format!("{} - {}", stem, info.file_name));
+        let target = unique_path(&target_dir, &file_name);
+
+        fs::write(&target, data)
+            .map_err(|e| format!("Impossible d'ecrire la piece jointe: {}", e))?;
+
+        extracted.push(ExhibitFile {
+            name: target.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+            path: target.to_string_lossy().to_string(),
+        });
+    }
+
+    Ok(extracted)
+}
+
+/// Produit une piece PDF imprimable du courriel (en-tete + corps)
+#[tauri::command]
+pub async fn render_email_exhibit_pdf(
+    path: String,
+    output_path: Option<String>,
+) -> Result<ExhibitFile, String> {
+    let email_path = validate_path(&path)?;
+    let parsed = parse_email_file(&email_path)?;
+
+    let target = match output_path {
+        Some(p) => validate_path(&p)?,
+        None => {
+            let dir = output_dir_for(&email_path, None)?;
+            unique_path(&dir, &format!("{}.pdf", email_stem(&email_path)))
+        }
+    };
+
+    write_email_pdf(&parsed.exhibit, &target)?;
+
+    Ok(ExhibitFile {
+        name: target.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+        path: target.to_string_lossy().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn html_to_text_breaks_only_on_block_tags() {
+        assert_eq!(html_to_text("<p>a</p><pre>b</pre>c<br/>d"), "\na\nbc\nd");
+        assert_eq!(html_to_text("<P class=\"x\">a&amp;b</P>"), "\na&b\n");
+        assert_eq!(html_to_text("<param name=\"x\">a<tr>b"), "a\nb");
+    }
+}
@@ -0,0 +1,361 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use super::common::{chrono_iso8601_now, sha256_file, validate_path};
+use super::exhibits::{collect_exhibit_files, validate_exhibit_dir};
+
+/// Registre de tracabilite d'un dossier de pieces (une entree JSON par ligne,
+/// jamais reecrit : on ne fait qu'ajouter)
+const LEDGER_FILE_NAME: &str = ".citadelle-ledger.jsonl";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LedgerEntry {
+    /// "recorded", "renamed", "stamped" ou "replaced"
+    pub event: String,
+    pub file_name: String,
+    pub previous_name: Option<String>,
+    pub sha256: String,
+    pub size: u64,
+    /// Provenance declaree (ex. "Client - courriel du 12/03"), ou piece source d'un tampon
+    pub origin: Option<String>,
+    /// Fichier tamponne produit a partir de cette piece
+    pub output_path: Option<String>,
+    pub timestamp: String,
+}
+
+#[derive(Serialize)]
+pub struct LedgerPieceStatus {
+    pub file_name: String,
+    /// "ok", "modified", "missing" ou "untracked"
+    pub status: String,
+    pub expected_sha256: Option<String>,
+    pub actual_sha256: Option<String>,
+    pub recorded_at: Option<String>,
+    pub origin: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct LedgerVerification {
+    pub folder: String,
+    pub checked: usize,
+    pub modified: usize,
+    pub missing: usize,
+    pub untracked: usize,
+    pub pieces: Vec<LedgerPieceStatus>,
+}
+
+/// Etat courant d'une piece apres rejeu du registre
+struct TrackedPiece {
+    sha256: String,
+    recorded_at: String,
+    origin: Option<String>,
+}
+
+fn ledger_path(dir: &Path) -> PathBuf {
+    dir.join(LEDGER_FILE_NAME)
+}
+
+fn read_ledger(dir: &Path) -> Result<Vec<LedgerEntry>, String> {
+    let path = ledger_path(dir);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Impossible de lire le registre: {}", e))?;
+
+    // Une ligne corrompue ne doit pas rendre tout le registre inutilisable
+    Ok(content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| serde_json::from_str::<LedgerEntry>(l).ok())
+        .collect())
+}
+
+fn append_ledger(dir: &Path, entries: &[LedgerEntry]) -> Result<(), String> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(ledger_path(dir))
+        .map_err(|e| format!("Impossible d'ouvrir le registre: {}", e))?;
+
+    for entry in entries {
+        let line = serde_json::to_string(entry)
+            .map_err(|e| format!("Failed to serialize ledger entry: {}", e))?;
+        writeln!(file, "{}", line)
+            .map_err(|e| format!("Impossible d'ecrire le registre: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Rejoue le registre pour obtenir le contenu attendu de chaque piece, par nom
+fn replay_ledger(entries: &[LedgerEntry]) -> HashMap<String, TrackedPiece> {
+    let mut pieces: HashMap<String, TrackedPiece> = HashMap::new();
+
+    for entry in entries {
+        match entry.event.as_str() {
+            "recorded" => {
+                pieces.entry(entry.file_name.clone()).or_insert(TrackedPiece {
+                    sha256: entry.sha256.clone(),
+                    recorded_at: entry.timestamp.clone(),
+                    origin: entry.origin.clone(),
+                });
+            }
+            // Un renommage conserve l'empreinte d'origine : une modification
+            // anterieure au renommage reste detectee
+            "renamed" => {
+                let previous = entry.previous_name.as_ref().and_then(|p| pieces.remove(p));
+                let piece = match previous {
+                    Some(piece) => piece,
+                    None => TrackedPiece {
+                        sha256: entry.sha256.clone(),
+                        recorded_at: entry.timestamp.clone(),
+                        origin: entry.origin.clone(),
+                    },
+                };
+                pieces.insert(entry.file_name.clone(), piece);
+            }
+            // Remplacement volontaire (nouveau tampon sur le meme fichier)
+            "replaced" => {
+                pieces.insert(entry.file_name.clone(), TrackedPiece {
+                    sha256: entry.sha256.clone(),
+                    recorded_at: entry.timestamp.clone(),
+                    origin: entry.origin.clone(),
+                });
+            }
+            // Le tampon produit un nouveau fichier : la piece source reste inchangee
+            _ => {}
+        }
+    }
+
+    pieces
+}
+
+fn new_entry(event: &str, path: &Path, previous_name: Option<String>, origin: Option<String>) -> Result<LedgerEntry, String> {
+    let size = fs::metadata(path)
+        .map(|m| m.len())
+        .map_err(|e| format!("Impossible de lire la piece: {}", e))?;
+
+    Ok(LedgerEntry {
+        event: event.to_string(),
+        file_name: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+        previous_name,
+        sha256: sha256_file(path)?,
+        size,
+        origin,
+        output_path: None,
+        timestamp: chrono_iso8601_now(),
+    })
+}
+
+fn parent_dir(path: &Path) -> Result<PathBuf, String> {
+    path.parent()
+        .map(|p| p.to_path_buf())
+        .ok_or_else(|| "Dossier de la piece introuvable".to_string())
+}
+
+/// Enregistre les pieces du dossier qui n'ont encore jamais ete vues
+#[tauri::command]
+pub async fn record_exhibit_ledger(path: String, origin: Option<String>) -> Result<Vec<LedgerEntry>, String> {
+    let dir_path = validate_exhibit_dir(&path)?;
+    let tracked = replay_ledger(&read_ledger(&dir_path)?);
+
+    let mut new_entries = Vec::new();
+    for file in collect_exhibit_files(&dir_path)? {
+        if !tracked.contains_key(&file.name) {
+            new_entries.push(new_entry("recorded", Path::new(&file.path), None, origin.clone())?);
+        }
+    }
+
+    append_ledger(&dir_path, &new_entries)?;
+    Ok(new_entries)
+}
+
+/// Chemins valides d'un renommage et ancien nom ; le registre est propre a un
+/// dossier, un deplacement vers un autre dossier n'est donc pas un renommage
+fn checked_rename(old_path: &str, new_path: &str) -> Result<(PathBuf, String), String> {
+    let old_path = validate_path(old_path)?;
+    let new_path = validate_path(new_path)?;
+
+    if parent_dir(&old_path)? != parent_dir(&new_path)? {
+        return Err("Le renommage doit rester dans le dossier de la piece".to_string());
+    }
+
+    let previous_name = old_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| "Ancien nom de la piece introuvable".to_string())?;
+
+    Ok((new_path, previous_name))
+}
+
+/// Trace un renommage (numerotation P[N], declassement...) effectue sur disque
+#[tauri::command]
+pub async fn record_exhibit_rename(old_path: String, new_path: String) -> Result<LedgerEntry, String> {
+    let (new_path, previous_name) = checked_rename(&old_path, &new_path)?;
+
+    let entry = new_entry("renamed", &new_path, Some(previous_name), None)?;
+    append_ledger(&parent_dir(&new_path)?, std::slice::from_ref(&entry))?;
+
+    Ok(entry)
+}
+
+/// Trace l'apposition d'un tampon : l'evenement est ajoute au registre de la
+/// piece source, et le fichier tamponne est enregistre dans son propre dossier
+/// avec la piece source pour provenance
+#[tauri::command]
+pub async fn record_exhibit_stamp(source_path: String, output_path: String) -> Result<LedgerEntry, String> {
+    let source = validate_path(&source_path)?;
+    let output = validate_path(&output_path)?;
+
+    let mut entry = new_entry("stamped", &source, None, None)?;
+    entry.output_path = Some(output.to_string_lossy().to_string());
+    append_ledger(&parent_dir(&source)?, std::slice::from_ref(&entry))?;
+
+    let origin = format!("Tampon de {} (sha256 {})", entry.file_name, entry.sha256);
+    let output_dir = parent_dir(&output)?;
+    let output_name = output.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+
+    // Un nouveau tampon sur le meme nom remplace le fichier precedent
+    let output_entry = if replay_ledger(&read_ledger(&output_dir)?).contains_key(&output_name) {
+        new_entry("replaced", &output, None, Some(origin))?
+    } else {
+        new_entry("recorded", &output, None, Some(origin))?
+    };
+    append_ledger(&output_dir, &[output_entry])?;
+
+    Ok(entry)
+}
+
+/// Compare chaque piece a son empreinte enregistree
+#[tauri::command]
+pub async fn verify_exhibit_ledger(path: String) -> Result<LedgerVerification, String> {
+    let dir_path = validate_exhibit_dir(&path)?;
+    let tracked = replay_ledger(&read_ledger(&dir_path)?);
+    let files = collect_exhibit_files(&dir_path)?;
+
+    let mut pieces = Vec::new();
+
+    for file in &files {
+        let actual = sha256_file(Path::new(&file.path)).ok();
+
+        let status = match tracked.get(&file.name) {
+            Some(piece) => LedgerPieceStatus {
+                file_name: file.name.clone(),
+                status: if actual.as_deref() == Some(piece.sha256.as_str()) { "ok" } else { "modified" }.to_string(),
+                expected_sha256: Some(piece.sha256.clone()),
+                actual_sha256: actual,
+                recorded_at: Some(piece.recorded_at.clone()),
+                origin: piece.origin.clone(),
+            },
+            None => LedgerPieceStatus {
+                file_name: file.name.clone(),
+                status: "untracked".to_string(),
+                expected_sha256: None,
+                actual_sha256: actual,
+                recorded_at: None,
+                origin: None,
+            },
+        };
+        pieces.push(status);
+    }
+
+    for (name, piece) in &tracked {
+        if !files.iter().any(|f| &f.name == name) {
+            pieces.push(LedgerPieceStatus {
+                file_name: name.clone(),
+                status: "missing".to_string(),
+                expected_sha256: Some(piece.sha256.clone()),
+                actual_sha256: None,
+                recorded_at: Some(piece.recorded_at.clone()),
+                origin: piece.origin.clone(),
+            });
+        }
+    }
+
+    pieces.sort_by_key(|p| p.file_name.to_lowercase());
+
+    let count = |status: &str| pieces.iter().filter(|p| p.status == status).count();
+
+    Ok(LedgerVerification {
+        folder: dir_path.to_string_lossy().to_string(),
+        checked: pieces.len(),
+        modified: count("modified"),
+        missing: count("missing"),
+        untracked: count("untracked"),
+        pieces,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(event: &str, file_name: &str, previous_name: Option<&str>, sha256: &str) -> LedgerEntry {
+        LedgerEntry {
+            event: event.to_string(),
+            file_name: file_name.to_string(),
+            previous_name: previous_name.map(str::to_string),
+            sha256: sha256.to_string(),
+            size: 0,
+            origin: None,
+            output_path: None,
+            timestamp: String::new(),
+        }
+    }
+
+    #[test]
+    fn replay_swap_through_temp_names() {
+        // P1 <-> P2 en deux passes, etapes temporaires tracees
+        let entries = vec![
+            entry("recorded", "P1 a.pdf", None, "aaa"),
+            entry("recorded", "P2 b.pdf", None, "bbb"),
+            entry("renamed", "__TEMP_1_0 a.pdf", Some("P1 a.pdf"), "aaa"),
+            entry("renamed", "__TEMP_1_1 b.pdf", Some("P2 b.pdf"), "bbb"),
+            entry("renamed", "P2 a.pdf", Some("__TEMP_1_0 a.pdf"), "aaa"),
+            entry("renamed", "P1 b.pdf", Some("__TEMP_1_1 b.pdf"), "bbb"),
+        ];
+
+        let pieces = replay_ledger(&entries);
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces["P2 a.pdf"].sha256, "aaa");
+        assert_eq!(pieces["P1 b.pdf"].sha256, "bbb");
+    }
+
+    #[test]
+    fn replay_keeps_original_hash_across_rename() {
+        let entries = vec![
+            entry("recorded", "a.pdf", None, "aaa"),
+            entry("renamed", "P1 a.pdf", Some("a.pdf"), "modifie"),
+        ];
+
+        let pieces = replay_ledger(&entries);
+        assert_eq!(pieces["P1 a.pdf"].sha256, "aaa");
+        assert!(!pieces.contains_key("a.pdf"));
+    }
+
+    #[test]
+    fn rename_stays_in_the_exhibit_folder() {
+        let dir = std::env::temp_dir().join(format!("citadelle-ledger-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("autre")).unwrap();
+        fs::write(dir.join("P1 a.pdf"), "a").unwrap();
+        let path = |name: &str| dir.join(name).to_string_lossy().to_string();
+
+        let (new_path, previous_name) = checked_rename(&path("a.pdf"), &path("P1 a.pdf")).unwrap();
+        assert_eq!(new_path, dir.join("P1 a.pdf").canonicalize().unwrap());
+        assert_eq!(previous_name, "a.pdf");
+
+        assert!(checked_rename(&path("autre/a.pdf"), &path("P1 a.pdf")).is_err());
+        assert!(checked_rename(&path("autre/../a.pdf"), &path("P1 a.pdf")).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,249 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::common::{ensure_dir_exists, get_citadelle_dir, validate_path};
+
+const DEFAULT_THUMBNAIL_SIZE: u32 = 256;
+const MAX_THUMBNAIL_SIZE: u32 = 1024;
+const DEFAULT_MAX_AGE_DAYS: u64 = 30;
+
+#[derive(Serialize)]
+pub struct ExhibitThumbnail {
+    pub source_path: String,
+    pub thumbnail_path: String,
+    pub width: u32,
+    pub height: u32,
+    pub cached: bool,
+}
+
+#[derive(Serialize)]
+pub struct ThumbnailPruneResult {
+    pub removed: usize,
+    pub freed_bytes: u64,
+    pub remaining: usize,
+}
+
+fn get_thumbnail_cache_dir() -> Result<PathBuf, String> {
+    let dir = get_citadelle_dir().join("cache").join("thumbnails");
+    ensure_dir_exists(&dir)?;
+    Ok(dir)
+}
+
+/// Cle de cache : chemin + date de modification + taille demandee
+fn thumbnail_cache_key(source: &Path, size: u32) -> Result<String, String> {
+    let modified = fs::metadata(source)
+        .and_then(|m| m.modified())
+        .map_err(|e| format!("Impossible de lire le fichier: {}", e))?;
+    let mtime = modified
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+
+    let mut hasher = Sha256::new();
+    hasher.update(source.to_string_lossy().as_bytes());
+    hasher.update(b"\0");
+    hasher.update(mtime.to_string().as_bytes());
+
+    Ok(format!("{:x}-{}", hasher.finalize(), size))
+}
+
+fn file_kind(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_string_lossy().to_lowercase();
+    match ext.as_str() {
+        "pdf" => Some("pdf"),
+        "jpg" | "jpeg" | "png" | "gif" | "tiff" | "bmp" | "webp" => Some("image"),
+        _ => None,
+    }
+}
+
+/// Rend la premiere page d'un PDF en PNG via l'outil systeme disponible
+/// (Quick Look sur macOS, pdftoppm de Poppler ailleurs)
+fn render_pdf_first_page(source: &Path, size: u32, work_dir: &Path) -> Result<PathBuf, String> {
+    #[cfg(target_os = "macos")]
+    {
+        let preview = Command::new("qlmanage")
+            .arg("-t")
+            .arg("-s")
+            .arg(size.to_string())
+            .arg("-o")
+            .arg(work_dir)
+            .arg(source)
+            .output();
+
+        if let Ok(output) = preview {
+            let file_name = source.file_name().unwrap_or_default().to_string_lossy();
+            let rendered = work_dir.join(format!("{}.png", file_name));
+            if output.status.success() && rendered.exists() {
+                return Ok(rendered);
+            }
+        }
+    }
+
+    let prefix = work_dir.join("page");
+    let output = Command::new("pdftoppm")
+        .arg("-png")
+        .arg("-singlefile")
+        .arg("-f")
+        .arg("1")
+        .arg("-l")
+        .arg("1")
+        .arg("-scale-to")
+        .arg(size.to_string())
+        .arg(source)
+        .arg(&prefix)
+        .output()
+        .map_err(|_| "Aucun moteur de rendu PDF disponible (pdftoppm introuvable)".to_string())?;
+
+    let rendered = prefix.with_extension("png");
+    if !output.status.success() || !rendered.exists() {
+        return Err(format!(
+            "Echec du rendu PDF: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(rendered)
+}
+
+fn generate_thumbnail(source: &Path, size: u32, target: &Path) -> Result<(u32, u32), String> {
+    let img = match file_kind(source) {
+        Some("image") => image::open(source)
+            .map_err(|e| format!("Image illisible: {}", e))?,
+        Some("pdf") => {
+            let work_dir = std::env::temp_dir()
+                .join(format!("citadelle-thumb-{}", uuid::Uuid::new_v4()));
+            ensure_dir_exists(&work_dir)?;
+
+            let result = render_pdf_first_page(source, size, &work_dir)
+                .and_then(|rendered| {
+                    image::open(&rendered).map_err(|e| format!("Rendu PDF illisible: {}", e))
+                });
+            let _ = fs::remove_dir_all(&work_dir);
+            result?
+        }
+        _ => return Err("Type de fichier sans apercu".to_string()),
+    };
+
+    // Ecrite a cote puis renommee : une lecture concurrente du cache ne voit
+    // jamais une miniature a moitie ecrite. Une miniature temporaire orpheline
+    // reste un .png, elaguee comme les autres
+    let thumbnail = img.thumbnail(size, size);
+    let dir = target.parent().ok_or("Chemin de miniature invalide")?;
+    let tmp = dir.join(format!(".{}.tmp.png", uuid::Uuid::new_v4()));
+    let written = thumbnail
+        .save_with_format(&tmp, image::ImageFormat::Png)
+        .map_err(|e| format!("Impossible d'ecrire la miniature: {}", e))
+        .and_then(|_| {
+            fs::rename(&tmp, target)
+                .map_err(|e| format!("Impossible d'ecrire la miniature: {}", e))
+        });
+    if written.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    written?;
+
+    Ok((thumbnail.width(), thumbnail.height()))
+}
+
+/// Renvoie la miniature PNG d'une piece (PDF : premiere page), generee au
+/// besoin dans ~/.citadelle/cache/thumbnails
+#[tauri::command]
+pub async fn get_exhibit_thumbnail(path: String, size: Option<u32>) -> Result<ExhibitThumbnail, String> {
+    let source = validate_path(&path)?;
+    if !source.is_file() {
+        return Err("Le fichier n'existe pas".to_string());
+    }
+
+    let size = size.unwrap_or(DEFAULT_THUMBNAIL_SIZE).clamp(16, MAX_THUMBNAIL_SIZE);
+    let cache_dir = get_thumbnail_cache_dir()?;
+    let target = cache_dir.join(format!("{}.png", thumbnail_cache_key(&source, size)?));
+
+    if target.exists() {
+        if let Ok((width, height)) = image::image_dimensions(&target) {
+            // Rafraichit la date pour que l'elagage conserve les miniatures utilisees
+            if let Ok(file) = fs::File::options().write(true).open(&target) {
+                let _ = file.set_modified(SystemTime::now());
+            }
+
+            return Ok(ExhibitThumbnail {
+                source_path: source.to_string_lossy().to_string(),
+                thumbnail_path: target.to_string_lossy().to_string(),
+                width,
+                height,
+                cached: true,
+            });
+        }
+    }
+
+    let (width, height) = generate_thumbnail(&source, size, &target)?;
+
+    Ok(ExhibitThumbnail {
+        source_path: source.to_string_lossy().to_string(),
+        thumbnail_path: target.to_string_lossy().to_string(),
+        width,
+        height,
+        cached: false,
+    })
+}
+
+/// Supprime les miniatures inutilisees depuis `max_age_days` jours, puis les
+/// plus anciennes tant que le cache depasse `max_size_mb`
+#[tauri::command]
+pub async fn prune_thumbnail_cache(
+    max_age_days: Option<u64>,
+    max_size_mb: Option<u64>,
+) -> Result<ThumbnailPruneResult, String> {
+    let cache_dir = get_thumbnail_cache_dir()?;
+    let max_age = Duration::from_secs(max_age_days.unwrap_or(DEFAULT_MAX_AGE_DAYS) * 86400);
+    let now = SystemTime::now();
+
+    let mut entries: Vec<(PathBuf, SystemTime, u64)> = fs::read_dir(&cache_dir)
+        .map_err(|e| format!("Impossible de lire le cache: {}", e))?
+        .flatten()
+        .filter(|e| e.path().extension().map(|x| x == "png").unwrap_or(false))
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            Some((e.path(), meta.modified().unwrap_or(UNIX_EPOCH), meta.len()))
+        })
+        .collect();
+
+    let mut removed = 0;
+    let mut freed_bytes = 0;
+
+    entries.retain(|(path, modified, len)| {
+        let expired = now.duration_since(*modified).map(|age| age > max_age).unwrap_or(false);
+        if expired && fs::remove_file(path).is_ok() {
+            removed += 1;
+            freed_bytes += len;
+            return false;
+        }
+        true
+    });
+
+    if let Some(max_mb) = max_size_mb {
+        let max_bytes = max_mb * 1024 * 1024;
+        let mut total: u64 = entries.iter().map(|(_, _, len)| len).sum();
+
+        // Les plus anciennes d'abord
+        entries.sort_by_key(|(_, modified, _)| *modified);
+        entries.retain(|(path, _, len)| {
+            if total > max_bytes && fs::remove_file(path).is_ok() {
+                total -= len;
+                removed += 1;
+                freed_bytes += len;
+                return false;
+            }
+            true
+        });
+    }
+
+    Ok(ThumbnailPruneResult {
+        removed,
+        freed_bytes,
+        remaining: entries.len(),
+    })
+}
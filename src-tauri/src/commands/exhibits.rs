@@ -1,16 +1,16 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use serde::Serialize;
 
 use super::common::validate_path;
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct ExhibitFile {
     pub name: String,
     pub path: String,
 }
 
-fn is_exhibit_file(path: &Path) -> bool {
+pub fn is_exhibit_file(path: &Path) -> bool {
     if let Some(ext) = path.extension() {
         let ext = ext.to_string_lossy().to_lowercase();
         matches!(ext.as_str(),
@@ -25,21 +25,11 @@ fn is_exhibit_file(path: &Path) -> bool {
     }
 }
 
-#[tauri::command]
-pub async fn list_exhibit_files(path: String) -> Result<Vec<ExhibitFile>, String> {
-    let dir_path = validate_path(&path)?;
-
-    if !dir_path.exists() {
-        return Err("Le dossier n'existe pas".to_string());
-    }
-
-    if !dir_path.is_dir() {
-        return Err("Le chemin n'est pas un dossier".to_string());
-    }
-
+/// Liste les pieces d'un dossier (non recursif), triees par nom
+pub fn collect_exhibit_files(dir_path: &Path) -> Result<Vec<ExhibitFile>, String> {
     let mut files = Vec::new();
 
-    let entries = fs::read_dir(&dir_path)
+    let entries = fs::read_dir(dir_path)
         .map_err(|e| format!("Impossible de lire le dossier: {}", e))?;
 
     for entry in entries.flatten() {
@@ -59,3 +49,24 @@ pub async fn list_exhibit_files(path: String) -> Result<Vec<ExhibitFile>, String
 
     Ok(files)
 }
+
+/// Valide qu'un chemin designe un dossier de pieces existant
+pub fn validate_exhibit_dir(path: &str) -> Result<PathBuf, String> {
+    let dir_path = validate_path(path)?;
+
+    if !dir_path.exists() {
+        return Err("Le dossier n'existe pas".to_string());
+    }
+
+    if !dir_path.is_dir() {
+        return Err("Le chemin n'est pas un dossier".to_string());
+    }
+
+    Ok(dir_path)
+}
+
+#[tauri::command]
+pub async fn list_exhibit_files(path: String) -> Result<Vec<ExhibitFile>, String> {
+    let dir_path = validate_exhibit_dir(&path)?;
+    collect_exhibit_files(&dir_path)
+}
@@ -0,0 +1,677 @@
+use std::collections::BTreeMap;
+use std::fs;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use super::common::{chrono_iso8601_now, is_leap_year, time_to_iso8601, validate_path};
+use super::goldocab_connection::GoldocabState;
+use super::goldocab_db::build_client_display_name;
+use super::goldocab_schema::{row_i64, row_text, GoldocabSchema};
+
+/// Duree par defaut d'une audience exportee avec une heure, en minutes
+const HEARING_DURATION_MINUTES: u32 = 60;
+
+/// Duree par defaut d'une echeance exportee avec une heure, en minutes
+const ITEM_DURATION_MINUTES: u32 = 30;
+
+/// Fuseau du cabinet : les heures GoldoCab sont des heures locales
+const ICS_TZID: &str = "Europe/Paris";
+
+/// Definition du fuseau exigee par RFC 5545 pour chaque TZID utilise
+/// (heure d'ete du dernier dimanche de mars au dernier dimanche d'octobre)
+const ICS_VTIMEZONE: [&str; 17] = [
+    "BEGIN:VTIMEZONE",
+    "TZID:Europe/Paris",
+    "BEGIN:DAYLIGHT",
+    "TZOFFSETFROM:+0100",
+    "TZOFFSETTO:+0200",
+    "TZNAME:CEST",
+    "DTSTART:19700329T020000",
+    "RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU",
+    "END:DAYLIGHT",
+    "BEGIN:STANDARD",
+    "TZOFFSETFROM:+0200",
+    "TZOFFSETTO:+0100",
+    "TZNAME:CET",
+    "DTSTART:19701025T030000",
+    "RRULE:FREQ=YEARLY;BYMONTH=10;BYDAY=-1SU",
+    "END:STANDARD",
+    "END:VTIMEZONE",
+];
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GoldocabAgendaEntry {
+    /// "audience" ou "echeance"
+    pub kind: String,
+    /// Jour au format AAAA-MM-JJ
+    pub date: String,
+    /// Heure HH:MM, absente pour une journee entiere
+    pub time: Option<String>,
+    pub title: String,
+    pub dossier_id: Option<i64>,
+    pub dossier_name: Option<String>,
+    pub client_name: Option<String>,
+    pub juridiction: Option<String>,
+    pub numero_rg: Option<String>,
+    pub item_id: Option<i64>,
+    pub urgence: Option<i64>,
+    pub gtd_phase: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GoldocabAgendaDay {
+    pub date: String,
+    pub entries: Vec<GoldocabAgendaEntry>,
+}
+
+/// Filtres de l'agenda ; l'urgence et la phase GTD ne portent que sur les echeances
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct GoldocabAgendaFilters {
+    #[serde(default)]
+    pub min_urgency: Option<i64>,
+    #[serde(default)]
+    pub gtd_phases: Option<Vec<String>>,
+    #[serde(default)]
+    pub include_hearings: Option<bool>,
+    #[serde(default)]
+    pub include_items: Option<bool>,
+}
+
+#[derive(Serialize)]
+pub struct GoldocabAgendaExport {
+    pub path: String,
+    pub event_count: usize,
+}
+
+/// Verifie une borne de l'intervalle (AAAA-MM-JJ)
+fn validate_day(day: &str) -> Result<String, String> {
+    match parse_day(day) {
+        Some(parsed) if parsed.len() == day.trim().len() => Ok(parsed),
+        _ => Err(format!("Date invalide (attendu AAAA-MM-JJ): {}", day)),
+    }
+}
+
+fn parse_day(value: &str) -> Option<String> {
+    let value = value.trim();
+    let bytes = value.as_bytes();
+    if bytes.len() < 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return None;
+    }
+
+    let year: i64 = value.get(0..4)?.parse().ok()?;
+    let month: u32 = value.get(5..7)?.parse().ok()?;
+    let day: u32 = value.get(8..10)?.parse().ok()?;
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    Some(format!("{:04}-{:02}-{:02}", year, month, day))
+}
+
+/// Un horodatage Unix a au moins 9 chiffres (1973 et apres) : "20260312" n'en est pas un
+const MIN_EPOCH_DIGITS: usize = 9;
+
+/// Jours depuis le 1970-01-01 (calendrier gregorien proleptique)
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (i64::from(month) + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Dernier dimanche du mois, en jours depuis epoch
+fn last_sunday(year: i64, month: u32) -> i64 {
+    let last = days_from_civil(year, month, days_in_month(year, month));
+    // Le 1970-01-01 etait un jeudi
+    last - (last + 4).rem_euclid(7)
+}
+
+/// Heure de Paris d'un instant UTC, sans fuseau : "AAAA-MM-JJTHH:MM:SS".
+/// Heure d'ete du dernier dimanche de mars au dernier dimanche d'octobre, 1 h UTC
+fn utc_to_paris(epoch: i64) -> Option<String> {
+    let year: i64 = time_to_iso8601(u64::try_from(epoch).ok()?).get(0..4)?.parse().ok()?;
+    let switch = |month| last_sunday(year, month) * 86400 + 3600;
+    let offset = if (switch(3)..switch(10)).contains(&epoch) { 7200 } else { 3600 };
+
+    let local = time_to_iso8601(u64::try_from(epoch + offset).ok()?);
+    Some(local.trim_end_matches('Z').to_string())
+}
+
+/// Decalage UTC explicite d'un horodatage ISO 8601 ("Z", "+02:00"), en secondes
+fn utc_offset(value: &str) -> Option<i64> {
+    if value.len() <= 10 {
+        return None;
+    }
+    if value.ends_with(['Z', 'z']) {
+        return Some(0);
+    }
+
+    let zone = value.get(value.len() - 6..)?;
+    let sign = match zone.as_bytes()[0] {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    if zone.as_bytes()[3] != b':' {
+        return None;
+    }
+    let hours: i64 = zone.get(1..3)?.parse().ok()?;
+    let minutes: i64 = zone.get(4..6)?.parse().ok()?;
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+fn is_epoch(value: &str) -> bool {
+    let integer = value.split_once('.').map_or(value, |(i, _)| i);
+    integer.len() >= MIN_EPOCH_DIGITS
+        && value.chars().filter(|c| *c == '.').count() <= 1
+        && value.chars().all(|c| c.is_ascii_digit() || c == '.')
+}
+
+/// Date GoldoCab (texte "AAAA-MM-JJ", "AAAA-MM-JJ HH:MM:SS", ISO 8601, ou
+/// horodatage Unix) -> (jour, heure eventuelle). Minuit signifie "journee entiere".
+/// Les instants UTC (horodatage, ISO avec "Z" ou decalage) sont ramenes a
+/// l'heure de Paris ; un texte sans fuseau est deja l'heure locale du cabinet
+pub fn parse_goldocab_date(value: &str) -> Option<(String, Option<String>)> {
+    let value = value.trim();
+
+    if is_epoch(value) {
+        let epoch = value.parse::<f64>().ok()? as i64;
+        return parse_goldocab_date(&utc_to_paris(epoch)?);
+    }
+
+    let day = parse_day(value)?;
+
+    if let Some(offset) = utc_offset(value) {
+        let hours: i64 = value.get(11..13)?.parse().ok()?;
+        let minutes: i64 = value.get(14..16)?.parse().ok()?;
+        let (year, month, d) = (
+            day[0..4].parse().ok()?,
+            day[5..7].parse().ok()?,
+            day[8..10].parse().ok()?,
+        );
+        let epoch = days_from_civil(year, month, d) * 86400 + hours * 3600 + minutes * 60 - offset;
+        return parse_goldocab_date(&utc_to_paris(epoch)?);
+    }
+
+    let time = value
+        .get(10..16)
+        .filter(|t| t.starts_with(' ') || t.starts_with('T'))
+        .map(|t| t[1..].to_string())
+        .filter(|t| t.as_bytes().get(2) == Some(&b':') && t != "00:00");
+
+    Some((day, time))
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn next_day(day: &str) -> String {
+    let year: i64 = day[0..4].parse().unwrap_or(1970);
+    let month: u32 = day[5..7].parse().unwrap_or(1);
+    let d: u32 = day[8..10].parse().unwrap_or(1);
+
+    if d < days_in_month(year, month) {
+        format!("{:04}-{:02}-{:02}", year, month, d + 1)
+    } else if month < 12 {
+        format!("{:04}-{:02}-01", year, month + 1)
+    } else {
+        format!("{:04}-01-01", year + 1)
+    }
+}
+
+fn read_hearings(
+    conn: &rusqlite::Connection,
+    schema: &GoldocabSchema,
+    start: &str,
+    end: &str,
+) -> Result<Vec<GoldocabAgendaEntry>, String> {
+    if !schema.has_field("dossiers", "dateAudience") {
+        return Ok(Vec::new());
+    }
+
+    let d = |f: &str| schema.col("d", "dossiers", f);
+    let c = |f: &str| schema.col("c", "clients", f);
+    let sql = format!(
+        "SELECT d.id, {}, {}, {}, {}, {}, {}, {}, {}
+         FROM dossiers d
+         LEFT JOIN clients c ON {} = c.id
+         WHERE {} IS NOT NULL",
+        d("nom"), d("dateAudience"), d("juridiction"), d("numeroRG"),
+        c("nom"), c("prenom"), c("denomination"), c("typeClient"),
+        d("clientID"), d("dateAudience"),
+    );
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Erreur requete audiences: {}", e))?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            let client_name = build_client_display_name(
+                &row_text(row, 5),
+                &row_text(row, 6),
+                &row_text(row, 7),
+                &row_text(row, 8),
+            );
+            Ok((row.get::<_, i64>(0)?, row_text(row, 1), row_text(row, 2), row_text(row, 3), row_text(row, 4), client_name))
+        })
+        .map_err(|e| format!("Erreur lecture audiences: {}", e))?;
+
+    let mut entries = Vec::new();
+    for (dossier_id, nom, date, juridiction, numero_rg, client_name) in rows.flatten() {
+        let Some((day, time)) = date.as_deref().and_then(parse_goldocab_date) else {
+            continue;
+        };
+        if day.as_str() < start || day.as_str() > end {
+            continue;
+        }
+
+        entries.push(GoldocabAgendaEntry {
+            kind: "audience".to_string(),
+            date: day,
+            time,
+            title: format!("Audience - {}", nom.as_deref().unwrap_or("Dossier sans nom")),
+            dossier_id: Some(dossier_id),
+            dossier_name: nom,
+            client_name,
+            juridiction,
+            numero_rg,
+            item_id: None,
+            urgence: None,
+            gtd_phase: None,
+        });
+    }
+
+    Ok(entries)
+}
+
+fn read_due_items(
+    conn: &rusqlite::Connection,
+    schema: &GoldocabSchema,
+    start: &str,
+    end: &str,
+    filters: &GoldocabAgendaFilters,
+) -> Result<Vec<GoldocabAgendaEntry>, String> {
+    if !schema.has_table("items") || !schema.has_field("items", "dateEcheance") {
+        return Ok(Vec::new());
+    }
+
+    let i = |f: &str| schema.col("i", "items", f);
+    let d = |f: &str| schema.col("d", "dossiers", f);
+
+    // Seules les taches sont des echeances ; sans colonne estTache, tout item date compte
+    let task_condition = if schema.has_field("items", "estTache") {
+        format!("AND COALESCE({}, 0) != 0", i("estTache"))
+    } else {
+        String::new()
+    };
+
+    let sql = format!(
+        "SELECT i.id, {}, {}, {}, {}, {}, {}, {}
+         FROM items i
+         LEFT JOIN dossiers d ON {} = d.id
+         WHERE {} IS NOT NULL {}",
+        i("titre"), i("dateEcheance"), i("urgence"), i("gtdPhase"), i("dossierID"),
+        d("nom"), d("numeroRG"),
+        i("dossierID"), i("dateEcheance"), task_condition,
+    );
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Erreur requete echeances: {}", e))?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row_text(row, 1),
+                row_text(row, 2),
+                row_i64(row, 3),
+                row_text(row, 4),
+                row_i64(row, 5),
+                row_text(row, 6),
+                row_text(row, 7),
+            ))
+        })
+        .map_err(|e| format!("Erreur lecture echeances: {}", e))?;
+
+    let phases: Option<Vec<String>> = filters
+        .gtd_phases
+        .as_ref()
+        .filter(|p| !p.is_empty())
+        .map(|p| p.iter().map(|s| s.to_lowercase()).collect());
+
+    let mut entries = Vec::new();
+    for (item_id, titre, date, urgence, gtd_phase, dossier_id, dossier_name, numero_rg) in rows.flatten() {
+        let Some((day, time)) = date.as_deref().and_then(parse_goldocab_date) else {
+            continue;
+        };
+        if day.as_str() < start || day.as_str() > end {
+            continue;
+        }
+
+        if let Some(min) = filters.min_urgency {
+            if urgence.unwrap_or(0) < min {
+                continue;
+            }
+        }
+
+        if let Some(phases) = &phases {
+            let phase = gtd_phase.as_deref().unwrap_or("").to_lowercase();
+            if !phases.contains(&phase) {
+                continue;
+            }
+        }
+
+        entries.push(GoldocabAgendaEntry {
+            kind: "echeance".to_string(),
+            date: day,
+            time,
+            title: titre.unwrap_or_else(|| "Tache sans titre".to_string()),
+            dossier_id,
+            dossier_name,
+            client_name: None,
+            juridiction: None,
+            numero_rg,
+            item_id: Some(item_id),
+            urgence,
+            gtd_phase,
+        });
+    }
+
+    Ok(entries)
+}
+
+fn build_agenda(
+    conn: &rusqlite::Connection,
+    schema: &GoldocabSchema,
+    start: &str,
+    end: &str,
+    filters: &GoldocabAgendaFilters,
+) -> Result<Vec<GoldocabAgendaDay>, String> {
+    let start = validate_day(start)?;
+    let end = validate_day(end)?;
+    if start > end {
+        return Err("La date de debut est posterieure a la date de fin".to_string());
+    }
+
+    schema.require("dossiers")?;
+
+    let mut entries = Vec::new();
+    if filters.include_hearings.unwrap_or(true) {
+        entries.extend(read_hearings(conn, schema, &start, &end)?);
+    }
+    if filters.include_items.unwrap_or(true) {
+        entries.extend(read_due_items(conn, schema, &start, &end, filters)?);
+    }
+
+    // Journees entieres en tete, puis par heure ; audiences avant echeances
+    entries.sort_by(|a, b| {
+        a.date
+            .cmp(&b.date)
+            .then_with(|| a.time.is_some().cmp(&b.time.is_some()))
+            .then_with(|| a.time.cmp(&b.time))
+            .then_with(|| a.kind.cmp(&b.kind))
+            .then_with(|| b.urgence.unwrap_or(0).cmp(&a.urgence.unwrap_or(0)))
+    });
+
+    let mut days: BTreeMap<String, Vec<GoldocabAgendaEntry>> = BTreeMap::new();
+    for entry in entries {
+        days.entry(entry.date.clone()).or_default().push(entry);
+    }
+
+    Ok(days
+        .into_iter()
+        .map(|(date, entries)| GoldocabAgendaDay { date, entries })
+        .collect())
+}
+
+/// Audiences et echeances de tous les dossiers entre deux dates incluses
+/// (AAAA-MM-JJ), regroupees par jour
+#[tauri::command]
+pub async fn get_goldocab_agenda(
+    state: State<'_, GoldocabState>,
+    start: String,
+    end: String,
+    filters: Option<GoldocabAgendaFilters>,
+) -> Result<Vec<GoldocabAgendaDay>, String> {
+    let filters = filters.unwrap_or_default();
+    let key = format!(
+        "agenda:{}:{}:{}",
+        start,
+        end,
+        serde_json::to_string(&filters).unwrap_or_default()
+    );
+
+    state
+        .query_cached(key, move |conn, schema| build_agenda(conn, schema, &start, &end, &filters))
+        .await
+}
+
+/// Echappement des valeurs texte iCalendar (RFC 5545, 3.3.11)
+fn ics_escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Coupe les lignes a 75 octets, les suites commencant par une espace
+fn ics_fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut width = 0;
+
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+
+    folded.push_str("\r\n");
+    folded
+}
+
+fn ics_date(day: &str) -> String {
+    day.replace('-', "")
+}
+
+fn ics_date_time(day: &str, time: &str) -> String {
+    format!("{}T{}00", ics_date(day), time.replace(':', ""))
+}
+
+fn ics_event(entry: &GoldocabAgendaEntry, stamp: &str) -> Vec<String> {
+    let uid = match entry.item_id {
+        Some(id) => format!("goldocab-item-{}@citadelle", id),
+        None => format!(
+            "goldocab-audience-{}-{}@citadelle",
+            entry.dossier_id.unwrap_or(0),
+            ics_date(&entry.date)
+        ),
+    };
+
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{}", uid),
+        format!("DTSTAMP:{}", stamp),
+    ];
+
+    // Heure locale du cabinet, rattachee a son fuseau ; tout evenement avec
+    // une heure a une duree, sinon certains agendas l'affichent sans longueur
+    match &entry.time {
+        Some(time) => {
+            let minutes = if entry.kind == "audience" {
+                HEARING_DURATION_MINUTES
+            } else {
+                ITEM_DURATION_MINUTES
+            };
+            lines.push(format!("DTSTART;TZID={}:{}", ICS_TZID, ics_date_time(&entry.date, time)));
+            lines.push(format!("DURATION:PT{}M", minutes));
+        }
+        None => {
+            lines.push(format!("DTSTART;VALUE=DATE:{}", ics_date(&entry.date)));
+            lines.push(format!("DTEND;VALUE=DATE:{}", ics_date(&next_day(&entry.date))));
+        }
+    }
+
+    lines.push(format!("SUMMARY:{}", ics_escape(&entry.title)));
+
+    let mut description = Vec::new();
+    if let Some(dossier) = &entry.dossier_name {
+        description.push(format!("Dossier : {}", dossier));
+    }
+    if let Some(client) = &entry.client_name {
+        description.push(format!("Client : {}", client));
+    }
+    if let Some(rg) = &entry.numero_rg {
+        description.push(format!("RG : {}", rg));
+    }
+    if let Some(urgence) = entry.urgence {
+        description.push(format!("Urgence : {}", urgence));
+    }
+    if !description.is_empty() {
+        lines.push(format!("DESCRIPTION:{}", ics_escape(&description.join("\n"))));
+    }
+
+    if let Some(juridiction) = &entry.juridiction {
+        lines.push(format!("LOCATION:{}", ics_escape(juridiction)));
+    }
+
+    lines.push(format!(
+        "CATEGORIES:{}",
+        if entry.kind == "audience" { "Audience" } else { "Echeance" }
+    ));
+    lines.push("END:VEVENT".to_string());
+    lines
+}
+
+/// Exporte l'agenda de l'intervalle au format iCalendar (.ics)
+#[tauri::command]
+pub async fn export_goldocab_agenda_ics(
+    state: State<'_, GoldocabState>,
+    start: String,
+    end: String,
+    filters: Option<GoldocabAgendaFilters>,
+    output_path: String,
+) -> Result<GoldocabAgendaExport, String> {
+    let output = validate_path(&output_path)?;
+    let filters = filters.unwrap_or_default();
+    let days = state
+        .query(move |conn, schema| build_agenda(conn, schema, &start, &end, &filters))
+        .await?;
+
+    // 2026-03-12T09:30:00Z -> 20260312T093000Z
+    let stamp: String = chrono_iso8601_now().chars().filter(|c| *c != '-' && *c != ':').collect();
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Citadelle//Agenda GoldoCab//FR".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+    ];
+    lines.extend(ICS_VTIMEZONE.iter().map(|l| l.to_string()));
+
+    let mut event_count = 0;
+    for entry in days.iter().flat_map(|d| d.entries.iter()) {
+        lines.extend(ics_event(entry, &stamp));
+        event_count += 1;
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let content: String = lines.iter().map(|l| ics_fold(l)).collect();
+    fs::write(&output, content)
+        .map_err(|e| format!("Impossible d'ecrire le calendrier: {}", e))?;
+
+    Ok(GoldocabAgendaExport {
+        path: output.to_string_lossy().to_string(),
+        event_count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_goldocab_date_keeps_local_text_as_is() {
+        assert_eq!(parse_goldocab_date("2026-03-12"), Some(("2026-03-12".to_string(), None)));
+        assert_eq!(
+            parse_goldocab_date("2026-03-12 09:30:00"),
+            Some(("2026-03-12".to_string(), Some("09:30".to_string())))
+        );
+        assert_eq!(parse_goldocab_date("2026-03-12 00:00:00"), Some(("2026-03-12".to_string(), None)));
+    }
+
+    #[test]
+    fn parse_goldocab_date_converts_utc_instants_to_paris_time() {
+        let at = |day: &str, time: &str| Some((day.to_string(), Some(time.to_string())));
+
+        // Heure d'hiver, UTC+1
+        assert_eq!(parse_goldocab_date("2026-03-12T14:05:00.000Z"), at("2026-03-12", "15:05"));
+        assert_eq!(parse_goldocab_date("1773311400"), at("2026-03-12", "11:30"));
+        assert_eq!(parse_goldocab_date("1773311400.5"), at("2026-03-12", "11:30"));
+        // Heure d'ete, UTC+2, jusqu'au passage du dimanche 25 octobre 2026 a 1 h UTC
+        assert_eq!(parse_goldocab_date("2026-07-01T08:00:00Z"), at("2026-07-01", "10:00"));
+        assert_eq!(parse_goldocab_date("2026-10-25T00:30:00Z"), at("2026-10-25", "02:30"));
+        assert_eq!(parse_goldocab_date("2026-10-25T01:30:00Z"), at("2026-10-25", "02:30"));
+        assert_eq!(parse_goldocab_date("2026-03-29T00:59:00Z"), at("2026-03-29", "01:59"));
+        assert_eq!(parse_goldocab_date("2026-03-29T01:00:00Z"), at("2026-03-29", "03:00"));
+        // Decalage explicite et changement de jour
+        assert_eq!(parse_goldocab_date("2026-03-12T09:30:00+01:00"), at("2026-03-12", "09:30"));
+        assert_eq!(parse_goldocab_date("2026-12-31T23:30:00Z"), at("2027-01-01", "00:30"));
+    }
+
+    #[test]
+    fn parse_goldocab_date_requires_nine_digits_for_epochs() {
+        assert_eq!(parse_goldocab_date("20260312"), None);
+        assert_eq!(parse_goldocab_date("123"), None);
+    }
+
+    #[test]
+    fn parse_goldocab_date_rejects_invalid_days() {
+        assert_eq!(parse_goldocab_date("2026-02-30"), None);
+        assert_eq!(parse_goldocab_date("2026-13-01"), None);
+        assert_eq!(parse_goldocab_date("12/03/2026"), None);
+        assert_eq!(parse_goldocab_date("-1"), None);
+        assert_eq!(parse_goldocab_date(""), None);
+        assert_eq!(parse_goldocab_date("2024-02-29"), Some(("2024-02-29".to_string(), None)));
+    }
+
+    #[test]
+    fn ics_fold_splits_long_lines_at_75_octets() {
+        let line = format!("SUMMARY:{}", "a".repeat(100));
+        let folded = ics_fold(&line);
+        let parts: Vec<&str> = folded.trim_end_matches("\r\n").split("\r\n").collect();
+
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].len(), 75);
+        assert!(parts[1].starts_with(' '));
+        assert_eq!(format!("{}{}", parts[0], &parts[1][1..]), line);
+    }
+
+    #[test]
+    fn ics_fold_never_splits_a_character() {
+        let line = format!("LOCATION:{}", "é".repeat(60));
+        let folded = ics_fold(&line);
+
+        for part in folded.trim_end_matches("\r\n").split("\r\n") {
+            assert!(part.len() <= 75);
+        }
+        assert_eq!(folded.replace("\r\n ", "").trim_end(), line);
+        assert_eq!(ics_fold("VERSION:2.0"), "VERSION:2.0\r\n");
+    }
+}
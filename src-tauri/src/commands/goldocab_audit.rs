@@ -0,0 +1,209 @@
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+
+use super::common::{chrono_iso8601_now, ensure_dir_exists, get_citadelle_dir, validate_path};
+
+/// Journal des echanges avec GoldoCab (une entree JSON par ligne, jamais
+/// reecrit : on ne fait qu'ajouter)
+const AUDIT_FILE_NAME: &str = "goldocab-audit.jsonl";
+
+/// Colonnes de l'export CSV, dans l'ordre
+const CSV_COLUMNS: [&str; 8] = [
+    "timestamp", "event", "path", "target_path", "sha256", "dossier_id", "session_id", "detail",
+];
+
+/// Une operation sur un document echange avec GoldoCab
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct GoldocabAuditEntry {
+    /// "session_started", "session_completed", "session_cancelled", "export",
+    /// "note_created", "note_saved" ou "note_deleted"
+    pub event: String,
+    /// ISO 8601 (UTC)
+    pub timestamp: String,
+    /// Document concerne (original d'une session, note, export)
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Fichier produit, s'il differe (session enregistree sous un autre nom...)
+    #[serde(default)]
+    pub target_path: Option<String>,
+    /// Empreinte du contenu apres l'operation (avant, pour une suppression)
+    #[serde(default)]
+    pub sha256: Option<String>,
+    #[serde(default)]
+    pub dossier_id: Option<String>,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub detail: Option<String>,
+}
+
+/// Filtres du journal ; les bornes sont des dates AAAA-MM-JJ ou des horodatages
+/// ISO 8601, incluses
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct GoldocabAuditQuery {
+    #[serde(default)]
+    pub dossier_id: Option<String>,
+    #[serde(default)]
+    pub from: Option<String>,
+    #[serde(default)]
+    pub to: Option<String>,
+    #[serde(default)]
+    pub event: Option<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct GoldocabAuditExport {
+    pub path: String,
+    pub entry_count: usize,
+}
+
+fn audit_path() -> PathBuf {
+    get_citadelle_dir().join(AUDIT_FILE_NAME)
+}
+
+fn append_audit(entry: &GoldocabAuditEntry) -> Result<(), String> {
+    ensure_dir_exists(&get_citadelle_dir())?;
+
+    let line = serde_json::to_string(entry)
+        .map_err(|e| format!("Failed to serialize audit entry: {}", e))?;
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(audit_path())
+        .map_err(|e| format!("Impossible d'ouvrir le journal GoldoCab: {}", e))?;
+
+    // Une seule ecriture par ligne : pas d'entrelacement entre deux commandes
+    file.write_all(format!("{}\n", line).as_bytes())
+        .map_err(|e| format!("Impossible d'ecrire le journal GoldoCab: {}", e))
+}
+
+/// Ajoute une entree au journal. Ne fait jamais echouer l'operation journalisee :
+/// une entree qui ne peut etre ecrite est perdue
+pub fn record_audit(mut entry: GoldocabAuditEntry) {
+    if entry.timestamp.is_empty() {
+        entry.timestamp = chrono_iso8601_now();
+    }
+    let _ = append_audit(&entry);
+}
+
+fn read_audit() -> Result<Vec<GoldocabAuditEntry>, String> {
+    let path = audit_path();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Impossible de lire le journal GoldoCab: {}", e))?;
+
+    // Une ligne corrompue ne doit pas rendre tout le journal inutilisable
+    Ok(content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| serde_json::from_str::<GoldocabAuditEntry>(l).ok())
+        .collect())
+}
+
+fn matches(entry: &GoldocabAuditEntry, query: &GoldocabAuditQuery) -> bool {
+    if let Some(dossier_id) = query.dossier_id.as_deref().map(str::trim).filter(|d| !d.is_empty()) {
+        if entry.dossier_id.as_deref() != Some(dossier_id) {
+            return false;
+        }
+    }
+    if let Some(event) = &query.event {
+        if &entry.event != event {
+            return false;
+        }
+    }
+    // Les horodatages ISO se comparent comme des chaines ; une borne "AAAA-MM-JJ"
+    // couvre toute la journee en ne comparant que le prefixe
+    if let Some(from) = &query.from {
+        if entry.timestamp.as_str() < from.as_str() {
+            return false;
+        }
+    }
+    if let Some(to) = &query.to {
+        let prefix = entry.timestamp.get(..to.len()).unwrap_or(&entry.timestamp);
+        if prefix > to.as_str() {
+            return false;
+        }
+    }
+    true
+}
+
+/// Entrees correspondant aux filtres, plus recentes d'abord
+fn query_audit(query: &GoldocabAuditQuery) -> Result<Vec<GoldocabAuditEntry>, String> {
+    let mut entries: Vec<GoldocabAuditEntry> = read_audit()?
+        .into_iter()
+        .filter(|e| matches(e, query))
+        .collect();
+    entries.reverse();
+
+    if let Some(limit) = query.limit {
+        entries.truncate(limit);
+    }
+    Ok(entries)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_row(entry: &GoldocabAuditEntry) -> String {
+    let opt = |v: &Option<String>| v.clone().unwrap_or_default();
+    [
+        entry.timestamp.clone(),
+        entry.event.clone(),
+        opt(&entry.path),
+        opt(&entry.target_path),
+        opt(&entry.sha256),
+        opt(&entry.dossier_id),
+        opt(&entry.session_id),
+        opt(&entry.detail),
+    ]
+    .iter()
+    .map(|v| csv_field(v))
+    .collect::<Vec<_>>()
+    .join(",")
+}
+
+#[tauri::command]
+pub async fn query_goldocab_audit_log(
+    query: Option<GoldocabAuditQuery>,
+) -> Result<Vec<GoldocabAuditEntry>, String> {
+    query_audit(&query.unwrap_or_default())
+}
+
+/// Exporte les entrees filtrees au format CSV, dans l'ordre chronologique
+#[tauri::command]
+pub async fn export_goldocab_audit_csv(
+    query: Option<GoldocabAuditQuery>,
+    output_path: String,
+) -> Result<GoldocabAuditExport, String> {
+    let output = validate_path(&output_path)?;
+    let mut entries = query_audit(&query.unwrap_or_default())?;
+    entries.reverse();
+
+    let mut content = CSV_COLUMNS.join(",");
+    content.push_str("\r\n");
+    for entry in &entries {
+        content.push_str(&csv_row(entry));
+        content.push_str("\r\n");
+    }
+
+    fs::write(&output, content)
+        .map_err(|e| format!("Impossible d'ecrire l'export CSV: {}", e))?;
+
+    Ok(GoldocabAuditExport {
+        path: output.to_string_lossy().to_string(),
+        entry_count: entries.len(),
+    })
+}
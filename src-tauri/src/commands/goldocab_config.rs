@@ -0,0 +1,273 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use serde::{Deserialize, Serialize};
+
+use super::common::{ensure_dir_exists, get_citadelle_dir};
+
+/// Variable d'environnement pointant vers la base GoldoCab
+pub const GOLDOCAB_DB_ENV_VAR: &str = "GOLDOCAB_DB_PATH";
+
+const GOLDOCAB_DB_FILE_NAME: &str = "goldocab.sqlite";
+
+/// Incremente a chaque ecriture des reglages : un chemin resolu avant est a revoir
+static SETTINGS_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Reglages Citadelle propres a l'integration GoldoCab (~/.citadelle/goldocab.json)
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct GoldocabSettings {
+    #[serde(default)]
+    pub database_path: Option<String>,
+    /// Session d'edition sans heartbeat depuis ce nombre d'heures : abandonnee
+    #[serde(default)]
+    pub session_stale_hours: Option<u64>,
+    /// Sessions terminees ou annulees archivees au-dela de ce nombre de jours
+    #[serde(default)]
+    pub session_retention_days: Option<u64>,
+    /// Commande ouvrant les URL goldocab:// a la place de open / xdg-open ;
+    /// `{url}` est remplace par l'URL, sinon elle est ajoutee en dernier argument
+    #[serde(default)]
+    pub url_handler_command: Option<String>,
+}
+
+/// Chemin de la base retenu et origine de ce choix
+pub struct GoldocabDbLocation {
+    pub path: PathBuf,
+    /// "settings", "environment" ou "default"
+    pub source: String,
+    /// Reglages illisibles, ignores pour cette resolution
+    pub settings_error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct GoldocabPathTest {
+    pub path: String,
+    pub exists: bool,
+    pub readable: bool,
+    pub is_goldocab: bool,
+    pub client_count: Option<i64>,
+    pub dossier_count: Option<i64>,
+    pub error: Option<String>,
+}
+
+fn get_goldocab_settings_path() -> PathBuf {
+    get_citadelle_dir().join("goldocab.json")
+}
+
+/// Lit les reglages ; un fichier absent donne les valeurs par defaut, un
+/// fichier illisible ou invalide une erreur
+pub fn read_goldocab_settings() -> Result<GoldocabSettings, String> {
+    read_settings_file(&get_goldocab_settings_path())
+}
+
+fn read_settings_file(path: &Path) -> Result<GoldocabSettings, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(GoldocabSettings::default()),
+        Err(e) => return Err(format!("Failed to read GoldoCab settings {}: {}", path.display(), e)),
+    };
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid GoldoCab settings {}: {}", path.display(), e))
+}
+
+pub fn write_goldocab_settings(settings: &GoldocabSettings) -> Result<(), String> {
+    ensure_dir_exists(&get_citadelle_dir())?;
+
+    let json = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize GoldoCab settings: {}", e))?;
+
+    fs::write(get_goldocab_settings_path(), json)
+        .map_err(|e| format!("Failed to write GoldoCab settings: {}", e))?;
+
+    SETTINGS_GENERATION.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Generation courante des reglages, pour savoir si un chemin resolu est perime
+pub fn settings_generation() -> u64 {
+    SETTINGS_GENERATION.load(Ordering::SeqCst)
+}
+
+/// Emplacements standard de la base selon la plateforme, par ordre de preference
+fn default_goldocab_db_candidates() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+
+    // macOS : ~/Library/Application Support, Linux : $XDG_DATA_HOME (~/.local/share),
+    // Windows : %APPDATA%
+    if let Some(data_dir) = dirs::data_dir() {
+        candidates.push(data_dir.join("GoldoCab").join(GOLDOCAB_DB_FILE_NAME));
+        candidates.push(data_dir.join("goldocab").join(GOLDOCAB_DB_FILE_NAME));
+    }
+
+    #[cfg(target_os = "windows")]
+    if let Some(local_dir) = dirs::data_local_dir() {
+        candidates.push(local_dir.join("GoldoCab").join(GOLDOCAB_DB_FILE_NAME));
+    }
+
+    #[cfg(target_os = "linux")]
+    if let Some(home) = dirs::home_dir() {
+        candidates.push(home.join(".goldocab").join(GOLDOCAB_DB_FILE_NAME));
+    }
+
+    candidates
+}
+
+/// Resout le chemin de la base : reglage Citadelle, puis variable
+/// d'environnement, puis emplacements par defaut de la plateforme ; des
+/// reglages illisibles sont ignores et signales dans `settings_error`
+pub fn resolve_goldocab_db_path() -> GoldocabDbLocation {
+    let (settings, settings_error) = match read_goldocab_settings() {
+        Ok(settings) => (settings, None),
+        Err(e) => (GoldocabSettings::default(), Some(e)),
+    };
+
+    if let Some(path) = settings.database_path.filter(|p| !p.trim().is_empty()) {
+        return GoldocabDbLocation {
+            path: PathBuf::from(path),
+            source: "settings".to_string(),
+            settings_error,
+        };
+    }
+
+    if let Ok(path) = std::env::var(GOLDOCAB_DB_ENV_VAR) {
+        if !path.trim().is_empty() {
+            return GoldocabDbLocation {
+                path: PathBuf::from(path),
+                source: "environment".to_string(),
+                settings_error,
+            };
+        }
+    }
+
+    let candidates = default_goldocab_db_candidates();
+    let path = candidates
+        .iter()
+        .find(|p| p.exists())
+        .or_else(|| candidates.first())
+        .cloned()
+        .unwrap_or_else(|| PathBuf::from(GOLDOCAB_DB_FILE_NAME));
+
+    GoldocabDbLocation {
+        path,
+        source: "default".to_string(),
+        settings_error,
+    }
+}
+
+fn test_goldocab_db_file(path: &PathBuf) -> GoldocabPathTest {
+    let mut result = GoldocabPathTest {
+        path: path.to_string_lossy().to_string(),
+        exists: path.is_file(),
+        readable: false,
+        is_goldocab: false,
+        client_count: None,
+        dossier_count: None,
+        error: None,
+    };
+
+    if !result.exists {
+        result.error = Some("Fichier introuvable".to_string());
+        return result;
+    }
+
+    let conn = match rusqlite::Connection::open_with_flags(
+        path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    ) {
+        Ok(c) => c,
+        Err(e) => {
+            result.error = Some(format!("Erreur ouverture base: {}", e));
+            return result;
+        }
+    };
+
+    // L'ouverture est paresseuse : une premiere requete confirme que c'est bien du SQLite
+    let table_count = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name IN ('clients', 'dossiers')",
+        [],
+        |row| row.get::<_, i64>(0),
+    );
+
+    match table_count {
+        Ok(count) => {
+            result.readable = true;
+            result.is_goldocab = count == 2;
+            if !result.is_goldocab {
+                result.error = Some("Tables GoldoCab (clients, dossiers) absentes".to_string());
+            }
+        }
+        Err(e) => {
+            result.error = Some(format!("Base illisible: {}", e));
+            return result;
+        }
+    }
+
+    if result.is_goldocab {
+        result.client_count = conn
+            .query_row("SELECT COUNT(*) FROM clients", [], |row| row.get(0))
+            .ok();
+        result.dossier_count = conn
+            .query_row("SELECT COUNT(*) FROM dossiers", [], |row| row.get(0))
+            .ok();
+    }
+
+    result
+}
+
+/// Teste un chemin candidat sans le memoriser
+#[tauri::command]
+pub async fn test_goldocab_db_path(path: String) -> Result<GoldocabPathTest, String> {
+    Ok(test_goldocab_db_file(&PathBuf::from(path)))
+}
+
+/// Memorise le chemin de la base dans les reglages Citadelle, apres verification
+#[tauri::command]
+pub async fn set_goldocab_db_path(path: String) -> Result<GoldocabPathTest, String> {
+    let test = test_goldocab_db_file(&PathBuf::from(&path));
+    if !test.is_goldocab {
+        return Err(test.error.unwrap_or_else(|| "Base GoldoCab invalide".to_string()));
+    }
+
+    // Un fichier invalide n'est pas ecrase : les autres reglages seraient perdus
+    let mut settings = read_goldocab_settings()?;
+    settings.database_path = Some(path);
+    write_goldocab_settings(&settings)?;
+
+    Ok(test)
+}
+
+/// Oublie le chemin configure : la resolution repasse par l'environnement et les defauts
+#[tauri::command]
+pub async fn clear_goldocab_db_path() -> Result<(), String> {
+    let mut settings = read_goldocab_settings()?;
+    settings.database_path = None;
+    write_goldocab_settings(&settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn missing_settings_are_defaults_but_invalid_ones_are_errors() {
+        let dir = std::env::temp_dir().join(format!("citadelle-settings-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("goldocab.json");
+
+        let settings = read_settings_file(&path).unwrap();
+        assert!(settings.database_path.is_none());
+
+        fs::write(&path, r#"{"database_path": "/data/goldocab.sqlite", "session_stale_hours": 6}"#).unwrap();
+        let settings = read_settings_file(&path).unwrap();
+        assert_eq!(settings.database_path.as_deref(), Some("/data/goldocab.sqlite"));
+        assert_eq!(settings.session_stale_hours, Some(6));
+
+        fs::write(&path, r#"{"database_path": "/data/goldocab.sqlite","#).unwrap();
+        let error = read_settings_file(&path).err().unwrap();
+        assert!(error.contains("Invalid GoldoCab settings"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,247 @@
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::goldocab_config::{resolve_goldocab_db_path, settings_generation};
+use super::goldocab_schema::GoldocabSchema;
+use super::goldocab_search::register_search_functions;
+
+/// Nombre de resultats de requetes gardes en memoire
+const QUERY_CACHE_CAPACITY: usize = 64;
+
+/// Identite du fichier : un remplacement atomique (nouvelle copie, restauration
+/// de sauvegarde) change l'inode, une ecriture normale non
+#[derive(PartialEq, Clone, Copy)]
+pub struct FileIdentity {
+    #[cfg(unix)]
+    inode: u64,
+    #[cfg(not(unix))]
+    created: Option<std::time::SystemTime>,
+}
+
+pub fn file_identity(path: &Path) -> Option<FileIdentity> {
+    let metadata = std::fs::metadata(path).ok()?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        Some(FileIdentity { inode: metadata.ino() })
+    }
+
+    #[cfg(not(unix))]
+    {
+        Some(FileIdentity { created: metadata.created().ok() })
+    }
+}
+
+pub fn open_goldocab_db_at(path: &Path) -> Result<rusqlite::Connection, String> {
+    if !path.exists() {
+        return Err("GOLDOCAB_UNAVAILABLE".to_string());
+    }
+    rusqlite::Connection::open_with_flags(
+        path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(|e| format!("Erreur ouverture base GoldoCab: {}", e))
+}
+
+pub fn data_version(conn: &rusqlite::Connection) -> Option<i64> {
+    conn.query_row("PRAGMA data_version", [], |row| row.get(0)).ok()
+}
+
+struct OpenDb {
+    conn: rusqlite::Connection,
+    path: PathBuf,
+    identity: Option<FileIdentity>,
+    schema: GoldocabSchema,
+    data_version: i64,
+}
+
+/// Resultats recents, valables tant que `data_version` ne bouge pas
+#[derive(Default)]
+struct QueryCache {
+    entries: HashMap<String, serde_json::Value>,
+    order: VecDeque<String>,
+}
+
+impl QueryCache {
+    fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        self.entries.get(key)
+    }
+
+    fn insert(&mut self, key: String, value: serde_json::Value) {
+        if self.entries.insert(key.clone(), value).is_none() {
+            self.order.push_back(key);
+        }
+        while self.order.len() > QUERY_CACHE_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+fn open_db(path: &Path, identity: Option<FileIdentity>) -> Result<OpenDb, String> {
+    let conn = open_goldocab_db_at(path)?;
+    register_search_functions(&conn)?;
+    let version = data_version(&conn)
+        .ok_or_else(|| "Base GoldoCab illisible".to_string())?;
+    let schema = GoldocabSchema::detect(&conn)?;
+
+    Ok(OpenDb {
+        conn,
+        path: path.to_path_buf(),
+        identity,
+        schema,
+        data_version: version,
+    })
+}
+
+#[derive(Default)]
+struct Inner {
+    db: Option<OpenDb>,
+    cache: QueryCache,
+    /// Chemin resolu et generation des reglages au moment de la resolution
+    resolved: Option<(u64, PathBuf)>,
+}
+
+impl Inner {
+    /// Chemin de la base, resolu de nouveau seulement si les reglages ont ete
+    /// ecrits depuis ou si la derniere ouverture a echoue
+    fn db_path(&mut self) -> PathBuf {
+        let generation = settings_generation();
+        match &self.resolved {
+            Some((resolved_at, path)) if *resolved_at == generation => path.clone(),
+            _ => {
+                let path = resolve_goldocab_db_path().path;
+                self.resolved = Some((generation, path.clone()));
+                path
+            }
+        }
+    }
+
+    /// Connexion a jour : rouverte si le chemin resolu ou le fichier ont change,
+    /// cache et schema rafraichis si GoldoCab a ecrit depuis la derniere requete
+    fn ensure_open(&mut self) -> Result<&OpenDb, String> {
+        let path = self.db_path();
+        let identity = file_identity(&path);
+
+        let stale = match &self.db {
+            Some(db) => db.path != path || db.identity != identity,
+            None => true,
+        };
+
+        if stale {
+            self.db = None;
+            self.cache.clear();
+
+            match open_db(&path, identity) {
+                Ok(db) => self.db = Some(db),
+                Err(e) => {
+                    // La base a pu etre creee ou deplacee ailleurs : chemin a resoudre de nouveau
+                    self.resolved = None;
+                    return Err(e);
+                }
+            }
+        } else if let Some(db) = self.db.as_mut() {
+            match data_version(&db.conn) {
+                Some(version) if version == db.data_version => {}
+                Some(version) => {
+                    // Une migration GoldoCab a pu modifier les colonnes
+                    db.schema = GoldocabSchema::detect(&db.conn)?;
+                    db.data_version = version;
+                    self.cache.clear();
+                }
+                None => {
+                    self.db = None;
+                    self.cache.clear();
+                    self.resolved = None;
+                    return Err("GOLDOCAB_UNAVAILABLE".to_string());
+                }
+            }
+        }
+
+        self.db.as_ref().ok_or_else(|| "GOLDOCAB_UNAVAILABLE".to_string())
+    }
+}
+
+/// Connexion GoldoCab partagee, geree par Tauri (`app.manage`)
+#[derive(Clone, Default)]
+pub struct GoldocabState(Arc<Mutex<Inner>>);
+
+impl GoldocabState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn query_blocking<T, F>(&self, f: F) -> Result<T, String>
+    where
+        F: FnOnce(&rusqlite::Connection, &GoldocabSchema) -> Result<T, String>,
+    {
+        let mut inner = self
+            .0
+            .lock()
+            .map_err(|_| "Connexion GoldoCab indisponible".to_string())?;
+        let db = inner.ensure_open()?;
+        f(&db.conn, &db.schema)
+    }
+
+    fn query_cached_blocking<T, F>(&self, key: &str, f: F) -> Result<T, String>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce(&rusqlite::Connection, &GoldocabSchema) -> Result<T, String>,
+    {
+        let mut guard = self
+            .0
+            .lock()
+            .map_err(|_| "Connexion GoldoCab indisponible".to_string())?;
+        let inner = &mut *guard;
+        inner.ensure_open()?;
+
+        if let Some(value) = inner.cache.get(key) {
+            if let Ok(result) = serde_json::from_value(value.clone()) {
+                return Ok(result);
+            }
+        }
+
+        let db = inner.db.as_ref().ok_or_else(|| "GOLDOCAB_UNAVAILABLE".to_string())?;
+        let result = f(&db.conn, &db.schema)?;
+        if let Ok(value) = serde_json::to_value(&result) {
+            inner.cache.insert(key.to_string(), value);
+        }
+
+        Ok(result)
+    }
+
+    /// Execute une requete sur un thread bloquant, hors de la boucle async
+    pub async fn query<T, F>(&self, f: F) -> Result<T, String>
+    where
+        T: Send + 'static,
+        F: FnOnce(&rusqlite::Connection, &GoldocabSchema) -> Result<T, String> + Send + 'static,
+    {
+        let state = self.clone();
+        tauri::async_runtime::spawn_blocking(move || state.query_blocking(f))
+            .await
+            .map_err(|e| format!("Erreur requete GoldoCab: {}", e))?
+    }
+
+    /// Comme `query`, en reutilisant le resultat precedent pour la meme cle
+    /// tant que la base n'a pas change
+    pub async fn query_cached<T, F>(&self, key: String, f: F) -> Result<T, String>
+    where
+        T: Serialize + DeserializeOwned + Send + 'static,
+        F: FnOnce(&rusqlite::Connection, &GoldocabSchema) -> Result<T, String> + Send + 'static,
+    {
+        let state = self.clone();
+        tauri::async_runtime::spawn_blocking(move || state.query_cached_blocking(&key, f))
+            .await
+            .map_err(|e| format!("Erreur requete GoldoCab: {}", e))?
+    }
+}
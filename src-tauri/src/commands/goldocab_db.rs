@@ -1,5 +1,13 @@
-use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use super::goldocab_config::resolve_goldocab_db_path;
+use super::goldocab_connection::GoldocabState;
+use super::goldocab_schema::{row_bool, row_i64, row_text, GoldocabSchema};
+use super::goldocab_search::{
+    clients_by_ids, dossiers_by_ids, fold_text, is_ranked, rank_clients, rank_dossiers, ranked_page,
+    ranking_options, search_clients, search_dossiers, GoldocabListOptions, GoldocabPage,
+};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct GoldocabClient {
@@ -17,6 +25,7 @@ pub struct GoldocabClient {
     pub profession: Option<String>,
     pub forme_juridique: Option<String>,
     pub numero_siren: Option<String>,
+    pub adresse: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -35,6 +44,39 @@ pub struct GoldocabDossier {
     pub est_favori: Option<bool>,
 }
 
+/// Partie ou intervenant rattache a un dossier
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GoldocabContact {
+    pub id: i64,
+    pub dossier_id: Option<i64>,
+    /// Qualite dans le dossier, ex. "Partie adverse", "Avocat adverse", "Expert"
+    pub role: Option<String>,
+    pub civilite: Option<String>,
+    pub nom: Option<String>,
+    pub prenom: Option<String>,
+    pub denomination: Option<String>,
+    pub adresse: Option<String>,
+    pub code_postal: Option<String>,
+    pub ville: Option<String>,
+    pub email: Option<String>,
+    pub telephone: Option<String>,
+    pub display_name: Option<String>,
+    pub is_adverse: bool,
+}
+
+/// Tout ce qu'il faut pour l'en-tete d'un dossier dans l'editeur
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GoldocabDossierDetail {
+    pub dossier: GoldocabDossier,
+    pub client: Option<GoldocabClient>,
+    pub adverse_parties: Vec<GoldocabContact>,
+    /// Autres intervenants (avocats, experts, huissiers...)
+    pub contacts: Vec<GoldocabContact>,
+    /// Dossier de documents enregistre dans GoldoCab
+    pub document_folder: Option<String>,
+    pub document_folder_exists: bool,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct GoldocabItem {
     pub id: i64,
@@ -52,31 +94,165 @@ pub struct GoldocabItem {
 pub struct GoldocabStatus {
     pub available: bool,
     pub database_path: String,
+    /// Origine du chemin : "settings", "environment" ou "default"
+    pub path_source: String,
     pub client_count: Option<i64>,
     pub dossier_count: Option<i64>,
+    /// Version du schema GoldoCab detectee (migration GRDB ou user_version)
+    pub schema_version: Option<String>,
+    /// Champs attendus absents de la base, ex. "clients.numeroSIREN"
+    pub missing_fields: Vec<String>,
+    /// Reglages ~/.citadelle/goldocab.json illisibles, ignores pour la resolution
+    pub settings_error: Option<String>,
 }
 
-fn get_goldocab_db_path() -> PathBuf {
-    let home = dirs::home_dir().expect("Could not find home directory");
-    home.join("Library")
-        .join("Application Support")
-        .join("GoldoCab")
-        .join("goldocab.sqlite")
+/// Champs clients lus par `map_client_row`, dans l'ordre
+pub const CLIENT_COLUMNS: [&str; 15] = [
+    "id", "denomination", "typeClient", "telephone", "email", "ville", "codePostal",
+    "statutClient", "nom", "prenom", "civilite", "profession", "formeJuridique", "numeroSIREN",
+    "adresse",
+];
+
+/// Champs dossiers lus par `map_dossier_row`, dans l'ordre
+pub const DOSSIER_COLUMNS: [&str; 11] = [
+    "id", "nom", "typeDossier", "clientID", "statutGestion", "etat",
+    "numeroRG", "juridiction", "dateAudience", "priorite", "estFavori",
+];
+
+/// Champs contacts lus par `map_contact_row`, dans l'ordre
+const CONTACT_COLUMNS: [&str; 12] = [
+    "id", "dossierID", "role", "civilite", "nom", "prenom", "denomination",
+    "adresse", "codePostal", "ville", "email", "telephone",
+];
+
+/// Champs items lus par `map_item_row`, dans l'ordre
+const ITEM_COLUMNS: [&str; 9] = [
+    "id", "titre", "contexte", "dossierID", "estTache", "dateEcheance",
+    "urgence", "gtdPhase", "enCours",
+];
+
+/// Liste SELECT des champs d'une table ; les colonnes absentes deviennent NULL
+pub fn select_columns(schema: &GoldocabSchema, alias: &str, table: &str, fields: &[&str]) -> String {
+    fields
+        .iter()
+        .map(|f| schema.col(alias, table, f))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
-fn open_goldocab_db() -> Result<rusqlite::Connection, String> {
-    let db_path = get_goldocab_db_path();
-    if !db_path.exists() {
-        return Err("GOLDOCAB_UNAVAILABLE".to_string());
-    }
-    rusqlite::Connection::open_with_flags(
-        &db_path,
-        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+pub fn client_select(schema: &GoldocabSchema, alias: &str) -> String {
+    select_columns(schema, alias, "clients", &CLIENT_COLUMNS)
+}
+
+/// Colonnes du dossier suivies des quatre champs client necessaires au nom affiche
+pub fn dossier_select(schema: &GoldocabSchema, alias: &str, client_alias: &str) -> String {
+    format!(
+        "{}, {}",
+        select_columns(schema, alias, "dossiers", &DOSSIER_COLUMNS),
+        select_columns(schema, client_alias, "clients", &["nom", "prenom", "denomination", "typeClient"]),
     )
-    .map_err(|e| format!("Erreur ouverture base GoldoCab: {}", e))
 }
 
-fn build_client_display_name(
+pub fn item_select(schema: &GoldocabSchema, alias: &str) -> String {
+    select_columns(schema, alias, "items", &ITEM_COLUMNS)
+}
+
+pub fn contact_select(schema: &GoldocabSchema, alias: &str) -> String {
+    select_columns(schema, alias, "contacts", &CONTACT_COLUMNS)
+}
+
+pub fn map_client_row(row: &rusqlite::Row, offset: usize) -> rusqlite::Result<GoldocabClient> {
+    Ok(GoldocabClient {
+        id: row.get(offset)?,
+        denomination: row_text(row, offset + 1),
+        type_client: row_text(row, offset + 2),
+        telephone: row_text(row, offset + 3),
+        email: row_text(row, offset + 4),
+        ville: row_text(row, offset + 5),
+        code_postal: row_text(row, offset + 6),
+        statut_client: row_text(row, offset + 7),
+        nom: row_text(row, offset + 8),
+        prenom: row_text(row, offset + 9),
+        civilite: row_text(row, offset + 10),
+        profession: row_text(row, offset + 11),
+        forme_juridique: row_text(row, offset + 12),
+        numero_siren: row_text(row, offset + 13),
+        adresse: row_text(row, offset + 14),
+    })
+}
+
+pub fn map_dossier_row(row: &rusqlite::Row, offset: usize) -> rusqlite::Result<GoldocabDossier> {
+    let c_nom = row_text(row, offset + 11);
+    let c_prenom = row_text(row, offset + 12);
+    let c_denom = row_text(row, offset + 13);
+    let c_type = row_text(row, offset + 14);
+
+    let client_name = build_client_display_name(&c_nom, &c_prenom, &c_denom, &c_type);
+
+    Ok(GoldocabDossier {
+        id: row.get(offset)?,
+        nom: row_text(row, offset + 1),
+        type_dossier: row_text(row, offset + 2),
+        client_id: row_i64(row, offset + 3),
+        client_name,
+        statut_gestion: row_text(row, offset + 4),
+        etat: row_text(row, offset + 5),
+        numero_rg: row_text(row, offset + 6),
+        juridiction: row_text(row, offset + 7),
+        date_audience: row_text(row, offset + 8),
+        priorite: row_i64(row, offset + 9),
+        est_favori: row_bool(row, offset + 10),
+    })
+}
+
+pub fn map_item_row(row: &rusqlite::Row, offset: usize) -> rusqlite::Result<GoldocabItem> {
+    Ok(GoldocabItem {
+        id: row.get(offset)?,
+        titre: row_text(row, offset + 1),
+        contexte: row_text(row, offset + 2),
+        dossier_id: row_i64(row, offset + 3),
+        est_tache: row_bool(row, offset + 4),
+        date_echeance: row_text(row, offset + 5),
+        urgence: row_i64(row, offset + 6),
+        gtd_phase: row_text(row, offset + 7),
+        en_cours: row_bool(row, offset + 8),
+    })
+}
+
+pub fn map_contact_row(row: &rusqlite::Row, offset: usize) -> rusqlite::Result<GoldocabContact> {
+    let role = row_text(row, offset + 2);
+    let nom = row_text(row, offset + 4);
+    let prenom = row_text(row, offset + 5);
+    let denomination = row_text(row, offset + 6);
+
+    // Les contacts n'ont pas de type : une denomination sans nom designe une personne morale
+    let display_name = build_client_display_name(&nom, &prenom, &denomination, &None);
+    // "Partie adverse" oui, "Avocat adverse" non : le conseil reste un intervenant
+    let is_adverse = role
+        .as_deref()
+        .map(fold_text)
+        .map(|r| r.contains("advers") && !r.contains("avocat") && !r.contains("conseil"))
+        .unwrap_or(false);
+
+    Ok(GoldocabContact {
+        id: row.get(offset)?,
+        dossier_id: row_i64(row, offset + 1),
+        role,
+        civilite: row_text(row, offset + 3),
+        nom,
+        prenom,
+        denomination,
+        adresse: row_text(row, offset + 7),
+        code_postal: row_text(row, offset + 8),
+        ville: row_text(row, offset + 9),
+        email: row_text(row, offset + 10),
+        telephone: row_text(row, offset + 11),
+        display_name,
+        is_adverse,
+    })
+}
+
+pub fn build_client_display_name(
     nom: &Option<String>,
     prenom: &Option<String>,
     denomination: &Option<String>,
@@ -98,221 +274,358 @@ fn build_client_display_name(
 }
 
 #[tauri::command]
-pub async fn check_goldocab_status() -> Result<GoldocabStatus, String> {
-    let db_path = get_goldocab_db_path();
-    let path_str = db_path.to_string_lossy().to_string();
-
-    let conn = match open_goldocab_db() {
-        Ok(c) => c,
-        Err(_) => {
-            return Ok(GoldocabStatus {
-                available: false,
-                database_path: path_str,
-                client_count: None,
-                dossier_count: None,
-            });
-        }
-    };
+pub async fn check_goldocab_status(state: State<'_, GoldocabState>) -> Result<GoldocabStatus, String> {
+    let location = resolve_goldocab_db_path();
+    let path_str = location.path.to_string_lossy().to_string();
 
-    let client_count: Option<i64> = conn
-        .query_row("SELECT COUNT(*) FROM clients", [], |row| row.get(0))
-        .ok();
+    let counts = state
+        .query(|conn, schema| {
+            let client_count: Option<i64> = conn
+                .query_row("SELECT COUNT(*) FROM clients", [], |row| row.get(0))
+                .ok();
 
-    let dossier_count: Option<i64> = conn
-        .query_row("SELECT COUNT(*) FROM dossiers", [], |row| row.get(0))
-        .ok();
+            let dossier_count: Option<i64> = conn
+                .query_row("SELECT COUNT(*) FROM dossiers", [], |row| row.get(0))
+                .ok();
+
+            Ok((client_count, dossier_count, schema.version.clone(), schema.missing_fields.clone()))
+        })
+        .await;
+
+    let Ok((client_count, dossier_count, schema_version, missing_fields)) = counts else {
+        return Ok(GoldocabStatus {
+            available: false,
+            database_path: path_str,
+            path_source: location.source,
+            client_count: None,
+            dossier_count: None,
+            schema_version: None,
+            missing_fields: Vec::new(),
+            settings_error: location.settings_error,
+        });
+    };
 
     Ok(GoldocabStatus {
         available: true,
         database_path: path_str,
+        path_source: location.source,
         client_count,
         dossier_count,
+        schema_version,
+        missing_fields,
+        settings_error: location.settings_error,
     })
 }
 
+/// Recherche sans accents ni casse, multi-termes, classee par pertinence
 #[tauri::command]
-pub async fn search_goldocab_clients(query: String, limit: Option<i64>) -> Result<Vec<GoldocabClient>, String> {
-    let conn = open_goldocab_db()?;
-    let max = limit.unwrap_or(20);
-    let pattern = format!("%{}%", query.to_lowercase());
+pub async fn search_goldocab_clients(
+    state: State<'_, GoldocabState>,
+    query: String,
+    limit: Option<i64>,
+) -> Result<Vec<GoldocabClient>, String> {
+    let options = GoldocabListOptions {
+        limit: Some(limit.unwrap_or(20)),
+        ..Default::default()
+    };
+    Ok(search_goldocab_clients_page(state, query, Some(options)).await?.items)
+}
 
-    let mut stmt = conn
-        .prepare(
-            "SELECT id, denomination, typeClient, telephone, email, ville, codePostal,
-                    statutClient, nom, prenom, civilite, profession, formeJuridique, numeroSIREN
-             FROM clients
-             WHERE LOWER(COALESCE(nom,'')) LIKE ?1
-                OR LOWER(COALESCE(prenom,'')) LIKE ?1
-                OR LOWER(COALESCE(denomination,'')) LIKE ?1
-                OR LOWER(COALESCE(email,'')) LIKE ?1
-             ORDER BY COALESCE(nom, denomination, '') COLLATE NOCASE ASC
-             LIMIT ?2",
-        )
-        .map_err(|e| format!("Erreur requete clients: {}", e))?;
+/// Recherche sans accents ni casse sur le dossier et son client, classee par pertinence
+#[tauri::command]
+pub async fn search_goldocab_dossiers(
+    state: State<'_, GoldocabState>,
+    query: String,
+    limit: Option<i64>,
+) -> Result<Vec<GoldocabDossier>, String> {
+    let options = GoldocabListOptions {
+        limit: Some(limit.unwrap_or(20)),
+        ..Default::default()
+    };
+    Ok(search_goldocab_dossiers_page(state, query, Some(options)).await?.items)
+}
 
-    let rows = stmt
-        .query_map(rusqlite::params![pattern, max], |row| {
-            Ok(GoldocabClient {
-                id: row.get(0)?,
-                denomination: row.get(1)?,
-                type_client: row.get(2)?,
-                telephone: row.get(3)?,
-                email: row.get(4)?,
-                ville: row.get(5)?,
-                code_postal: row.get(6)?,
-                statut_client: row.get(7)?,
-                nom: row.get(8)?,
-                prenom: row.get(9)?,
-                civilite: row.get(10)?,
-                profession: row.get(11)?,
-                forme_juridique: row.get(12)?,
-                numero_siren: row.get(13)?,
-            })
-        })
-        .map_err(|e| format!("Erreur lecture clients: {}", e))?;
+fn list_cache_key(prefix: &str, query: &str, options: &GoldocabListOptions) -> String {
+    format!(
+        "{}:{}:{}",
+        prefix,
+        query,
+        serde_json::to_string(options).unwrap_or_default()
+    )
+}
 
-    let mut clients = Vec::new();
-    for row in rows {
-        if let Ok(client) = row {
-            clients.push(client);
-        }
+/// Page de clients avec total, tri et filtre `statut_client` ; une requete vide liste tout
+#[tauri::command]
+pub async fn search_goldocab_clients_page(
+    state: State<'_, GoldocabState>,
+    query: String,
+    options: Option<GoldocabListOptions>,
+) -> Result<GoldocabPage<GoldocabClient>, String> {
+    let options = options.unwrap_or_default();
+    if !is_ranked(&query, &options)? {
+        return state.query_cached(list_cache_key("clients", &query, &options), move |conn, schema| {
+            schema.require("clients")?;
+
+            search_clients(conn, schema, &query, &options)
+        })
+        .await;
     }
 
-    Ok(clients)
+    // Classement calcule une fois par recherche, puis decoupe page par page
+    let ranking = ranking_options(&options);
+    let ranked: Vec<i64> = state
+        .query_cached(list_cache_key("clients-ranking", &query, &ranking), move |conn, schema| {
+            schema.require("clients")?;
+
+            rank_clients(conn, schema, &query, &ranking)
+        })
+        .await?;
+
+    state
+        .query(move |conn, schema| ranked_page(&ranked, &options, |ids| clients_by_ids(conn, schema, ids)))
+        .await
 }
 
+/// Page de dossiers avec total, tri et filtres (statuts, etat, type) ; une requete vide liste tout
 #[tauri::command]
-pub async fn search_goldocab_dossiers(query: String, limit: Option<i64>) -> Result<Vec<GoldocabDossier>, String> {
-    let conn = open_goldocab_db()?;
-    let max = limit.unwrap_or(20);
-    let pattern = format!("%{}%", query.to_lowercase());
+pub async fn search_goldocab_dossiers_page(
+    state: State<'_, GoldocabState>,
+    query: String,
+    options: Option<GoldocabListOptions>,
+) -> Result<GoldocabPage<GoldocabDossier>, String> {
+    let options = options.unwrap_or_default();
+    if !is_ranked(&query, &options)? {
+        return state.query_cached(list_cache_key("dossiers", &query, &options), move |conn, schema| {
+            schema.require("dossiers")?;
+            schema.require("clients")?;
+
+            search_dossiers(conn, schema, &query, &options)
+        })
+        .await;
+    }
 
-    let mut stmt = conn
-        .prepare(
-            "SELECT d.id, d.nom, d.typeDossier, d.clientID, d.statutGestion, d.etat,
-                    d.numeroRG, d.juridiction, d.dateAudience, d.priorite, d.estFavori,
-                    c.nom AS c_nom, c.prenom AS c_prenom, c.denomination AS c_denom, c.typeClient AS c_type
-             FROM dossiers d
-             LEFT JOIN clients c ON d.clientID = c.id
-             WHERE LOWER(COALESCE(d.nom,'')) LIKE ?1
-                OR LOWER(COALESCE(d.numeroRG,'')) LIKE ?1
-                OR LOWER(COALESCE(d.juridiction,'')) LIKE ?1
-                OR LOWER(COALESCE(c.nom,'')) LIKE ?1
-                OR LOWER(COALESCE(c.prenom,'')) LIKE ?1
-                OR LOWER(COALESCE(c.denomination,'')) LIKE ?1
-             ORDER BY COALESCE(d.estFavori, 0) DESC, d.id DESC
-             LIMIT ?2",
-        )
-        .map_err(|e| format!("Erreur requete dossiers: {}", e))?;
+    // Classement calcule une fois par recherche, puis decoupe page par page
+    let ranking = ranking_options(&options);
+    let ranked: Vec<i64> = state
+        .query_cached(list_cache_key("dossiers-ranking", &query, &ranking), move |conn, schema| {
+            schema.require("dossiers")?;
+            schema.require("clients")?;
 
-    let rows = stmt
-        .query_map(rusqlite::params![pattern, max], |row| {
-            let c_nom: Option<String> = row.get(11)?;
-            let c_prenom: Option<String> = row.get(12)?;
-            let c_denom: Option<String> = row.get(13)?;
-            let c_type: Option<String> = row.get(14)?;
-
-            let client_name = build_client_display_name(&c_nom, &c_prenom, &c_denom, &c_type);
-
-            Ok(GoldocabDossier {
-                id: row.get(0)?,
-                nom: row.get(1)?,
-                type_dossier: row.get(2)?,
-                client_id: row.get(3)?,
-                client_name,
-                statut_gestion: row.get(4)?,
-                etat: row.get(5)?,
-                numero_rg: row.get(6)?,
-                juridiction: row.get(7)?,
-                date_audience: row.get(8)?,
-                priorite: row.get(9)?,
-                est_favori: row.get(10)?,
-            })
+            rank_dossiers(conn, schema, &query, &ranking)
         })
-        .map_err(|e| format!("Erreur lecture dossiers: {}", e))?;
-
-    let mut dossiers = Vec::new();
-    for row in rows {
-        if let Ok(dossier) = row {
-            dossiers.push(dossier);
-        }
-    }
+        .await?;
 
-    Ok(dossiers)
+    state
+        .query(move |conn, schema| ranked_page(&ranked, &options, |ids| dossiers_by_ids(conn, schema, ids)))
+        .await
 }
 
 #[tauri::command]
-pub async fn get_goldocab_client(id: i64) -> Result<Option<GoldocabClient>, String> {
-    let conn = open_goldocab_db()?;
-
-    let result = conn.query_row(
-        "SELECT id, denomination, typeClient, telephone, email, ville, codePostal,
-                statutClient, nom, prenom, civilite, profession, formeJuridique, numeroSIREN
-         FROM clients WHERE id = ?1",
-        rusqlite::params![id],
-        |row| {
-            Ok(GoldocabClient {
-                id: row.get(0)?,
-                denomination: row.get(1)?,
-                type_client: row.get(2)?,
-                telephone: row.get(3)?,
-                email: row.get(4)?,
-                ville: row.get(5)?,
-                code_postal: row.get(6)?,
-                statut_client: row.get(7)?,
-                nom: row.get(8)?,
-                prenom: row.get(9)?,
-                civilite: row.get(10)?,
-                profession: row.get(11)?,
-                forme_juridique: row.get(12)?,
-                numero_siren: row.get(13)?,
-            })
-        },
-    );
+pub async fn get_goldocab_client(state: State<'_, GoldocabState>, id: i64) -> Result<Option<GoldocabClient>, String> {
+    state.query_cached(format!("client:{}", id), move |conn, schema| {
+        schema.require("clients")?;
 
-    match result {
-        Ok(client) => Ok(Some(client)),
-        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(format!("Erreur lecture client: {}", e)),
-    }
+        let sql = format!("SELECT {} FROM clients WHERE id = ?1", client_select(schema, ""));
+        let result = conn.query_row(&sql, rusqlite::params![id], |row| map_client_row(row, 0));
+
+        match result {
+            Ok(client) => Ok(Some(client)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(format!("Erreur lecture client: {}", e)),
+        }
+    })
+    .await
 }
 
 #[tauri::command]
-pub async fn get_goldocab_dossier_items(dossier_id: i64) -> Result<Vec<GoldocabItem>, String> {
-    let conn = open_goldocab_db()?;
+pub async fn get_goldocab_dossier_items(
+    state: State<'_, GoldocabState>,
+    dossier_id: i64,
+) -> Result<Vec<GoldocabItem>, String> {
+    state.query_cached(format!("items:{}", dossier_id), move |conn, schema| {
+        // Sans table items ou sans lien vers le dossier, il n'y a simplement rien a lister
+        if !schema.has_table("items") || !schema.has_field("items", "dossierID") {
+            return Ok(Vec::new());
+        }
 
-    let mut stmt = conn
-        .prepare(
-            "SELECT id, titre, contexte, dossierID, estTache, dateEcheance,
-                    urgence, gtdPhase, enCours
+        let col = |f: &str| schema.col("", "items", f);
+        let sql = format!(
+            "SELECT {}
              FROM items
-             WHERE dossierID = ?1
-             ORDER BY COALESCE(enCours, 0) DESC, COALESCE(urgence, 0) DESC, id DESC",
-        )
-        .map_err(|e| format!("Erreur requete items: {}", e))?;
+             WHERE {} = ?1
+             ORDER BY COALESCE({}, 0) DESC, COALESCE({}, 0) DESC, id DESC",
+            item_select(schema, ""),
+            col("dossierID"), col("enCours"), col("urgence"),
+        );
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| format!("Erreur requete items: {}", e))?;
+
+        let rows = stmt
+            .query_map(rusqlite::params![dossier_id], |row| map_item_row(row, 0))
+            .map_err(|e| format!("Erreur lecture items: {}", e))?;
+
+        let mut items = Vec::new();
+        for row in rows {
+            if let Ok(item) = row {
+                items.push(item);
+            }
+        }
+
+        Ok(items)
+    })
+    .await
+}
+
+fn read_dossier_contacts(
+    conn: &rusqlite::Connection,
+    schema: &GoldocabSchema,
+    dossier_id: i64,
+) -> Result<Vec<GoldocabContact>, String> {
+    // Toutes les versions de GoldoCab n'ont pas de table des parties
+    if schema.require("contacts").is_err() {
+        return Ok(Vec::new());
+    }
+
+    let col = |f: &str| schema.col("", "contacts", f);
+    let sql = format!(
+        "SELECT {}
+         FROM {}
+         WHERE {} = ?1
+         ORDER BY COALESCE({}, '') COLLATE NOCASE ASC, id ASC",
+        contact_select(schema, ""),
+        schema.table("contacts"),
+        col("dossierID"), col("role"),
+    );
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Erreur requete contacts: {}", e))?;
 
     let rows = stmt
-        .query_map(rusqlite::params![dossier_id], |row| {
-            Ok(GoldocabItem {
-                id: row.get(0)?,
-                titre: row.get(1)?,
-                contexte: row.get(2)?,
-                dossier_id: row.get(3)?,
-                est_tache: row.get(4)?,
-                date_echeance: row.get(5)?,
-                urgence: row.get(6)?,
-                gtd_phase: row.get(7)?,
-                en_cours: row.get(8)?,
-            })
-        })
-        .map_err(|e| format!("Erreur lecture items: {}", e))?;
+        .query_map(rusqlite::params![dossier_id], |row| map_contact_row(row, 0))
+        .map_err(|e| format!("Erreur lecture contacts: {}", e))?;
+
+    Ok(rows.flatten().collect())
+}
+
+pub fn read_dossier_detail(
+    conn: &rusqlite::Connection,
+    schema: &GoldocabSchema,
+    id: i64,
+) -> Result<Option<GoldocabDossierDetail>, String> {
+    schema.require("dossiers")?;
+    schema.require("clients")?;
+
+    let sql = format!(
+        "SELECT {}, {}
+         FROM dossiers d
+         LEFT JOIN clients c ON {} = c.id
+         WHERE d.id = ?1",
+        dossier_select(schema, "d", "c"),
+        schema.col("d", "dossiers", "cheminDossier"),
+        schema.col("d", "dossiers", "clientID"),
+    );
+
+    let result = conn.query_row(&sql, rusqlite::params![id], |row| {
+        Ok((map_dossier_row(row, 0)?, row_text(row, DOSSIER_COLUMNS.len() + 4)))
+    });
 
-    let mut items = Vec::new();
-    for row in rows {
-        if let Ok(item) = row {
-            items.push(item);
+    let (dossier, document_folder) = match result {
+        Ok(found) => found,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+        Err(e) => return Err(format!("Erreur lecture dossier: {}", e)),
+    };
+
+    let client = match dossier.client_id {
+        Some(client_id) => {
+            let sql = format!("SELECT {} FROM clients WHERE id = ?1", client_select(schema, ""));
+            conn.query_row(&sql, rusqlite::params![client_id], |row| map_client_row(row, 0))
+                .ok()
         }
-    }
+        None => None,
+    };
+
+    let (adverse_parties, contacts): (Vec<_>, Vec<_>) = read_dossier_contacts(conn, schema, id)?
+        .into_iter()
+        .partition(|c| c.is_adverse);
+
+    let document_folder = document_folder.filter(|p| !p.trim().is_empty());
+    let document_folder_exists = document_folder
+        .as_deref()
+        .map(|p| std::path::Path::new(p).is_dir())
+        .unwrap_or(false);
+
+    Ok(Some(GoldocabDossierDetail {
+        dossier,
+        client,
+        adverse_parties,
+        contacts,
+        document_folder,
+        document_folder_exists,
+    }))
+}
 
-    Ok(items)
+/// Fiche complete d'un dossier : client, parties adverses, intervenants et
+/// dossier de documents
+#[tauri::command]
+pub async fn get_goldocab_dossier(
+    state: State<'_, GoldocabState>,
+    id: i64,
+) -> Result<Option<GoldocabDossierDetail>, String> {
+    state.query_cached(format!("dossier:{}", id), move |conn, schema| {
+        read_dossier_detail(conn, schema, id)
+    })
+    .await
+}
+
+/// Dossiers d'un client, favoris puis plus recents d'abord
+#[tauri::command]
+pub async fn list_goldocab_client_dossiers(
+    state: State<'_, GoldocabState>,
+    client_id: i64,
+) -> Result<Vec<GoldocabDossier>, String> {
+    state.query_cached(format!("client_dossiers:{}", client_id), move |conn, schema| {
+        schema.require("dossiers")?;
+        schema.require("clients")?;
+
+        if !schema.has_field("dossiers", "clientID") {
+            return Ok(Vec::new());
+        }
+
+        let d = |f: &str| schema.col("d", "dossiers", f);
+        let sql = format!(
+            "SELECT {}
+             FROM dossiers d
+             LEFT JOIN clients c ON {} = c.id
+             WHERE {} = ?1
+             ORDER BY COALESCE({}, 0) DESC, d.id DESC",
+            dossier_select(schema, "d", "c"),
+            d("clientID"), d("clientID"), d("estFavori"),
+        );
+
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| format!("Erreur requete dossiers: {}", e))?;
+
+        let rows = stmt
+            .query_map(rusqlite::params![client_id], |row| map_dossier_row(row, 0))
+            .map_err(|e| format!("Erreur lecture dossiers: {}", e))?;
+
+        Ok(rows.flatten().collect())
+    })
+    .await
+}
+
+/// Parties et intervenants d'un dossier (vide si GoldoCab ne les gere pas)
+#[tauri::command]
+pub async fn get_goldocab_dossier_contacts(
+    state: State<'_, GoldocabState>,
+    dossier_id: i64,
+) -> Result<Vec<GoldocabContact>, String> {
+    state.query_cached(format!("contacts:{}", dossier_id), move |conn, schema| {
+        read_dossier_contacts(conn, schema, dossier_id)
+    })
+    .await
 }
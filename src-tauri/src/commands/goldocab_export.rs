@@ -0,0 +1,246 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use super::common::{chrono_now, numbered_file_name, sanitize_file_name};
+use super::goldocab_audit::{record_audit, GoldocabAuditEntry};
+use super::goldocab_notifier::{notify_goldocab, GoldocabNotification};
+use super::goldocab_sessions::get_goldocab_handoff_dir;
+
+/// Version du format des fichiers `<document>.meta.json` lus par GoldoCab
+pub const EXPORT_METADATA_SCHEMA_VERSION: u32 = 1;
+
+pub const EXPORT_METADATA_SUFFIX: &str = ".meta.json";
+
+/// Tentatives de creation si un autre export prend le meme nom entre-temps
+const MAX_CREATE_ATTEMPTS: usize = 10;
+
+/// Suffixes des fichiers du protocole d'echange : un document exporte sous ces
+/// noms serait pris pour une session, une demande ou un marqueur
+const RESERVED_SUFFIXES: [&str; 4] = [EXPORT_METADATA_SUFFIX, ".session.json", ".request.json", ".done"];
+
+/// Contenu d'un fichier `<document>.meta.json` du dossier 03_HANDOFF
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GoldocabExportMetadata {
+    pub schema_version: u32,
+    /// Nom final, apres nettoyage et dedoublonnage
+    pub file_name: String,
+    pub file_path: String,
+    /// Nom demande par l'appelant, s'il a du etre modifie
+    #[serde(default)]
+    pub requested_file_name: Option<String>,
+    #[serde(default)]
+    pub dossier_id: Option<String>,
+    pub document_type: String,
+    /// "text" ou "binary"
+    pub content_kind: String,
+    #[serde(default)]
+    pub mime_type: Option<String>,
+    pub size: u64,
+    pub sha256: String,
+    /// Secondes depuis epoch
+    pub created_at: String,
+    pub source: String,
+}
+
+#[derive(Serialize, Clone)]
+pub struct GoldocabExportResult {
+    pub path: String,
+    pub metadata_path: String,
+    pub metadata: GoldocabExportMetadata,
+    pub notification: Option<GoldocabNotification>,
+    /// GoldoCab n'a pu etre prevenu ni par URL ni par fichier de notification
+    pub notification_error: Option<String>,
+}
+
+fn mime_type(file_name: &str) -> Option<&'static str> {
+    let ext = Path::new(file_name).extension()?.to_string_lossy().to_lowercase();
+    let mime = match ext.as_str() {
+        "md" | "markdown" => "text/markdown",
+        "txt" => "text/plain",
+        "html" | "htm" => "text/html",
+        "json" => "application/json",
+        "pdf" => "application/pdf",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "odt" => "application/vnd.oasis.opendocument.text",
+        "rtf" => "application/rtf",
+        _ => return None,
+    };
+    Some(mime)
+}
+
+/// Lit un fichier `<document>.meta.json` ; `None` s'il est absent ou illisible
+pub fn read_export_metadata(path: &Path) -> Option<GoldocabExportMetadata> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Rend `tmp` visible sous `target` sans jamais ecraser un fichier existant :
+/// le fichier apparait d'un coup, complet. Repli sur un renommage si le systeme
+/// de fichiers ne gere pas les liens physiques
+fn publish_new(tmp: &Path, target: &Path) -> std::io::Result<()> {
+    match fs::hard_link(tmp, target) {
+        Err(e) if e.kind() != std::io::ErrorKind::AlreadyExists => {
+            if target.exists() {
+                return Err(std::io::ErrorKind::AlreadyExists.into());
+            }
+            fs::rename(tmp, target)
+        }
+        result => result,
+    }
+}
+
+/// Fichier temporaire cache dans `dir`, ignore par GoldoCab
+fn write_temp(dir: &Path, bytes: &[u8]) -> Result<PathBuf, String> {
+    let tmp = dir.join(format!(".{}.citadelle-tmp", Uuid::new_v4()));
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&tmp)
+        .map_err(|e| format!("Failed to write temporary file: {}", e))?;
+    file.write_all(bytes).map_err(|e| {
+        let _ = fs::remove_file(&tmp);
+        format!("Failed to write temporary file: {}", e)
+    })?;
+    Ok(tmp)
+}
+
+/// Publie le document sous un nom libre, sans jamais ecraser un fichier
+/// existant. Le `.meta.json` apparait complet avant le document : GoldoCab ne
+/// voit jamais un document sans ses metadonnees
+fn publish_unique(
+    dir: &Path,
+    file_name: &str,
+    document_tmp: &Path,
+    metadata_for: impl Fn(&str, &Path) -> GoldocabExportMetadata,
+) -> Result<(PathBuf, PathBuf, GoldocabExportMetadata), String> {
+    let mut races = 0;
+    let mut n = 1;
+    while races < MAX_CREATE_ATTEMPTS {
+        let name = numbered_file_name(file_name, n);
+        n += 1;
+
+        let path = dir.join(&name);
+        let meta_path = dir.join(format!("{}{}", name, EXPORT_METADATA_SUFFIX));
+        if path.exists() || meta_path.exists() {
+            continue;
+        }
+
+        let metadata = metadata_for(&name, &path);
+        let meta_json = serde_json::to_string_pretty(&metadata)
+            .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
+        let meta_tmp = write_temp(dir, meta_json.as_bytes())?;
+        let published = publish_new(&meta_tmp, &meta_path);
+        let _ = fs::remove_file(&meta_tmp);
+        match published {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                races += 1;
+                continue;
+            }
+            Err(e) => return Err(format!("Failed to write metadata: {}", e)),
+        }
+
+        match publish_new(document_tmp, &path) {
+            Ok(()) => return Ok((path, meta_path, metadata)),
+            Err(e) => {
+                // Ce `.meta.json` a ete cree par nous : le retirer ne touche
+                // pas a l'export concurrent
+                let _ = fs::remove_file(&meta_path);
+                if e.kind() != std::io::ErrorKind::AlreadyExists {
+                    return Err(format!("Failed to write document: {}", e));
+                }
+                races += 1;
+            }
+        }
+    }
+
+    Err(format!("No free file name for {}", file_name))
+}
+
+/// Depose un document dans 03_HANDOFF avec son `.meta.json` et previent GoldoCab.
+/// `content` pour le texte (Markdown...), `binary_content` pour DOCX ou PDF
+#[tauri::command]
+pub async fn export_to_goldocab(
+    content: Option<String>,
+    binary_content: Option<Vec<u8>>,
+    file_name: String,
+    dossier_id: Option<String>,
+    document_type: Option<String>,
+) -> Result<GoldocabExportResult, String> {
+    let (bytes, content_kind) = match (content, binary_content) {
+        (Some(text), None) => (text.into_bytes(), "text"),
+        (None, Some(bytes)) => (bytes, "binary"),
+        _ => return Err("Provide exactly one of content or binary_content".to_string()),
+    };
+
+    let handoff_dir = get_goldocab_handoff_dir()?;
+
+    // Jamais de separateur ni de ".." : le document reste dans 03_HANDOFF
+    let safe_name = sanitize_file_name(&file_name);
+    let lower_name = safe_name.to_lowercase();
+    if let Some(suffix) = RESERVED_SUFFIXES.iter().find(|s| lower_name.ends_with(*s)) {
+        return Err(format!("File names ending in {} are reserved for GoldoCab", suffix));
+    }
+
+    let size = bytes.len() as u64;
+    let sha256 = format!("{:x}", Sha256::digest(&bytes));
+    let created_at = chrono_now();
+    let dossier_id = dossier_id.filter(|id| !id.trim().is_empty());
+    let document_type = document_type.unwrap_or_else(|| "document".to_string());
+
+    let document_tmp = write_temp(&handoff_dir, &bytes)?;
+    let published = publish_unique(&handoff_dir, &safe_name, &document_tmp, |final_name, path| {
+        GoldocabExportMetadata {
+            schema_version: EXPORT_METADATA_SCHEMA_VERSION,
+            requested_file_name: (final_name != file_name).then(|| file_name.clone()),
+            file_path: path.to_string_lossy().to_string(),
+            dossier_id: dossier_id.clone(),
+            document_type: document_type.clone(),
+            content_kind: content_kind.to_string(),
+            mime_type: mime_type(final_name).map(str::to_string),
+            size,
+            sha256: sha256.clone(),
+            created_at: created_at.clone(),
+            source: "citadelle".to_string(),
+            file_name: final_name.to_string(),
+        }
+    });
+    let _ = fs::remove_file(&document_tmp);
+    let (_, meta_path, metadata) = published?;
+
+    record_audit(GoldocabAuditEntry {
+        event: "export".to_string(),
+        path: Some(metadata.file_path.clone()),
+        sha256: Some(metadata.sha256.clone()),
+        dossier_id: metadata.dossier_id.clone(),
+        detail: Some(metadata.document_type.clone()),
+        ..Default::default()
+    });
+
+    let goldocab_url = format!(
+        "goldocab://document/new?path={}&source=citadelle{}",
+        urlencoding::encode(&metadata.file_path),
+        metadata
+            .dossier_id
+            .as_ref()
+            .map(|id| format!("&dossierID={}", urlencoding::encode(id)))
+            .unwrap_or_default()
+    );
+
+    let (notification, notification_error) = match notify_goldocab(&goldocab_url) {
+        Ok(notification) => (Some(notification), None),
+        Err(e) => (None, Some(e)),
+    };
+
+    Ok(GoldocabExportResult {
+        path: metadata.file_path.clone(),
+        metadata_path: meta_path.to_string_lossy().to_string(),
+        metadata,
+        notification,
+        notification_error,
+    })
+}
@@ -0,0 +1,413 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, State};
+use uuid::Uuid;
+
+use super::common::{chrono_iso8601_now, validate_path};
+use super::goldocab_sessions::{cancel_session, get_goldocab_handoff_dir, read_session};
+
+/// Evenement emis vers le frontend pour chaque demande GoldoCab acceptee
+pub const GOLDOCAB_REQUEST_EVENT: &str = "goldocab-request";
+
+/// Version du format des demandes deposees par GoldoCab dans `03_HANDOFF/inbox`
+pub const INBOUND_REQUEST_SCHEMA_VERSION: u32 = 1;
+
+const INBOX_DIR_NAME: &str = "inbox";
+const REQUEST_SUFFIX: &str = ".request.json";
+const RESPONSE_SUFFIX: &str = ".response.json";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Un JSON invalide plus recent que ce delai est peut-etre encore en cours
+/// d'ecriture : il est relu au tour suivant plutot que rejete
+const WRITE_GRACE: Duration = Duration::from_secs(5);
+
+/// Action demandee par GoldoCab
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GoldocabInboundAction {
+    /// Ouvrir un document dans une session d'edition
+    OpenFile {
+        path: String,
+        #[serde(default)]
+        dossier_id: Option<String>,
+        #[serde(default)]
+        dossier_name: Option<String>,
+    },
+    /// Annuler une session en cours (document repris dans GoldoCab...)
+    CancelSession {
+        session_id: String,
+        #[serde(default)]
+        reason: Option<String>,
+    },
+}
+
+/// Contenu d'un fichier `inbox/<request_id>.request.json`, ecrit par GoldoCab
+/// (ecriture puis renommage)
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GoldocabInboundRequest {
+    pub schema_version: u32,
+    pub request_id: String,
+    pub created_at: String,
+    #[serde(flatten)]
+    pub action: GoldocabInboundAction,
+}
+
+/// Reponse ecrite par Citadelle dans `inbox/<request_id>.response.json`
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GoldocabInboundResponse {
+    pub schema_version: u32,
+    pub request_id: String,
+    /// "accepted" ou "rejected"
+    pub status: String,
+    #[serde(default)]
+    pub message: Option<String>,
+    pub processed_at: String,
+}
+
+struct PendingRequests {
+    /// Vrai une fois que le frontend ecoute `goldocab-request`
+    ready: bool,
+    requests: Vec<GoldocabInboundRequest>,
+}
+
+/// Demandes acceptees avant que le frontend n'ecoute (au lancement, celles deja
+/// deposees dans l'inbox), distribuees par `flush_pending_goldocab_requests`
+pub struct GoldocabInboxState {
+    pending: Mutex<PendingRequests>,
+}
+
+impl Default for GoldocabInboxState {
+    fn default() -> Self {
+        GoldocabInboxState {
+            pending: Mutex::new(PendingRequests {
+                ready: false,
+                requests: Vec::new(),
+            }),
+        }
+    }
+}
+
+impl GoldocabInboxState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn get_inbox_dir() -> Result<PathBuf, String> {
+    let dir = get_goldocab_handoff_dir()?.join(INBOX_DIR_NAME);
+    fs::create_dir_all(&dir).map_err(|e| format!("Erreur creation dossier inbox: {}", e))?;
+    Ok(dir)
+}
+
+fn response_path(dir: &Path, request_id: &str) -> PathBuf {
+    dir.join(format!("{}{}", request_id, RESPONSE_SUFFIX))
+}
+
+fn recently_modified(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .map(|elapsed| elapsed < WRITE_GRACE)
+        .unwrap_or(false)
+}
+
+/// Lecture en deux temps : la version d'abord, pour rejeter proprement un
+/// format plus recent que celui que Citadelle connait
+fn parse_request(request_id: &str, content: &str) -> Result<GoldocabInboundRequest, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(content).map_err(|e| format!("JSON invalide: {}", e))?;
+
+    let version = value
+        .get("schema_version")
+        .and_then(|v| v.as_u64())
+        .ok_or("Champ schema_version manquant ou invalide")?;
+    if version != INBOUND_REQUEST_SCHEMA_VERSION as u64 {
+        return Err(format!(
+            "Version de schema non prise en charge: {} (attendu {})",
+            version, INBOUND_REQUEST_SCHEMA_VERSION
+        ));
+    }
+
+    let request: GoldocabInboundRequest =
+        serde_json::from_value(value).map_err(|e| format!("Demande non conforme: {}", e))?;
+    if request.request_id != request_id {
+        return Err(format!(
+            "request_id {} ne correspond pas au fichier {}",
+            request.request_id, request_id
+        ));
+    }
+
+    Ok(request)
+}
+
+/// Verifie la demande et execute la partie backend ; le frontend recoit
+/// ensuite l'evenement pour le reste (ouverture dans l'editeur...)
+fn apply_request(handoff_dir: &Path, request: &GoldocabInboundRequest) -> Result<(), String> {
+    match &request.action {
+        GoldocabInboundAction::OpenFile { path, .. } => {
+            if path.trim().is_empty() {
+                return Err("Chemin du document manquant".to_string());
+            }
+            let file = validate_path(path)?;
+            if !file.is_file() {
+                return Err(format!("Document introuvable: {}", path));
+            }
+            Ok(())
+        }
+        GoldocabInboundAction::CancelSession { session_id, .. } => {
+            let session = read_session(handoff_dir, session_id)
+                .map_err(|_| format!("Session introuvable: {}", session_id))?;
+            if session.status != "active" {
+                return Err(format!("Session {} deja {}", session_id, session.status));
+            }
+            cancel_session(handoff_dir, session_id)
+        }
+    }
+}
+
+fn write_response(dir: &Path, request_id: &str, result: &Result<(), String>) -> Result<(), String> {
+    let response = GoldocabInboundResponse {
+        schema_version: INBOUND_REQUEST_SCHEMA_VERSION,
+        request_id: request_id.to_string(),
+        status: if result.is_ok() { "accepted" } else { "rejected" }.to_string(),
+        message: result.as_ref().err().cloned(),
+        processed_at: chrono_iso8601_now(),
+    };
+    let json = serde_json::to_string_pretty(&response)
+        .map_err(|e| format!("Erreur serialisation reponse GoldoCab: {}", e))?;
+
+    let path = response_path(dir, request_id);
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, json).map_err(|e| format!("Erreur ecriture reponse GoldoCab: {}", e))?;
+    fs::rename(&tmp, &path).map_err(|e| {
+        let _ = fs::remove_file(&tmp);
+        format!("Erreur ecriture reponse GoldoCab: {}", e)
+    })
+}
+
+/// Traite les demandes sans reponse ; renvoie celles a transmettre au frontend
+fn process_inbox(inbox_dir: &Path, handoff_dir: &Path) -> Vec<GoldocabInboundRequest> {
+    let Ok(entries) = fs::read_dir(inbox_dir) else {
+        return Vec::new();
+    };
+
+    let mut pending: Vec<(String, PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let request_id = name.strip_suffix(REQUEST_SUFFIX)?.to_string();
+            Some((request_id, e.path()))
+        })
+        .filter(|(request_id, _)| !response_path(inbox_dir, request_id).exists())
+        .collect();
+    // Ordre de depot, a defaut de mieux
+    pending.sort_by_key(|(_, path)| fs::metadata(path).and_then(|m| m.modified()).ok());
+
+    let mut accepted = Vec::new();
+    for (request_id, path) in pending {
+        // Les identifiants sont des UUID : rien d'autre n'est lu ni acquitte
+        if Uuid::parse_str(&request_id).is_err() {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+
+        // JSON tronque : fichier sans doute en cours d'ecriture
+        let complete = serde_json::from_str::<serde_json::Value>(&content).is_ok();
+        if !complete && recently_modified(&path) {
+            continue;
+        }
+
+        let request = parse_request(&request_id, &content);
+
+        let result = request
+            .as_ref()
+            .map_err(|e| e.clone())
+            .and_then(|request| apply_request(handoff_dir, request));
+
+        // Sans reponse ecrite, la demande sera relue au tour suivant
+        if write_response(inbox_dir, &request_id, &result).is_err() {
+            continue;
+        }
+        if let (Ok(request), Ok(())) = (request, result) {
+            accepted.push(request);
+        }
+    }
+
+    accepted
+}
+
+/// Transmet une demande acceptee, ou la met de cote tant que le frontend n'est pas pret
+fn deliver(app: &tauri::AppHandle, request: GoldocabInboundRequest) {
+    let state = app.state::<GoldocabInboxState>();
+    if let Ok(mut pending) = state.pending.lock() {
+        if !pending.ready {
+            pending.requests.push(request);
+            return;
+        }
+    }
+    let _ = app.emit_all(GOLDOCAB_REQUEST_EVENT, request);
+}
+
+/// Surveille `03_HANDOFF/inbox` et emet `goldocab-request` pour chaque demande
+/// valide de GoldoCab ; chaque demande recoit une reponse, acceptee ou rejetee
+pub fn start_goldocab_handoff_watcher(app: tauri::AppHandle) {
+    thread::spawn(move || loop {
+        // Dossier indisponible (disque reseau...) : nouvel essai au tour suivant
+        if let (Ok(inbox_dir), Ok(handoff_dir)) = (get_inbox_dir(), get_goldocab_handoff_dir()) {
+            for request in process_inbox(&inbox_dir, &handoff_dir) {
+                deliver(&app, request);
+            }
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    });
+}
+
+/// Appele par le frontend une fois `goldocab-request` ecoute : distribue les
+/// demandes acceptees entre-temps
+#[tauri::command]
+pub async fn flush_pending_goldocab_requests(
+    app: tauri::AppHandle,
+    state: State<'_, GoldocabInboxState>,
+) -> Result<usize, String> {
+    let pending = {
+        let mut pending = state.pending.lock().map_err(|_| "Etat de l'inbox indisponible")?;
+        pending.ready = true;
+        std::mem::take(&mut pending.requests)
+    };
+
+    let count = pending.len();
+    for request in pending {
+        let _ = app.emit_all(GOLDOCAB_REQUEST_EVENT, request);
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("citadelle-inbox-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn open_file_request(request_id: &str, path: &Path) -> String {
+        serde_json::json!({
+            "schema_version": INBOUND_REQUEST_SCHEMA_VERSION,
+            "request_id": request_id,
+            "created_at": "2026-03-12T10:00:00Z",
+            "kind": "open_file",
+            "path": path.to_string_lossy(),
+        })
+        .to_string()
+    }
+
+    fn write_request(inbox: &Path, request_id: &str, content: &str) -> PathBuf {
+        let path = inbox.join(format!("{}{}", request_id, REQUEST_SUFFIX));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn read_response(inbox: &Path, request_id: &str) -> Option<GoldocabInboundResponse> {
+        let content = fs::read_to_string(response_path(inbox, request_id)).ok()?;
+        Some(serde_json::from_str(&content).unwrap())
+    }
+
+    #[test]
+    fn parse_request_rejects_other_schema_versions() {
+        let id = Uuid::new_v4().to_string();
+        let request = open_file_request(&id, Path::new("/tmp/acte.docx"));
+        assert!(parse_request(&id, &request).is_ok());
+
+        let mut value: serde_json::Value = serde_json::from_str(&request).unwrap();
+        value["schema_version"] = 2.into();
+        let error = parse_request(&id, &value.to_string()).err().unwrap();
+        assert!(error.contains("non prise en charge: 2"), "{}", error);
+
+        value.as_object_mut().unwrap().remove("schema_version");
+        assert!(parse_request(&id, &value.to_string()).is_err());
+    }
+
+    #[test]
+    fn parse_request_requires_the_file_name_id() {
+        let id = Uuid::new_v4().to_string();
+        let other = Uuid::new_v4().to_string();
+        let request = open_file_request(&other, Path::new("/tmp/acte.docx"));
+
+        let error = parse_request(&id, &request).err().unwrap();
+        assert!(error.contains("ne correspond pas"), "{}", error);
+    }
+
+    #[test]
+    fn process_inbox_answers_each_request_once() {
+        let dir = temp_dir();
+        let inbox = dir.join(INBOX_DIR_NAME);
+        fs::create_dir_all(&inbox).unwrap();
+        let document = dir.join("acte.docx");
+        fs::write(&document, "contenu").unwrap();
+
+        let accepted_id = Uuid::new_v4().to_string();
+        write_request(&inbox, &accepted_id, &open_file_request(&accepted_id, &document));
+        let missing_id = Uuid::new_v4().to_string();
+        write_request(&inbox, &missing_id, &open_file_request(&missing_id, &dir.join("absent.docx")));
+        // Nom qui n'est pas un UUID : ni lu ni acquitte
+        write_request(&inbox, "pas-un-uuid", &open_file_request("pas-un-uuid", &document));
+
+        let accepted = process_inbox(&inbox, &dir);
+        assert_eq!(accepted.len(), 1);
+        assert_eq!(accepted[0].request_id, accepted_id);
+
+        let response = read_response(&inbox, &accepted_id).unwrap();
+        assert_eq!(response.status, "accepted");
+        assert_eq!(response.message, None);
+        let response = read_response(&inbox, &missing_id).unwrap();
+        assert_eq!(response.status, "rejected");
+        assert!(response.message.unwrap().contains("introuvable"));
+        assert!(read_response(&inbox, "pas-un-uuid").is_none());
+
+        // Deja repondues : plus rien a transmettre
+        assert!(process_inbox(&inbox, &dir).is_empty());
+        assert!(!inbox.read_dir().unwrap().any(|e| e.unwrap().path().extension() == Some("tmp".as_ref())));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn truncated_request_waits_for_the_grace_period() {
+        let dir = temp_dir();
+        let inbox = dir.join(INBOX_DIR_NAME);
+        fs::create_dir_all(&inbox).unwrap();
+
+        let id = Uuid::new_v4().to_string();
+        let complete = open_file_request(&id, &dir.join("acte.docx"));
+        let path = write_request(&inbox, &id, &complete[..complete.len() / 2]);
+
+        // Peut-etre encore en cours d'ecriture : pas de reponse
+        assert!(process_inbox(&inbox, &dir).is_empty());
+        assert!(read_response(&inbox, &id).is_none());
+
+        // Toujours tronque apres le delai : rejete
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() - WRITE_GRACE * 2)
+            .unwrap();
+        assert!(process_inbox(&inbox, &dir).is_empty());
+        let response = read_response(&inbox, &id).unwrap();
+        assert_eq!(response.status, "rejected");
+        assert!(response.message.unwrap().contains("JSON invalide"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,256 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use serde::Serialize;
+
+use super::common::validate_path;
+use super::goldocab_export::{read_export_metadata, GoldocabExportMetadata, EXPORT_METADATA_SUFFIX};
+use super::goldocab_notes::{list_note_entries, read_frontmatter_mapping, yaml_scalar_string, NoteFileEntry};
+use super::goldocab_sessions::{get_goldocab_handoff_dir, list_goldocab_sessions, GoldocabEditSession};
+
+/// Extensions dont le frontmatter est lu lors du parcours d'un projet
+const FRONTMATTER_EXTENSIONS: [&str; 3] = ["md", "markdown", "txt"];
+
+/// Le frontmatter est en tete de fichier : inutile de lire au-dela
+const FRONTMATTER_READ_LIMIT: u64 = 16 * 1024;
+
+/// Garde-fous pour les projets volumineux
+const MAX_SCANNED_FILES: usize = 20_000;
+const MAX_PROJECT_FILES: usize = 500;
+
+/// Cles designant le dossier GoldoCab, dans un frontmatter ou un `.meta.json`
+const DOSSIER_KEYS: [&str; 3] = ["dossierID", "dossier_id", "dossierId"];
+
+/// Fichier d'un projet rattache au dossier par ses metadonnees
+#[derive(Serialize, Clone)]
+pub struct GoldocabDossierProjectFile {
+    pub path: String,
+    pub name: String,
+    /// "frontmatter" (cle dans l'en-tete YAML) ou "metadata" (fichier `.meta.json` voisin)
+    pub source: String,
+    pub metadata_path: Option<String>,
+}
+
+/// Tout ce que Citadelle connait d'un dossier GoldoCab
+#[derive(Serialize, Clone)]
+pub struct GoldocabDossierInventory {
+    pub dossier_id: String,
+    pub notes: Vec<NoteFileEntry>,
+    /// Exports encore presents dans 03_HANDOFF, plus recents d'abord
+    pub exports: Vec<GoldocabExportMetadata>,
+    /// Sessions d'edition actives
+    pub sessions: Vec<GoldocabEditSession>,
+    pub project_files: Vec<GoldocabDossierProjectFile>,
+    /// Sources illisibles ou parcours interrompu ; le reste de l'inventaire reste valable
+    pub warnings: Vec<String>,
+}
+
+fn same_dossier(value: Option<&str>, dossier_id: &str) -> bool {
+    value.map(str::trim) == Some(dossier_id)
+}
+
+fn handoff_exports(dossier_id: &str) -> Result<Vec<GoldocabExportMetadata>, String> {
+    let handoff_dir = get_goldocab_handoff_dir()?;
+    let entries = fs::read_dir(&handoff_dir)
+        .map_err(|e| format!("Impossible de lire 03_HANDOFF: {}", e))?;
+
+    let mut exports: Vec<GoldocabExportMetadata> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().ends_with(EXPORT_METADATA_SUFFIX))
+        .filter_map(|e| read_export_metadata(&e.path()))
+        .filter(|m| same_dossier(m.dossier_id.as_deref(), dossier_id))
+        // Document deja repris par GoldoCab : la metadonnee seule ne dit plus rien
+        .filter(|m| handoff_dir.join(&m.file_name).is_file())
+        .collect();
+
+    exports.sort_by_key(|m| std::cmp::Reverse(m.created_at.parse::<u64>().unwrap_or(0)));
+    Ok(exports)
+}
+
+/// Valeur de la cle dossier dans le frontmatter YAML d'un fichier
+fn frontmatter_dossier_id(head: &str) -> Option<String> {
+    let (mapping, _) = read_frontmatter_mapping(head)?;
+    DOSSIER_KEYS
+        .iter()
+        .find_map(|key| mapping.get(*key).and_then(yaml_scalar_string))
+}
+
+fn read_head(path: &Path) -> Option<String> {
+    let mut head = Vec::new();
+    fs::File::open(path)
+        .ok()?
+        .take(FRONTMATTER_READ_LIMIT)
+        .read_to_end(&mut head)
+        .ok()?;
+    Some(String::from_utf8_lossy(&head).to_string())
+}
+
+/// Cle dossier d'un `.meta.json`, chaine ou nombre
+fn metadata_dossier_id(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+
+    DOSSIER_KEYS.iter().find_map(|key| match value.get(*key)? {
+        serde_json::Value::String(s) => Some(s.trim().to_string()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    })
+}
+
+struct ProjectScan {
+    dossier_id: String,
+    scanned: usize,
+    files: Vec<GoldocabDossierProjectFile>,
+}
+
+impl ProjectScan {
+    fn full(&self) -> bool {
+        self.scanned >= MAX_SCANNED_FILES || self.files.len() >= MAX_PROJECT_FILES
+    }
+
+    fn visit(&mut self, dir: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            if self.full() {
+                return;
+            }
+
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') || name == "node_modules" || name == "target" {
+                continue;
+            }
+
+            // Le type de l'entree elle-meme : un lien vers un dossier parent
+            // ferait boucler le parcours
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                self.visit(&path);
+                continue;
+            }
+            if file_type.is_symlink() && path.is_dir() {
+                continue;
+            }
+            self.scanned += 1;
+
+            if let Some(document_name) = name.strip_suffix(EXPORT_METADATA_SUFFIX) {
+                let document = dir.join(document_name);
+                if document.is_file()
+                    && same_dossier(metadata_dossier_id(&path).as_deref(), &self.dossier_id)
+                {
+                    self.files.push(GoldocabDossierProjectFile {
+                        path: document.to_string_lossy().to_string(),
+                        name: document_name.to_string(),
+                        source: "metadata".to_string(),
+                        metadata_path: Some(path.to_string_lossy().to_string()),
+                    });
+                }
+                continue;
+            }
+
+            let ext = path
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            if FRONTMATTER_EXTENSIONS.contains(&ext.as_str())
+                && same_dossier(
+                    read_head(&path).and_then(|h| frontmatter_dossier_id(&h)).as_deref(),
+                    &self.dossier_id,
+                )
+            {
+                self.files.push(GoldocabDossierProjectFile {
+                    path: path.to_string_lossy().to_string(),
+                    name,
+                    source: "frontmatter".to_string(),
+                    metadata_path: None,
+                });
+            }
+        }
+    }
+}
+
+/// Notes, exports, sessions actives et fichiers du projet (s'il y en a un)
+/// rattaches a un dossier GoldoCab
+#[tauri::command]
+pub async fn get_goldocab_dossier_inventory(
+    dossier_id: String,
+    project_root: Option<String>,
+) -> Result<GoldocabDossierInventory, String> {
+    let dossier_id = dossier_id.trim().to_string();
+    if dossier_id.is_empty() {
+        return Err("Identifiant de dossier manquant".to_string());
+    }
+
+    let mut warnings = Vec::new();
+
+    let notes = match list_note_entries() {
+        Ok(notes) => notes
+            .into_iter()
+            .filter(|n| same_dossier(n.dossier_id.as_deref(), &dossier_id))
+            .collect(),
+        Err(e) => {
+            warnings.push(format!("Notes: {}", e));
+            Vec::new()
+        }
+    };
+
+    let exports = handoff_exports(&dossier_id).unwrap_or_else(|e| {
+        warnings.push(format!("Exports: {}", e));
+        Vec::new()
+    });
+
+    let sessions = match list_goldocab_sessions().await {
+        Ok(sessions) => sessions
+            .into_iter()
+            .filter(|s| same_dossier(s.dossier_id.as_deref(), &dossier_id))
+            .collect(),
+        Err(e) => {
+            warnings.push(format!("Sessions: {}", e));
+            Vec::new()
+        }
+    };
+
+    let mut project_files = Vec::new();
+    if let Some(root) = project_root.filter(|r| !r.trim().is_empty()) {
+        let root = validate_path(&root)?;
+        if !root.is_dir() {
+            return Err("Le chemin n'est pas un dossier".to_string());
+        }
+
+        let mut scan = ProjectScan {
+            dossier_id: dossier_id.clone(),
+            scanned: 0,
+            files: Vec::new(),
+        };
+        // Parcours disque potentiellement long : hors du runtime async
+        let scan = tauri::async_runtime::spawn_blocking(move || {
+            scan.visit(&root);
+            scan
+        })
+        .await
+        .map_err(|e| format!("Erreur parcours du projet: {}", e))?;
+        if scan.full() {
+            warnings.push(format!(
+                "Projet: parcours limite a {} fichiers, {} resultats",
+                MAX_SCANNED_FILES, MAX_PROJECT_FILES
+            ));
+        }
+
+        project_files = scan.files;
+        project_files.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+
+    Ok(GoldocabDossierInventory {
+        dossier_id,
+        notes,
+        exports,
+        sessions,
+        project_files,
+        warnings,
+    })
+}
@@ -0,0 +1,302 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use super::common::{chrono_now, time_to_iso8601};
+use super::goldocab_sessions::{get_goldocab_handoff_dir, read_session};
+
+/// Version du format des fichiers de verrou partages avec GoldoCab
+pub const DOCUMENT_LOCK_VERSION: u32 = 1;
+
+const LOCKS_DIR_NAME: &str = "locks";
+
+/// Un verrou dont le heartbeat n'a pas bouge depuis ce delai peut etre casse ;
+/// chaque application rafraichit les siens au moins toutes les 10 minutes
+const LOCK_STALE_SECS: u64 = 30 * 60;
+
+/// Delai laisse a une session pour ecrire son fichier apres avoir pris le verrou ;
+/// un verrou illisible plus recent est en cours d'ecriture et n'est pas casse
+const SESSION_SETUP_GRACE_SECS: u64 = 60;
+
+/// Contenu de `03_HANDOFF/locks/<sha256 du chemin>.lock`, ecrit par Citadelle
+/// comme par GoldoCab quand l'un d'eux edite un document.
+///
+/// Un verrou est perime (et peut etre casse par l'une ou l'autre application)
+/// si son processus n'existe plus, si son heartbeat date de plus de 30 minutes,
+/// ou, pour un verrou Citadelle, si sa session n'est plus active.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GoldocabDocumentLock {
+    pub version: u32,
+    pub original_path: String,
+    /// "citadelle" ou "goldocab"
+    pub owner: String,
+    pub session_id: String,
+    #[serde(default)]
+    pub pid: Option<u32>,
+    /// Secondes depuis epoch
+    pub acquired_at: u64,
+    pub heartbeat: u64,
+}
+
+fn now_secs() -> u64 {
+    chrono_now().parse().unwrap_or(0)
+}
+
+fn lock_key(original_path: &str) -> String {
+    let canonical = Path::new(original_path)
+        .canonicalize()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| original_path.to_string());
+    format!("{:x}", Sha256::digest(canonical.as_bytes()))
+}
+
+fn lock_path(handoff_dir: &Path, original_path: &str) -> Result<PathBuf, String> {
+    let dir = handoff_dir.join(LOCKS_DIR_NAME);
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create locks directory: {}", e))?;
+    Ok(dir.join(format!("{}.lock", lock_key(original_path))))
+}
+
+fn read_lock(path: &Path) -> Option<GoldocabDocumentLock> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map(|s| s.success())
+        // Dans le doute, le verrou est respecte
+        .unwrap_or(true)
+}
+
+#[cfg(not(unix))]
+fn process_alive(_pid: u32) -> bool {
+    true
+}
+
+fn is_stale(handoff_dir: &Path, lock: &GoldocabDocumentLock) -> bool {
+    if now_secs().saturating_sub(lock.heartbeat) > LOCK_STALE_SECS {
+        return true;
+    }
+
+    if let Some(pid) = lock.pid {
+        if pid != std::process::id() && !process_alive(pid) {
+            return true;
+        }
+    }
+
+    if lock.owner == "citadelle" {
+        return match read_session(handoff_dir, &lock.session_id) {
+            Ok(session) => session.status != "active",
+            Err(_) => now_secs().saturating_sub(lock.acquired_at) > SESSION_SETUP_GRACE_SECS,
+        };
+    }
+
+    false
+}
+
+/// Prend le verrou d'un document pour une session, en cassant un verrou perime
+pub fn acquire_document_lock(
+    handoff_dir: &Path,
+    original_path: &str,
+    session_id: &str,
+) -> Result<(), String> {
+    let path = lock_path(handoff_dir, original_path)?;
+    let now = now_secs();
+    let lock = GoldocabDocumentLock {
+        version: DOCUMENT_LOCK_VERSION,
+        original_path: original_path.to_string(),
+        owner: "citadelle".to_string(),
+        session_id: session_id.to_string(),
+        pid: Some(std::process::id()),
+        acquired_at: now,
+        heartbeat: now,
+    };
+    let json = serde_json::to_string_pretty(&lock)
+        .map_err(|e| format!("Failed to serialize lock: {}", e))?;
+
+    // Le verrou est ecrit a part puis lie sous son nom : la creation du lien
+    // echoue si le fichier existe, et personne ne lit un verrou a moitie ecrit
+    let pending = path.with_extension(format!("{}.tmp", session_id));
+    fs::write(&pending, &json).map_err(|e| format!("Failed to write lock: {}", e))?;
+
+    let result = link_lock(handoff_dir, &pending, &path, original_path, now);
+    let _ = fs::remove_file(&pending);
+    result
+}
+
+fn link_lock(
+    handoff_dir: &Path,
+    pending: &Path,
+    path: &Path,
+    original_path: &str,
+    now: u64,
+) -> Result<(), String> {
+    // Deux tentatives : la seconde apres avoir casse un verrou perime
+    for _ in 0..2 {
+        match fs::hard_link(pending, path) {
+            Ok(()) => return Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(format!("Failed to create lock: {}", e)),
+        }
+
+        // Verrou libere entre-temps : nouvelle tentative
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+
+        match serde_json::from_str::<GoldocabDocumentLock>(&content) {
+            Ok(existing) if !is_stale(handoff_dir, &existing) => {
+                return Err(format!(
+                    "{} is already being edited (session {}, since {})",
+                    original_path,
+                    existing.session_id,
+                    time_to_iso8601(existing.acquired_at)
+                ));
+            }
+            Err(_) if !modified_before(path, now, SESSION_SETUP_GRACE_SECS) => {
+                return Err(format!("Lock on {} is being written", original_path));
+            }
+            _ => break_lock(path, &content),
+        }
+    }
+
+    Err(format!("Could not acquire lock on {}", original_path))
+}
+
+fn modified_before(path: &Path, now: u64, age_secs: u64) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|modified| now.saturating_sub(modified.as_secs()) > age_secs)
+        .unwrap_or(false)
+}
+
+/// Casse le verrou perime dont le contenu est `expected`. Il est d'abord mis de
+/// cote sous un nom unique puis relu : si un autre l'a casse et remplace entre
+/// la lecture et le renommage, c'est ce nouveau verrou qui a ete deplace, et il
+/// est remis en place
+fn break_lock(path: &Path, expected: &str) {
+    let aside = path.with_extension(format!("broken-{}", Uuid::new_v4()));
+    if fs::rename(path, &aside).is_err() {
+        return;
+    }
+
+    if fs::read_to_string(&aside).ok().as_deref() != Some(expected) {
+        let _ = fs::hard_link(&aside, path);
+    }
+    let _ = fs::remove_file(&aside);
+}
+
+/// Libere le verrou s'il appartient toujours a cette session
+pub fn release_document_lock(handoff_dir: &Path, original_path: &str, session_id: &str) {
+    let Ok(path) = lock_path(handoff_dir, original_path) else {
+        return;
+    };
+    if read_lock(&path).map(|l| l.session_id == session_id).unwrap_or(false) {
+        let _ = fs::remove_file(&path);
+    }
+}
+
+/// Rafraichit le heartbeat du verrou de la session
+pub fn refresh_document_lock(
+    handoff_dir: &Path,
+    original_path: &str,
+    session_id: &str,
+) -> Result<(), String> {
+    let path = lock_path(handoff_dir, original_path)?;
+
+    match read_lock(&path) {
+        Some(mut lock) if lock.session_id == session_id => {
+            lock.heartbeat = now_secs();
+            let json = serde_json::to_string_pretty(&lock)
+                .map_err(|e| format!("Failed to serialize lock: {}", e))?;
+
+            // Ecrit a part puis renomme : le verrou n'est jamais lu vide ou tronque
+            let pending = path.with_extension(format!("{}.tmp", session_id));
+            fs::write(&pending, json).map_err(|e| format!("Failed to update lock: {}", e))?;
+            fs::rename(&pending, &path).map_err(|e| {
+                let _ = fs::remove_file(&pending);
+                format!("Failed to update lock: {}", e)
+            })
+        }
+        Some(lock) => Err(format!(
+            "Lock on {} now belongs to session {}",
+            original_path, lock.session_id
+        )),
+        // Verrou casse entre-temps : on le reprend s'il est libre
+        None => acquire_document_lock(handoff_dir, original_path, session_id),
+    }
+}
+
+/// Verrou en cours sur un document, qu'il vienne de Citadelle ou de GoldoCab
+#[tauri::command]
+pub async fn get_goldocab_document_lock(path: String) -> Result<Option<GoldocabDocumentLock>, String> {
+    let handoff_dir = get_goldocab_handoff_dir()?;
+    let lock_file = lock_path(&handoff_dir, &path)?;
+
+    Ok(read_lock(&lock_file).filter(|lock| !is_stale(&handoff_dir, lock)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_handoff() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("citadelle-locks-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn unreadable_recent_lock_is_not_broken() {
+        let handoff = temp_handoff();
+        let path = lock_path(&handoff, "/tmp/acte.docx").unwrap();
+        fs::write(&path, "").unwrap();
+
+        assert!(acquire_document_lock(&handoff, "/tmp/acte.docx", "s1").is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+
+        fs::remove_dir_all(&handoff).unwrap();
+    }
+
+    #[test]
+    fn breaking_restores_a_lock_that_replaced_the_stale_one() {
+        let handoff = temp_handoff();
+        let path = lock_path(&handoff, "/tmp/acte.docx").unwrap();
+
+        // Le verrou perime lu a ete remplace par un verrou neuf entre-temps
+        fs::write(&path, "neuf").unwrap();
+        break_lock(&path, "perime");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "neuf");
+
+        break_lock(&path, "neuf");
+        assert!(!path.exists());
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 0);
+
+        fs::remove_dir_all(&handoff).unwrap();
+    }
+
+    #[test]
+    fn refresh_keeps_the_lock_readable() {
+        let handoff = temp_handoff();
+        acquire_document_lock(&handoff, "/tmp/acte.docx", "s1").unwrap();
+        let path = lock_path(&handoff, "/tmp/acte.docx").unwrap();
+
+        refresh_document_lock(&handoff, "/tmp/acte.docx", "s1").unwrap();
+        assert_eq!(read_lock(&path).unwrap().session_id, "s1");
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+        assert!(refresh_document_lock(&handoff, "/tmp/acte.docx", "s2").is_err());
+
+        fs::remove_dir_all(&handoff).unwrap();
+    }
+}
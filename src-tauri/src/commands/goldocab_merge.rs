@@ -0,0 +1,206 @@
+//! Fusion a trois voies ligne par ligne (facon diff3) entre la version de
+//! depart d'une session, la copie de travail Citadelle et l'original modifie
+//! entre-temps par GoldoCab.
+
+/// Au-dela, la table LCS devient trop couteuse en memoire
+const MAX_LCS_CELLS: usize = 16_000_000;
+
+pub const MARKER_OURS: &str = "<<<<<<< Citadelle";
+pub const MARKER_BASE: &str = "||||||| Original";
+pub const MARKER_SEPARATOR: &str = "=======";
+pub const MARKER_THEIRS: &str = ">>>>>>> GoldoCab";
+
+pub struct MergeOutcome {
+    pub text: String,
+    /// Nombre de blocs en conflit, delimites par des marqueurs
+    pub conflicts: usize,
+}
+
+/// Lignes avec leur fin de ligne, pour restituer le texte a l'identique
+fn split_lines(text: &str) -> Vec<&str> {
+    text.split_inclusive('\n').collect()
+}
+
+/// Pour chaque ligne de `base`, l'indice de la ligne appariee dans `other`
+/// selon une plus longue sous-sequence commune
+fn lcs_matches(base: &[&str], other: &[&str]) -> Result<Vec<Option<usize>>, String> {
+    let mut matches = vec![None; base.len()];
+
+    // Prefixe et suffixe communs sont apparies sans table
+    let prefix = base.iter().zip(other).take_while(|(a, b)| a == b).count();
+    let suffix = base[prefix..]
+        .iter()
+        .rev()
+        .zip(other[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    for (i, m) in matches.iter_mut().enumerate().take(prefix) {
+        *m = Some(i);
+    }
+    for s in 0..suffix {
+        matches[base.len() - 1 - s] = Some(other.len() - 1 - s);
+    }
+
+    let b = &base[prefix..base.len() - suffix];
+    let o = &other[prefix..other.len() - suffix];
+    if b.is_empty() || o.is_empty() {
+        return Ok(matches);
+    }
+
+    let width = o.len() + 1;
+    if (b.len() + 1).saturating_mul(width) > MAX_LCS_CELLS {
+        return Err("Fichier trop volumineux pour une fusion automatique".to_string());
+    }
+
+    // lengths[i][j] = LCS de b[i..] et o[j..]
+    let mut lengths = vec![0u32; (b.len() + 1) * width];
+    for i in (0..b.len()).rev() {
+        for j in (0..o.len()).rev() {
+            lengths[i * width + j] = if b[i] == o[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    while i < b.len() && j < o.len() {
+        if b[i] == o[j] {
+            matches[prefix + i] = Some(prefix + j);
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    Ok(matches)
+}
+
+fn push_marker(out: &mut String, marker: &str) {
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(marker);
+    out.push('\n');
+}
+
+/// Resout un bloc instable : un seul cote modifie, ou les deux a l'identique,
+/// sinon conflit
+fn resolve_chunk(out: &mut String, base: &[&str], ours: &[&str], theirs: &[&str]) -> bool {
+    if ours == base {
+        out.extend(theirs.iter().copied());
+    } else if theirs == base || ours == theirs {
+        out.extend(ours.iter().copied());
+    } else {
+        push_marker(out, MARKER_OURS);
+        out.extend(ours.iter().copied());
+        push_marker(out, MARKER_BASE);
+        out.extend(base.iter().copied());
+        push_marker(out, MARKER_SEPARATOR);
+        out.extend(theirs.iter().copied());
+        push_marker(out, MARKER_THEIRS);
+        return true;
+    }
+    false
+}
+
+pub fn merge_three_way(base: &str, ours: &str, theirs: &str) -> Result<MergeOutcome, String> {
+    let base_lines = split_lines(base);
+    let our_lines = split_lines(ours);
+    let their_lines = split_lines(theirs);
+
+    let ours_match = lcs_matches(&base_lines, &our_lines)?;
+    let theirs_match = lcs_matches(&base_lines, &their_lines)?;
+
+    let mut text = String::with_capacity(ours.len().max(theirs.len()));
+    let mut conflicts = 0;
+    let (mut i, mut j, mut k) = (0, 0, 0);
+
+    loop {
+        // Prochaine ligne de base presente des deux cotes, apres les positions courantes
+        let stable = (i..base_lines.len()).find_map(|bi| match (ours_match[bi], theirs_match[bi]) {
+            (Some(oj), Some(tk)) if oj >= j && tk >= k => Some((bi, oj, tk)),
+            _ => None,
+        });
+
+        match stable {
+            Some((bi, oj, tk)) if bi == i && oj == j && tk == k => {
+                text.push_str(base_lines[i]);
+                i += 1;
+                j += 1;
+                k += 1;
+            }
+            Some((bi, oj, tk)) => {
+                if resolve_chunk(&mut text, &base_lines[i..bi], &our_lines[j..oj], &their_lines[k..tk]) {
+                    conflicts += 1;
+                }
+                i = bi;
+                j = oj;
+                k = tk;
+            }
+            None => {
+                if resolve_chunk(&mut text, &base_lines[i..], &our_lines[j..], &their_lines[k..]) {
+                    conflicts += 1;
+                }
+                break;
+            }
+        }
+    }
+
+    Ok(MergeOutcome { text, conflicts })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_applies_changes_from_both_sides() {
+        let base = "titre\nun\ndeux\ntrois\n";
+        let ours = "titre\nun modifie\ndeux\ntrois\n";
+        let theirs = "titre\nun\ndeux\ntrois\nquatre\n";
+
+        let outcome = merge_three_way(base, ours, theirs).unwrap();
+        assert_eq!(outcome.conflicts, 0);
+        assert_eq!(outcome.text, "titre\nun modifie\ndeux\ntrois\nquatre\n");
+    }
+
+    #[test]
+    fn merge_keeps_identical_changes_once() {
+        let base = "a\nb\n";
+        let changed = "a\nB\n";
+
+        let outcome = merge_three_way(base, changed, changed).unwrap();
+        assert_eq!(outcome.conflicts, 0);
+        assert_eq!(outcome.text, changed);
+    }
+
+    #[test]
+    fn merge_marks_conflicting_changes() {
+        let base = "a\nb\nc\n";
+        let ours = "a\nCitadelle\nc\n";
+        let theirs = "a\nGoldoCab\nc\n";
+
+        let outcome = merge_three_way(base, ours, theirs).unwrap();
+        assert_eq!(outcome.conflicts, 1);
+        assert_eq!(
+            outcome.text,
+            format!(
+                "a\n{}\nCitadelle\n{}\nb\n{}\nGoldoCab\n{}\nc\n",
+                MARKER_OURS, MARKER_BASE, MARKER_SEPARATOR, MARKER_THEIRS
+            )
+        );
+    }
+
+    #[test]
+    fn merge_without_final_newline() {
+        let outcome = merge_three_way("a\nb", "a\nb\nc", "z\na\nb").unwrap();
+        assert_eq!(outcome.conflicts, 0);
+        assert_eq!(outcome.text, "z\na\nb\nc");
+    }
+}
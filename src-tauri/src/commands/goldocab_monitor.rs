@@ -0,0 +1,246 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use rusqlite::types::ValueRef;
+use serde::Serialize;
+use tauri::Manager;
+
+use super::goldocab_config::resolve_goldocab_db_path;
+use super::goldocab_connection::{data_version, file_identity, open_goldocab_db_at, FileIdentity};
+use super::goldocab_schema::GoldocabSchema;
+
+/// Evenement emis vers le frontend quand la base GoldoCab change
+pub const GOLDOCAB_CHANGED_EVENT: &str = "goldocab-changed";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Tables logiques suivies (cf. `GoldocabSchema`)
+const WATCHED_TABLES: [&str; 4] = ["clients", "dossiers", "items", "contacts"];
+
+#[derive(Serialize, Clone)]
+pub struct GoldocabChangeEvent {
+    /// Tables logiques modifiees : "clients", "dossiers", "items", "contacts"
+    pub tables: Vec<String>,
+    pub available: bool,
+    /// Vrai quand la base a ete rouverte (fichier remplace ou chemin modifie)
+    pub reopened: bool,
+}
+
+struct MonitoredDb {
+    conn: rusqlite::Connection,
+    path: PathBuf,
+    identity: Option<FileIdentity>,
+    data_version: i64,
+    fingerprints: HashMap<&'static str, u64>,
+}
+
+/// Empreinte legere d'une table : nombre de lignes, plus grand rowid et
+/// derniere date de modification quand la colonne existe. Une mise a jour sur
+/// place d'une table sans date de modification ne la change pas : cf. `changed_tables`
+fn table_fingerprint(conn: &rusqlite::Connection, schema: &GoldocabSchema, table: &str) -> u64 {
+    if !schema.has_table(table) {
+        return 0;
+    }
+
+    let modified = if schema.has_field(table, "dateModification") {
+        format!("MAX({})", schema.col("", table, "dateModification"))
+    } else {
+        "NULL".to_string()
+    };
+    let sql = format!(
+        "SELECT COUNT(*), MAX(rowid), {} FROM {}",
+        modified,
+        schema.table(table)
+    );
+
+    let mut hasher = DefaultHasher::new();
+    let _ = conn.query_row(&sql, [], |row| {
+        for idx in 0..3 {
+            match row.get_ref(idx) {
+                Ok(ValueRef::Integer(i)) => (1u8, i).hash(&mut hasher),
+                Ok(ValueRef::Real(f)) => (2u8, f.to_bits()).hash(&mut hasher),
+                Ok(ValueRef::Text(t)) => (3u8, t).hash(&mut hasher),
+                Ok(ValueRef::Blob(b)) => (4u8, b).hash(&mut hasher),
+                _ => 0u8.hash(&mut hasher),
+            }
+        }
+        Ok(())
+    });
+
+    hasher.finish()
+}
+
+fn fingerprints(conn: &rusqlite::Connection) -> HashMap<&'static str, u64> {
+    let Ok(schema) = GoldocabSchema::detect(conn) else {
+        return HashMap::new();
+    };
+
+    WATCHED_TABLES
+        .iter()
+        .map(|table| (*table, table_fingerprint(conn, &schema, table)))
+        .collect()
+}
+
+fn open_monitored(path: &Path) -> Option<MonitoredDb> {
+    if !path.is_file() {
+        return None;
+    }
+
+    let conn = open_goldocab_db_at(path).ok()?;
+
+    Some(MonitoredDb {
+        data_version: data_version(&conn)?,
+        fingerprints: fingerprints(&conn),
+        identity: file_identity(path),
+        path: path.to_path_buf(),
+        conn,
+    })
+}
+
+fn all_tables() -> Vec<String> {
+    WATCHED_TABLES.iter().map(|t| t.to_string()).collect()
+}
+
+/// Un tour de surveillance ; renvoie l'evenement a emettre s'il y a lieu
+fn poll(current: &mut Option<MonitoredDb>) -> Option<GoldocabChangeEvent> {
+    let path = resolve_goldocab_db_path().path;
+
+    let replaced = match current.as_ref() {
+        Some(db) => db.path != path || file_identity(&path) != db.identity,
+        None => path.is_file(),
+    };
+
+    if replaced {
+        let was_open = current.is_some();
+        *current = open_monitored(&path);
+
+        // Rien a signaler tant que la base reste introuvable
+        if !was_open && current.is_none() {
+            return None;
+        }
+
+        return Some(GoldocabChangeEvent {
+            tables: all_tables(),
+            available: current.is_some(),
+            reopened: true,
+        });
+    }
+
+    let db = current.as_mut()?;
+
+    // Fichier supprime ou devenu illisible
+    let Some(version) = data_version(&db.conn) else {
+        *current = None;
+        return Some(GoldocabChangeEvent {
+            tables: all_tables(),
+            available: false,
+            reopened: false,
+        });
+    };
+
+    Some(GoldocabChangeEvent {
+        tables: changed_tables(db, version)?,
+        available: true,
+        reopened: false,
+    })
+}
+
+/// Tables modifiees depuis le tour precedent, `None` si la base n'a pas change.
+/// `data_version` a bouge sans qu'aucune empreinte ne change : mise a jour sur
+/// place d'une table sans date de modification, toutes les tables sont signalees
+fn changed_tables(db: &mut MonitoredDb, version: i64) -> Option<Vec<String>> {
+    if version == db.data_version {
+        return None;
+    }
+    db.data_version = version;
+
+    let updated = fingerprints(&db.conn);
+    let tables: Vec<String> = WATCHED_TABLES
+        .iter()
+        .filter(|t| updated.get(*t) != db.fingerprints.get(*t))
+        .map(|t| t.to_string())
+        .collect();
+    db.fingerprints = updated;
+
+    Some(if tables.is_empty() { all_tables() } else { tables })
+}
+
+/// Surveille la base GoldoCab en arriere-plan (`PRAGMA data_version` sur une
+/// connexion en lecture seule) et emet `goldocab-changed` avec les tables touchees
+pub fn start_goldocab_monitor(app: tauri::AppHandle) {
+    thread::spawn(move || {
+        let mut current = open_monitored(&resolve_goldocab_db_path().path);
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            if let Some(event) = poll(&mut current) {
+                let _ = app.emit_all(GOLDOCAB_CHANGED_EVENT, event);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprint_follows_inserts_and_dated_updates() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE clients (id INTEGER PRIMARY KEY, nom TEXT, dateModification TEXT);
+             INSERT INTO clients (nom, dateModification) VALUES ('MARTIN', '2026-01-01');",
+        )
+        .unwrap();
+        let schema = GoldocabSchema::detect(&conn).unwrap();
+
+        let initial = table_fingerprint(&conn, &schema, "clients");
+        conn.execute("INSERT INTO clients (nom, dateModification) VALUES ('DUPONT', '2026-01-01')", [])
+            .unwrap();
+        let inserted = table_fingerprint(&conn, &schema, "clients");
+        assert_ne!(initial, inserted);
+
+        conn.execute("UPDATE clients SET nom = 'DURAND', dateModification = '2026-02-01' WHERE id = 1", [])
+            .unwrap();
+        assert_ne!(inserted, table_fingerprint(&conn, &schema, "clients"));
+
+        // Table absente de cette version de GoldoCab
+        assert_eq!(table_fingerprint(&conn, &schema, "items"), 0);
+    }
+
+    #[test]
+    fn undated_update_reports_every_table() {
+        let dir = std::env::temp_dir().join(format!("citadelle-monitor-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("goldocab.sqlite");
+
+        let writer = rusqlite::Connection::open(&path).unwrap();
+        writer
+            .execute_batch(
+                "CREATE TABLE clients (id INTEGER PRIMARY KEY, nom TEXT);
+                 CREATE TABLE dossiers (id INTEGER PRIMARY KEY, nomDossier TEXT);
+                 INSERT INTO clients (nom) VALUES ('MARTIN');",
+            )
+            .unwrap();
+
+        let mut db = open_monitored(&path).unwrap();
+        let version = data_version(&db.conn).unwrap();
+        assert_eq!(changed_tables(&mut db, version), None);
+
+        writer.execute("UPDATE clients SET nom = 'DURAND' WHERE id = 1", []).unwrap();
+        let version = data_version(&db.conn).unwrap();
+        assert_eq!(changed_tables(&mut db, version), Some(all_tables()));
+
+        writer.execute("INSERT INTO dossiers (nomDossier) VALUES ('Martin c/ Durand')", []).unwrap();
+        let version = data_version(&db.conn).unwrap();
+        assert_eq!(changed_tables(&mut db, version), Some(vec!["dossiers".to_string()]));
+
+        drop(db);
+        drop(writer);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
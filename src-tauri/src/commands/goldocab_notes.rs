@@ -1,8 +1,30 @@
 use std::fs;
 use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
+use serde_norway::{Mapping, Value};
 use uuid::Uuid;
 
+use super::common::{chrono_iso8601_now, sha256_file};
+use super::goldocab_audit::{record_audit, GoldocabAuditEntry};
+use super::goldocab_notifier::{notify_goldocab, GoldocabNotification};
+
+// Cles du frontmatter gerees par Citadelle, dans l'ordre d'ecriture d'une nouvelle note
+const KEY_ID: &str = "id";
+const KEY_TITLE: &str = "title";
+const KEY_DOSSIER_ID: &str = "dossierID";
+const KEY_CLIENT_ID: &str = "clientID";
+const KEY_FOLDER_ID: &str = "folderID";
+const KEY_TAGS: &str = "tags";
+const KEY_IS_PINNED: &str = "isPinned";
+const KEY_COLOR: &str = "color";
+const KEY_CREATED_AT: &str = "createdAt";
+const KEY_UPDATED_AT: &str = "updatedAt";
+
+const KNOWN_KEYS: [&str; 10] = [
+    KEY_ID, KEY_TITLE, KEY_DOSSIER_ID, KEY_CLIENT_ID, KEY_FOLDER_ID,
+    KEY_TAGS, KEY_IS_PINNED, KEY_COLOR, KEY_CREATED_AT, KEY_UPDATED_AT,
+];
+
 fn get_notes_dir() -> Result<PathBuf, String> {
     let home = dirs::home_dir().ok_or("Could not determine home directory")?;
     let notes_dir = home.join("Documents").join("Cabinet").join("Notes");
@@ -40,6 +62,15 @@ pub struct NoteFileContent {
     pub body: String,
 }
 
+/// Note creee ou enregistree, avec le resultat de la notification GoldoCab
+#[derive(Serialize, Clone)]
+pub struct GoldocabNoteResult {
+    pub path: String,
+    pub notification: Option<GoldocabNotification>,
+    /// GoldoCab n'a pu etre prevenu ni par URL ni par fichier de notification
+    pub notification_error: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct NoteFrontmatter {
     pub id: String,
@@ -54,130 +85,282 @@ pub struct NoteFrontmatter {
     pub updated_at: String,
 }
 
-/// Parse le frontmatter YAML d'un contenu markdown
-fn parse_frontmatter(content: &str) -> (Option<NoteFrontmatter>, String) {
-    let trimmed = content.trim_start();
-
-    if !trimmed.starts_with("---") {
-        return (None, content.to_string());
+/// Separe le bloc YAML (entre deux lignes `---`) du corps de la note
+fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let after_first = content.trim_start().strip_prefix("---")?;
+    let after_first = after_first
+        .strip_prefix("\r\n")
+        .or_else(|| after_first.strip_prefix('\n'))?;
+
+    let mut offset = 0;
+    for line in after_first.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            let body = &after_first[(offset + line.len())..];
+            return Some((&after_first[..offset], body.trim_start_matches(['\r', '\n'])));
+        }
+        offset += line.len();
     }
+    None
+}
 
-    // Trouver la fin du frontmatter
-    let after_first = &trimmed[3..];
-    let end_pos = after_first.find("\n---");
+/// Notes ecrites avant le passage a YAML : valeurs entre guillemets sans
+/// echappement des `\`, que le parseur YAML refuse. Lecture ligne a ligne,
+/// seulement pour un bloc plat `cle: valeur` comme l'ecrivait l'ancien format
+fn legacy_mapping(block: &str) -> Option<Mapping> {
+    let flat = block
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .all(|l| !l.starts_with([' ', '\t', '-', '#']) && l.contains(':'));
+    if !flat {
+        return None;
+    }
 
-    let end_pos = match end_pos {
-        Some(pos) => pos,
-        None => return (None, content.to_string()),
+    let unquote = |s: &str| -> String {
+        match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+            Some(inner) => inner.replace("\\\"", "\""),
+            None => s.to_string(),
+        }
     };
 
-    let fm_block = &after_first[1..end_pos]; // Skip leading \n
-    let body = &after_first[(end_pos + 4)..]; // Skip \n---
-    let body = body.trim_start_matches('\n');
-
-    // Parser les paires cle: valeur
-    let mut id = String::new();
-    let mut title = String::from("Sans titre");
-    let mut dossier_id: Option<String> = None;
-    let mut client_id: Option<String> = None;
-    let mut folder_id: Option<String> = None;
-    let mut tags: Vec<String> = Vec::new();
-    let mut is_pinned = false;
-    let mut color: Option<String> = None;
-    let mut created_at = String::new();
-    let mut updated_at = String::new();
-
-    for line in fm_block.lines() {
-        let line = line.trim();
-        if let Some(colon_pos) = line.find(':') {
-            let key = line[..colon_pos].trim();
-            let mut value = line[(colon_pos + 1)..].trim().to_string();
-
-            // Retirer les guillemets
-            if value.starts_with('"') && value.ends_with('"') && value.len() >= 2 {
-                value = value[1..value.len()-1].replace("\\\"", "\"");
+    let mut mapping = Mapping::new();
+    for line in block.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        let value = if let Some(inner) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            Value::Sequence(
+                inner
+                    .split(',')
+                    .map(|item| unquote(item.trim()))
+                    .filter(|item| !item.is_empty())
+                    .map(Value::String)
+                    .collect(),
+            )
+        } else {
+            match value {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                "" | "null" => Value::Null,
+                _ => Value::String(unquote(value)),
             }
+        };
+        mapping.insert(Value::String(key.trim().to_string()), value);
+    }
+    Some(mapping)
+}
 
-            match key {
-                "id" => id = value,
-                "title" => title = value,
-                "dossierID" => { if !value.is_empty() && value != "null" { dossier_id = Some(value); } }
-                "clientID" => { if !value.is_empty() && value != "null" { client_id = Some(value); } }
-                "folderID" => { if !value.is_empty() && value != "null" { folder_id = Some(value); } }
-                "isPinned" => is_pinned = value == "true",
-                "color" => { if !value.is_empty() && value != "null" { color = Some(value); } }
-                "createdAt" => created_at = value,
-                "updatedAt" => updated_at = value,
-                "tags" => {
-                    if value.starts_with('[') && value.ends_with(']') {
-                        let inner = &value[1..value.len()-1];
-                        tags = inner.split(',')
-                            .map(|s| {
-                                let s = s.trim();
-                                if s.starts_with('"') && s.ends_with('"') && s.len() >= 2 {
-                                    s[1..s.len()-1].to_string()
-                                } else {
-                                    s.to_string()
-                                }
-                            })
-                            .filter(|s| !s.is_empty())
-                            .collect();
-                    }
-                }
-                _ => {}
-            }
-        }
+fn parse_frontmatter_block(block: &str) -> Option<Mapping> {
+    if block.trim().is_empty() {
+        return Some(Mapping::new());
     }
 
-    if id.is_empty() || created_at.is_empty() {
-        return (None, content.to_string());
+    match serde_norway::from_str::<Value>(block) {
+        Ok(Value::Mapping(mapping)) => Some(mapping),
+        Ok(_) => None,
+        Err(_) => legacy_mapping(block),
     }
+}
 
-    let fm = NoteFrontmatter {
-        id,
-        title,
-        dossier_id,
-        client_id,
-        folder_id,
-        tags,
-        is_pinned,
-        color,
-        created_at,
-        updated_at,
-    };
+/// Bloc frontmatter complet (cles inconnues comprises, dans l'ordre du
+/// fichier) et corps de la note
+pub fn read_frontmatter_mapping(content: &str) -> Option<(Mapping, &str)> {
+    let (block, body) = split_frontmatter(content)?;
+    Some((parse_frontmatter_block(block)?, body))
+}
 
-    (Some(fm), body.to_string())
+/// Texte source d'une entree de premier niveau du bloc
+#[derive(Default)]
+struct SourceEntry {
+    /// Commentaires places juste avant la cle
+    comments: Vec<String>,
+    /// Lignes de l'entree, seulement si elles se relisent a l'identique
+    text: Option<String>,
 }
 
-/// Serialise le frontmatter en YAML
-fn serialize_frontmatter(fm: &NoteFrontmatter) -> String {
-    let mut lines = vec!["---".to_string()];
+/// Decoupe le bloc en entrees de premier niveau pour les reecrire telles
+/// quelles : commentaires, guillemets et mise en forme sont conserves.
+/// Renvoie aussi les commentaires restants en fin de bloc
+fn source_entries(block: &str, mapping: &Mapping) -> (Vec<(Value, SourceEntry)>, Vec<String>) {
+    let mut chunks: Vec<(Vec<String>, Vec<&str>)> = Vec::new();
+    let mut comments = Vec::new();
+
+    for line in block.lines() {
+        let top_level = !line.trim().is_empty() && !line.starts_with([' ', '\t', '-', '#']);
+        if line.starts_with('#') {
+            comments.push(line.to_string());
+        } else if top_level {
+            chunks.push((std::mem::take(&mut comments), vec![line]));
+        } else if let Some((_, lines)) = chunks.last_mut() {
+            // Suite de l'entree courante : valeur multiligne, liste, ligne vide
+            lines.push(line);
+        }
+    }
 
-    lines.push(format!("id: \"{}\"", fm.id));
-    lines.push(format!("title: \"{}\"", fm.title.replace('"', "\\\"")));
+    let entries = chunks
+        .into_iter()
+        .filter_map(|(comments, lines)| {
+            let text = lines.join("\n").trim_end().to_string();
+            let Ok(Value::Mapping(parsed)) = serde_norway::from_str::<Value>(&text) else {
+                return None;
+            };
+            let (key, value) = parsed.into_iter().next()?;
+            let text = (mapping.get(&key) == Some(&value)).then_some(text);
+            Some((key, SourceEntry { comments, text }))
+        })
+        .collect();
 
-    if let Some(ref did) = fm.dossier_id {
-        lines.push(format!("dossierID: \"{}\"", did));
-    }
-    if let Some(ref cid) = fm.client_id {
-        lines.push(format!("clientID: \"{}\"", cid));
+    (entries, comments)
+}
+
+/// Valeur scalaire sous forme de texte (`dossierID: 123` comme `"123"`)
+pub fn yaml_scalar_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Tagged(tagged) => yaml_scalar_string(&tagged.value),
+        _ => None,
     }
-    if let Some(ref fid) = fm.folder_id {
-        lines.push(format!("folderID: \"{}\"", fid));
+}
+
+fn note_frontmatter(mapping: &Mapping) -> Option<NoteFrontmatter> {
+    let text = |key: &str| mapping.get(key).and_then(yaml_scalar_string);
+    let optional = |key: &str| text(key).filter(|v| !v.trim().is_empty());
+
+    // Liste en ligne ou en bloc ; une simple chaine "a, b" est aussi acceptee
+    let tags = match mapping.get(KEY_TAGS) {
+        Some(Value::Sequence(items)) => items.iter().filter_map(yaml_scalar_string).collect(),
+        Some(value) => yaml_scalar_string(value)
+            .map(|s| s.split(',').map(|t| t.trim().to_string()).collect())
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+
+    let id = optional(KEY_ID)?;
+    let created_at = optional(KEY_CREATED_AT)?;
+
+    Some(NoteFrontmatter {
+        id,
+        title: optional(KEY_TITLE).unwrap_or_else(|| "Sans titre".to_string()),
+        dossier_id: optional(KEY_DOSSIER_ID),
+        client_id: optional(KEY_CLIENT_ID),
+        folder_id: optional(KEY_FOLDER_ID),
+        tags: tags.into_iter().filter(|t: &String| !t.is_empty()).collect(),
+        is_pinned: text(KEY_IS_PINNED).as_deref() == Some("true"),
+        color: optional(KEY_COLOR),
+        updated_at: text(KEY_UPDATED_AT).unwrap_or_default(),
+        created_at,
+    })
+}
+
+/// Parse le frontmatter YAML d'un contenu markdown
+fn parse_frontmatter(content: &str) -> (Option<NoteFrontmatter>, String) {
+    match read_frontmatter_mapping(content)
+        .and_then(|(mapping, body)| Some((note_frontmatter(&mapping)?, body)))
+    {
+        Some((fm, body)) => (Some(fm), body.to_string()),
+        None => (None, content.to_string()),
     }
+}
 
-    let tags_str: Vec<String> = fm.tags.iter().map(|t| format!("\"{}\"", t)).collect();
-    lines.push(format!("tags: [{}]", tags_str.join(", ")));
+/// Chaine YAML entre guillemets doubles ; l'echappement JSON en est un sous-ensemble valide
+fn quoted(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string())
+}
 
-    lines.push(format!("isPinned: {}", fm.is_pinned));
+fn set_key(mapping: &mut Mapping, key: &str, value: Option<Value>) {
+    match value {
+        // Une cle deja presente garde sa place
+        Some(value) => {
+            mapping.insert(Value::String(key.to_string()), value);
+        }
+        None => {
+            mapping.shift_remove(key);
+        }
+    }
+}
 
-    if let Some(ref c) = fm.color {
-        lines.push(format!("color: \"{}\"", c));
+/// Une entree `cle: valeur` ; les champs de Citadelle gardent leur forme
+/// habituelle (chaines entre guillemets, tags en liste en ligne), les autres
+/// reprennent leur texte d'origine s'il est connu, sinon sont ecrits par serde_norway
+fn yaml_entry(key: &Value, value: &Value, source: Option<&SourceEntry>) -> String {
+    match (key.as_str(), value) {
+        (Some(KEY_TAGS), Value::Sequence(items)) => {
+            let tags: Vec<String> = items
+                .iter()
+                .filter_map(yaml_scalar_string)
+                .map(|t| quoted(&t))
+                .collect();
+            format!("{}: [{}]", KEY_TAGS, tags.join(", "))
+        }
+        (Some(key), Value::String(s)) if KNOWN_KEYS.contains(&key) => {
+            format!("{}: {}", key, quoted(s))
+        }
+        (Some(key), _) if KNOWN_KEYS.contains(&key) => {
+            let mut entry = Mapping::new();
+            entry.insert(Value::String(key.to_string()), value.clone());
+            serde_norway::to_string(&entry)
+                .map(|s| s.trim_end().to_string())
+                .unwrap_or_default()
+        }
+        _ => {
+            if let Some(text) = source.and_then(|s| s.text.as_ref()) {
+                return text.clone();
+            }
+            let mut entry = Mapping::new();
+            entry.insert(key.clone(), value.clone());
+            serde_norway::to_string(&entry)
+                .map(|s| s.trim_end().to_string())
+                .unwrap_or_default()
+        }
     }
+}
 
-    lines.push(format!("createdAt: \"{}\"", fm.created_at));
-    lines.push(format!("updatedAt: \"{}\"", fm.updated_at));
+/// Serialise le frontmatter en YAML. `original` est le bloc deja present dans
+/// le fichier : l'ordre des cles, les commentaires et le texte des cles
+/// inconnues sont conserves
+fn serialize_frontmatter(fm: &NoteFrontmatter, original: Option<&str>) -> String {
+    let mut mapping = original.and_then(parse_frontmatter_block).unwrap_or_default();
+    let (sources, trailing_comments) = original
+        .map(|block| source_entries(block, &mapping))
+        .unwrap_or_default();
+    let string = |v: &Option<String>| v.clone().map(Value::String);
+    // Un identifiant inchange garde son type d'origine : `dossierID: 123` reste un nombre
+    let id_value = |key: &str, v: &Option<String>| match (mapping.get(key), v) {
+        (Some(existing), Some(v)) if yaml_scalar_string(existing).as_deref() == Some(v.as_str()) => {
+            Some(existing.clone())
+        }
+        _ => string(v),
+    };
+    let dossier_id = id_value(KEY_DOSSIER_ID, &fm.dossier_id);
+    let client_id = id_value(KEY_CLIENT_ID, &fm.client_id);
+    let folder_id = id_value(KEY_FOLDER_ID, &fm.folder_id);
+
+    set_key(&mut mapping, KEY_ID, Some(Value::String(fm.id.clone())));
+    set_key(&mut mapping, KEY_TITLE, Some(Value::String(fm.title.clone())));
+    set_key(&mut mapping, KEY_DOSSIER_ID, dossier_id);
+    set_key(&mut mapping, KEY_CLIENT_ID, client_id);
+    set_key(&mut mapping, KEY_FOLDER_ID, folder_id);
+    set_key(
+        &mut mapping,
+        KEY_TAGS,
+        Some(Value::Sequence(fm.tags.iter().cloned().map(Value::String).collect())),
+    );
+    set_key(&mut mapping, KEY_IS_PINNED, Some(Value::Bool(fm.is_pinned)));
+    set_key(&mut mapping, KEY_COLOR, string(&fm.color));
+    set_key(&mut mapping, KEY_CREATED_AT, Some(Value::String(fm.created_at.clone())));
+    set_key(&mut mapping, KEY_UPDATED_AT, Some(Value::String(fm.updated_at.clone())));
 
+    let mut lines = vec!["---".to_string()];
+    for (key, value) in mapping.iter() {
+        let source = sources.iter().find(|(k, _)| k == key).map(|(_, s)| s);
+        if let Some(source) = source {
+            lines.extend(source.comments.iter().cloned());
+        }
+        lines.push(yaml_entry(key, value, source));
+    }
+    lines.extend(trailing_comments);
     lines.push("---".to_string());
 
     lines.join("\n")
@@ -219,8 +402,8 @@ fn slugify(title: &str) -> String {
     }
 }
 
-#[tauri::command]
-pub async fn list_goldocab_notes() -> Result<Vec<NoteFileEntry>, String> {
+/// Toutes les notes du dossier Notes, plus recentes d'abord
+pub fn list_note_entries() -> Result<Vec<NoteFileEntry>, String> {
     let notes_dir = get_notes_dir()?;
     let mut entries = Vec::new();
 
@@ -278,6 +461,11 @@ pub async fn list_goldocab_notes() -> Result<Vec<NoteFileEntry>, String> {
     Ok(entries)
 }
 
+#[tauri::command]
+pub async fn list_goldocab_notes() -> Result<Vec<NoteFileEntry>, String> {
+    list_note_entries()
+}
+
 #[tauri::command]
 pub async fn read_goldocab_note(path: String) -> Result<NoteFileContent, String> {
     let content = fs::read_to_string(&path)
@@ -300,7 +488,7 @@ pub async fn create_goldocab_note(
     dossier_id: Option<String>,
     client_id: Option<String>,
     tags: Option<Vec<String>>,
-) -> Result<String, String> {
+) -> Result<GoldocabNoteResult, String> {
     let notes_dir = get_notes_dir()?;
 
     // Determiner le dossier cible
@@ -341,7 +529,7 @@ pub async fn create_goldocab_note(
         updated_at: now,
     };
 
-    let frontmatter_str = serialize_frontmatter(&fm);
+    let frontmatter_str = serialize_frontmatter(&fm, None);
     let full_content = if content.is_empty() {
         format!("{}\n", frontmatter_str)
     } else {
@@ -351,20 +539,33 @@ pub async fn create_goldocab_note(
     fs::write(&file_path, full_content)
         .map_err(|e| format!("Failed to write note: {}", e))?;
 
-    // Notifier GoldoCab
-    #[cfg(target_os = "macos")]
-    {
-        let goldocab_url = format!(
-            "goldocab://note/modified?path={}&noteID={}",
-            urlencoding::encode(&file_path.to_string_lossy()),
-            urlencoding::encode(&fm.id)
-        );
-        let _ = std::process::Command::new("open")
-            .arg(&goldocab_url)
-            .spawn();
-    }
+    record_audit(GoldocabAuditEntry {
+        event: "note_created".to_string(),
+        path: Some(file_path.to_string_lossy().to_string()),
+        sha256: sha256_file(&file_path).ok(),
+        dossier_id: fm.dossier_id.clone(),
+        detail: Some(fm.id.clone()),
+        ..Default::default()
+    });
 
-    Ok(file_path.to_string_lossy().to_string())
+    // Notifier GoldoCab (URL, sinon fichier dans 03_HANDOFF/notifications)
+    let goldocab_url = format!(
+        "goldocab://note/modified?path={}&noteID={}",
+        urlencoding::encode(&file_path.to_string_lossy()),
+        urlencoding::encode(&fm.id)
+    );
+    // La note est enregistree meme si GoldoCab n'a pu etre prevenu : l'echec
+    // est renvoye a l'appelant
+    let (notification, notification_error) = match notify_goldocab(&goldocab_url) {
+        Ok(notification) => (Some(notification), None),
+        Err(e) => (None, Some(e)),
+    };
+
+    Ok(GoldocabNoteResult {
+        path: file_path.to_string_lossy().to_string(),
+        notification,
+        notification_error,
+    })
 }
 
 #[tauri::command]
@@ -372,16 +573,25 @@ pub async fn save_goldocab_note(
     path: String,
     body: String,
     frontmatter_json: Option<String>,
-) -> Result<(), String> {
+) -> Result<GoldocabNoteResult, String> {
+    // Bloc deja sur disque : ses cles inconnues sont reecrites telles quelles
+    let existing = fs::read_to_string(&path);
+    let original = existing
+        .as_deref()
+        .ok()
+        .and_then(split_frontmatter)
+        .map(|(block, _)| block);
+
     // Lire le frontmatter existant ou utiliser celui fourni
     let fm = if let Some(ref json_str) = frontmatter_json {
         serde_json::from_str::<NoteFrontmatter>(json_str)
             .map_err(|e| format!("Failed to parse frontmatter: {}", e))?
     } else {
         // Lire depuis le fichier existant
-        let existing = fs::read_to_string(&path)
+        let existing = existing
+            .as_deref()
             .map_err(|e| format!("Failed to read existing note: {}", e))?;
-        let (existing_fm, _) = parse_frontmatter(&existing);
+        let (existing_fm, _) = parse_frontmatter(existing);
         existing_fm.ok_or("No frontmatter found in existing file")?
     };
 
@@ -389,7 +599,7 @@ pub async fn save_goldocab_note(
     let mut updated_fm = fm;
     updated_fm.updated_at = chrono_iso8601_now();
 
-    let frontmatter_str = serialize_frontmatter(&updated_fm);
+    let frontmatter_str = serialize_frontmatter(&updated_fm, original);
     let full_content = if body.is_empty() {
         format!("{}\n", frontmatter_str)
     } else {
@@ -399,27 +609,55 @@ pub async fn save_goldocab_note(
     fs::write(&path, full_content)
         .map_err(|e| format!("Failed to write note: {}", e))?;
 
-    // Notifier GoldoCab
-    #[cfg(target_os = "macos")]
-    {
-        let goldocab_url = format!(
-            "goldocab://note/modified?path={}&noteID={}",
-            urlencoding::encode(&path),
-            urlencoding::encode(&updated_fm.id)
-        );
-        let _ = std::process::Command::new("open")
-            .arg(&goldocab_url)
-            .spawn();
-    }
+    record_audit(GoldocabAuditEntry {
+        event: "note_saved".to_string(),
+        path: Some(path.clone()),
+        sha256: sha256_file(std::path::Path::new(&path)).ok(),
+        dossier_id: updated_fm.dossier_id.clone(),
+        detail: Some(updated_fm.id.clone()),
+        ..Default::default()
+    });
 
-    Ok(())
+    // Notifier GoldoCab (URL, sinon fichier dans 03_HANDOFF/notifications)
+    let goldocab_url = format!(
+        "goldocab://note/modified?path={}&noteID={}",
+        urlencoding::encode(&path),
+        urlencoding::encode(&updated_fm.id)
+    );
+    // La note est enregistree meme si GoldoCab n'a pu etre prevenu : l'echec
+    // est renvoye a l'appelant
+    let (notification, notification_error) = match notify_goldocab(&goldocab_url) {
+        Ok(notification) => (Some(notification), None),
+        Err(e) => (None, Some(e)),
+    };
+
+    Ok(GoldocabNoteResult {
+        path,
+        notification,
+        notification_error,
+    })
 }
 
 #[tauri::command]
 pub async fn delete_goldocab_note(path: String) -> Result<(), String> {
     if std::path::Path::new(&path).exists() {
+        // Empreinte et dossier releves avant suppression, pour le journal
+        let sha256 = sha256_file(std::path::Path::new(&path)).ok();
+        let fm = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| parse_frontmatter(&content).0);
+
         fs::remove_file(&path)
             .map_err(|e| format!("Failed to delete note: {}", e))?;
+
+        record_audit(GoldocabAuditEntry {
+            event: "note_deleted".to_string(),
+            path: Some(path.clone()),
+            sha256,
+            dossier_id: fm.as_ref().and_then(|f| f.dossier_id.clone()),
+            detail: fm.map(|f| f.id),
+            ..Default::default()
+        });
     }
     Ok(())
 }
@@ -447,62 +685,132 @@ pub async fn list_note_folders() -> Result<Vec<String>, String> {
     Ok(folders)
 }
 
-fn chrono_iso8601_now() -> String {
-    use std::time::{SystemTime, UNIX_EPOCH};
-    let duration = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
-    let secs = duration.as_secs();
-    // Format ISO 8601 simplifie
-    let datetime = time_to_iso8601(secs);
-    datetime
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-fn time_to_iso8601(epoch_secs: u64) -> String {
-    // Conversion simplifiee epoch -> ISO 8601
-    let days = epoch_secs / 86400;
-    let time_of_day = epoch_secs % 86400;
-    let hours = time_of_day / 3600;
-    let minutes = (time_of_day % 3600) / 60;
-    let seconds = time_of_day % 60;
-
-    // Calcul de la date depuis epoch (1970-01-01)
-    let mut y = 1970i64;
-    let mut remaining_days = days as i64;
-
-    loop {
-        let days_in_year = if is_leap_year(y) { 366 } else { 365 };
-        if remaining_days < days_in_year {
-            break;
-        }
-        remaining_days -= days_in_year;
-        y += 1;
+    #[test]
+    fn split_frontmatter_separates_block_and_body() {
+        let (block, body) = split_frontmatter("---\r\nid: a\r\n---\r\n\r\ncorps\n").unwrap();
+        assert_eq!(block, "id: a\r\n");
+        assert_eq!(body, "corps\n");
+
+        assert!(split_frontmatter("pas de frontmatter").is_none());
+        assert!(split_frontmatter("---\nid: a\n").is_none());
     }
 
-    let days_in_months: [i64; 12] = if is_leap_year(y) {
-        [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-    } else {
-        [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31]
-    };
+    #[test]
+    fn legacy_block_round_trips_through_yaml() {
+        let legacy = "---\nid: \"l1\"\ntitle: \"C:\\dir \\\"q\\\"\"\ntags: [\"a\", \"b\"]\nisPinned: false\ncreatedAt: \"2025\"\nupdatedAt: \"2025\"\n---\n\ncorps";
+        let (block, _) = split_frontmatter(legacy).unwrap();
+        assert!(serde_norway::from_str::<Value>(block).is_err());
+
+        let (fm, body) = parse_frontmatter(legacy);
+        let fm = fm.unwrap();
+        assert_eq!(fm.title, "C:\\dir \"q\"");
+        assert_eq!(fm.tags, vec!["a", "b"]);
+        assert_eq!(body, "corps");
+
+        let rewritten = format!("{}\n\n{}", serialize_frontmatter(&fm, Some(block)), body);
+        let (again, _) = parse_frontmatter(&rewritten);
+        let again = again.unwrap();
+        assert_eq!(again.title, fm.title);
+        assert_eq!(again.tags, fm.tags);
+        assert_eq!(again.created_at, "2025");
+    }
+
+    #[test]
+    fn legacy_mapping_refuses_nested_blocks() {
+        assert!(legacy_mapping("id: a\nmeta:\n  cle: valeur\n").is_none());
+        assert!(legacy_mapping("id: a\n# commentaire\n").is_none());
+    }
+
+    const GOLDOCAB_NOTE: &str = "---
+# Note creee par GoldoCab
+id: \"n1\"
+title: \"Ancien titre\"
+meta:
+  source: goldocab
+  refs:
+    - a
+    - { cle: valeur }
+# Tags en liste bloc
+tags:
+  - urgent
+  - client
+dossierID: 123
+createdAt: \"2025-01-01T10:00:00Z\"
+updatedAt: \"2025-01-01T10:00:00Z\"
+# fin du bloc
+---
+
+corps";
+
+    fn reparse(fm: &NoteFrontmatter, original: &str) -> (String, Mapping) {
+        let rewritten = format!("{}\n\ncorps", serialize_frontmatter(fm, Some(original)));
+        let (mapping, _) = read_frontmatter_mapping(&rewritten).unwrap();
+        (rewritten, mapping)
+    }
+
+    #[test]
+    fn unknown_nested_keys_and_comments_survive_a_save() {
+        let (block, _) = split_frontmatter(GOLDOCAB_NOTE).unwrap();
+        let (original, _) = read_frontmatter_mapping(GOLDOCAB_NOTE).unwrap();
+        let (fm, _) = parse_frontmatter(GOLDOCAB_NOTE);
+        let mut fm = fm.unwrap();
+        fm.updated_at = "2025-02-01T10:00:00Z".to_string();
+
+        let (rewritten, mapping) = reparse(&fm, block);
 
-    let mut m = 0;
-    for i in 0..12 {
-        if remaining_days < days_in_months[i] {
-            m = i;
-            break;
+        assert_eq!(mapping.get("meta"), original.get("meta"));
+        assert!(rewritten.contains("meta:\n  source: goldocab\n  refs:\n    - a\n    - { cle: valeur }"));
+        for comment in ["# Note creee par GoldoCab", "# Tags en liste bloc", "# fin du bloc"] {
+            assert!(rewritten.contains(comment), "{} absent", comment);
         }
-        remaining_days -= days_in_months[i];
+        // Cles gardees a leur place, cle ajoutee par Citadelle a la fin
+        let keys: Vec<_> = mapping.keys().filter_map(Value::as_str).collect();
+        assert_eq!(keys, ["id", "title", "meta", "tags", "dossierID", "createdAt", "updatedAt", "isPinned"]);
     }
 
-    format!(
-        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
-        y,
-        m + 1,
-        remaining_days + 1,
-        hours,
-        minutes,
-        seconds
-    )
-}
+    #[test]
+    fn block_list_tags_are_read_and_kept() {
+        let (block, _) = split_frontmatter(GOLDOCAB_NOTE).unwrap();
+        let (fm, _) = parse_frontmatter(GOLDOCAB_NOTE);
+        let fm = fm.unwrap();
+        assert_eq!(fm.tags, vec!["urgent", "client"]);
+
+        let (rewritten, _) = reparse(&fm, block);
+        let (again, _) = parse_frontmatter(&rewritten);
+        assert_eq!(again.unwrap().tags, fm.tags);
+    }
 
-fn is_leap_year(year: i64) -> bool {
-    (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
+    #[test]
+    fn title_with_yaml_punctuation_round_trips() {
+        let (block, _) = split_frontmatter(GOLDOCAB_NOTE).unwrap();
+        let (fm, _) = parse_frontmatter(GOLDOCAB_NOTE);
+        let mut fm = fm.unwrap();
+        fm.title = "Audience: \"renvoi\" # a confirmer \\ C:\\dossier".to_string();
+
+        let (rewritten, mapping) = reparse(&fm, block);
+        assert_eq!(mapping.get("title").and_then(Value::as_str), Some(fm.title.as_str()));
+        let (again, _) = parse_frontmatter(&rewritten);
+        assert_eq!(again.unwrap().title, fm.title);
+    }
+
+    #[test]
+    fn numeric_dossier_id_stays_a_number() {
+        let (block, _) = split_frontmatter(GOLDOCAB_NOTE).unwrap();
+        let (fm, _) = parse_frontmatter(GOLDOCAB_NOTE);
+        let mut fm = fm.unwrap();
+        assert_eq!(fm.dossier_id.as_deref(), Some("123"));
+
+        let (rewritten, mapping) = reparse(&fm, block);
+        assert!(rewritten.contains("\ndossierID: 123\n"));
+        assert_eq!(mapping.get("dossierID"), Some(&Value::Number(123.into())));
+
+        // Un dossier change par Citadelle est ecrit comme les autres champs
+        fm.dossier_id = Some("456".to_string());
+        let (_, mapping) = reparse(&fm, block);
+        assert_eq!(mapping.get("dossierID").and_then(Value::as_str), Some("456"));
+    }
 }
@@ -0,0 +1,157 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use super::common::chrono_iso8601_now;
+use super::goldocab_config::read_goldocab_settings;
+use super::goldocab_sessions::get_goldocab_handoff_dir;
+
+/// Version du format des fichiers de notification de repli
+pub const NOTIFICATION_SCHEMA_VERSION: u32 = 1;
+
+const NOTIFICATIONS_DIR_NAME: &str = "notifications";
+
+/// Au-dela, le gestionnaire d'URL est considere comme lance avec succes
+/// (certains restent attaches a l'application ouverte)
+const HANDLER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Resultat d'une notification envoyee a GoldoCab
+#[derive(Serialize, Clone)]
+pub struct GoldocabNotification {
+    pub url: String,
+    /// "url_handler" ou "notification_file"
+    pub method: String,
+    /// Fichier ecrit dans `03_HANDOFF/notifications` quand l'URL n'a pas pu etre ouverte
+    pub notification_file: Option<String>,
+    /// Echec du gestionnaire d'URL, le cas echeant
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct NotificationFile<'a> {
+    schema_version: u32,
+    url: &'a str,
+    created_at: String,
+    source: &'static str,
+    reason: &'a str,
+}
+
+/// Commande et arguments ouvrant `url` : reglage `url_handler_command`, sinon
+/// l'ouvreur par defaut de la plateforme
+fn handler_command(url: &str) -> Result<(String, Vec<String>), String> {
+    if let Some(command) = read_goldocab_settings()?
+        .url_handler_command
+        .filter(|c| !c.trim().is_empty())
+    {
+        let mut parts: Vec<String> = command.split_whitespace().map(str::to_string).collect();
+        let program = parts.remove(0);
+        if parts.iter().any(|p| p.contains("{url}")) {
+            for part in parts.iter_mut() {
+                *part = part.replace("{url}", url);
+            }
+        } else {
+            parts.push(url.to_string());
+        }
+        return Ok((program, parts));
+    }
+
+    if cfg!(target_os = "macos") {
+        Ok(("open".to_string(), vec![url.to_string()]))
+    } else if cfg!(target_os = "windows") {
+        Ok((
+            "rundll32".to_string(),
+            vec!["url.dll,FileProtocolHandler".to_string(), url.to_string()],
+        ))
+    } else {
+        Ok(("xdg-open".to_string(), vec![url.to_string()]))
+    }
+}
+
+/// Lance le gestionnaire d'URL ; un code de sortie non nul signifie en general
+/// qu'aucune application n'est associee au schema goldocab://
+fn open_url(url: &str) -> Result<(), String> {
+    let (program, args) = handler_command(url)?;
+
+    let mut child = Command::new(&program)
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Impossible de lancer {}: {}", program, e))?;
+
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => return Ok(()),
+            Ok(Some(status)) => {
+                return Err(format!(
+                    "{} a echoue ({}) : aucune application ne gere goldocab:// ?",
+                    program, status
+                ))
+            }
+            Ok(None) if started.elapsed() >= HANDLER_TIMEOUT => return Ok(()),
+            Ok(None) => thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(format!("Erreur attente {}: {}", program, e)),
+        }
+    }
+}
+
+/// Repli : GoldoCab relit `03_HANDOFF/notifications` a son demarrage. Une meme
+/// URL reecrit le meme fichier, pour ne pas accumuler les enregistrements de note
+fn write_notification_file(url: &str, reason: &str) -> Result<PathBuf, String> {
+    let dir = get_goldocab_handoff_dir()?.join(NOTIFICATIONS_DIR_NAME);
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Erreur creation dossier notifications: {}", e))?;
+
+    let key = format!("{:x}", Sha256::digest(url.as_bytes()));
+    let path = dir.join(format!("{}.notification.json", &key[..16]));
+
+    let notification = NotificationFile {
+        schema_version: NOTIFICATION_SCHEMA_VERSION,
+        url,
+        created_at: chrono_iso8601_now(),
+        source: "citadelle",
+        reason,
+    };
+    let json = serde_json::to_string_pretty(&notification)
+        .map_err(|e| format!("Erreur serialisation notification: {}", e))?;
+
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, json).map_err(|e| format!("Erreur ecriture notification: {}", e))?;
+    fs::rename(&tmp, &path).map_err(|e| {
+        let _ = fs::remove_file(&tmp);
+        format!("Erreur ecriture notification: {}", e)
+    })?;
+
+    Ok(path)
+}
+
+/// Ouvre une URL goldocab:// ; si elle ne peut pas l'etre, la depose dans le
+/// dossier d'echange. Erreur seulement si les deux echouent
+pub fn notify_goldocab(url: &str) -> Result<GoldocabNotification, String> {
+    match open_url(url) {
+        Ok(()) => Ok(GoldocabNotification {
+            url: url.to_string(),
+            method: "url_handler".to_string(),
+            notification_file: None,
+            error: None,
+        }),
+        Err(handler_error) => {
+            let path = write_notification_file(url, &handler_error).map_err(|e| {
+                format!("Notification GoldoCab impossible ({}) ; {}", handler_error, e)
+            })?;
+
+            Ok(GoldocabNotification {
+                url: url.to_string(),
+                method: "notification_file".to_string(),
+                notification_file: Some(path.to_string_lossy().to_string()),
+                error: Some(handler_error),
+            })
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+use rusqlite::types::ValueRef;
+
+/// Champ logique attendu par Citadelle et noms de colonnes connus pour ce
+/// champ selon les versions de GoldoCab (le premier est le nom canonique)
+struct FieldSpec {
+    name: &'static str,
+    required: bool,
+    /// Colonne que GoldoCab n'a pas forcement : son absence n'est pas signalee
+    /// dans `missing_fields`
+    optional: bool,
+    variants: &'static [&'static str],
+}
+
+const fn field(name: &'static str, required: bool, variants: &'static [&'static str]) -> FieldSpec {
+    FieldSpec { name, required, optional: false, variants }
+}
+
+const fn optional_field(name: &'static str, variants: &'static [&'static str]) -> FieldSpec {
+    FieldSpec { name, required: false, optional: true, variants }
+}
+
+const CLIENT_FIELDS: &[FieldSpec] = &[
+    field("id", true, &["id"]),
+    field("denomination", false, &["denomination", "raisonSociale", "raison_sociale"]),
+    field("typeClient", false, &["typeClient", "type_client", "clientType"]),
+    field("telephone", false, &["telephone", "tel", "phone"]),
+    field("email", false, &["email", "mail", "courriel"]),
+    field("ville", false, &["ville", "city"]),
+    field("codePostal", false, &["codePostal", "code_postal", "cp"]),
+    field("statutClient", false, &["statutClient", "statut_client", "statut"]),
+    field("nom", false, &["nom", "lastName", "last_name"]),
+    field("prenom", false, &["prenom", "firstName", "first_name"]),
+    field("civilite", false, &["civilite", "civility"]),
+    field("profession", false, &["profession"]),
+    field("formeJuridique", false, &["formeJuridique", "forme_juridique"]),
+    field("numeroSIREN", false, &["numeroSIREN", "numeroSiren", "numero_siren", "siren"]),
+    field("adresse", false, &["adresse", "address", "adressePostale", "adresse_postale"]),
+    optional_field("dateModification", &["dateModification", "date_modification", "updatedAt", "updated_at", "dateMaj"]),
+];
+
+const DOSSIER_FIELDS: &[FieldSpec] = &[
+    field("id", true, &["id"]),
+    field("nom", false, &["nom", "titre", "intitule"]),
+    field("typeDossier", false, &["typeDossier", "type_dossier"]),
+    field("clientID", false, &["clientID", "clientId", "client_id"]),
+    field("statutGestion", false, &["statutGestion", "statut_gestion"]),
+    field("etat", false, &["etat", "state"]),
+    field("numeroRG", false, &["numeroRG", "numeroRg", "numero_rg", "rg"]),
+    field("juridiction", false, &["juridiction", "tribunal"]),
+    field("dateAudience", false, &["dateAudience", "date_audience", "prochaineAudience"]),
+    field("priorite", false, &["priorite", "priority"]),
+    field("estFavori", false, &["estFavori", "est_favori", "favori", "isFavorite"]),
+    optional_field("cheminDossier", &["cheminDossier", "chemin_dossier", "dossierPath", "folderPath", "cheminDocuments"]),
+    optional_field("dateModification", &["dateModification", "date_modification", "updatedAt", "updated_at", "dateMaj"]),
+];
+
+const ITEM_FIELDS: &[FieldSpec] = &[
+    field("id", true, &["id"]),
+    field("titre", false, &["titre", "title"]),
+    field("contexte", false, &["contexte", "context"]),
+    field("dossierID", false, &["dossierID", "dossierId", "dossier_id"]),
+    field("estTache", false, &["estTache", "est_tache", "isTask"]),
+    field("dateEcheance", false, &["dateEcheance", "date_echeance", "echeance", "dueDate"]),
+    field("urgence", false, &["urgence", "urgency"]),
+    field("gtdPhase", false, &["gtdPhase", "gtd_phase"]),
+    field("enCours", false, &["enCours", "en_cours", "inProgress"]),
+    optional_field("dateModification", &["dateModification", "date_modification", "updatedAt", "updated_at", "dateMaj"]),
+];
+
+/// Parties et intervenants rattaches a un dossier (adversaire, avocat adverse, expert...)
+const CONTACT_FIELDS: &[FieldSpec] = &[
+    field("id", true, &["id"]),
+    field("dossierID", true, &["dossierID", "dossierId", "dossier_id"]),
+    optional_field("role", &["role", "qualite", "type", "typeContact"]),
+    optional_field("civilite", &["civilite", "civility"]),
+    optional_field("nom", &["nom", "lastName", "last_name"]),
+    optional_field("prenom", &["prenom", "firstName", "first_name"]),
+    optional_field("denomination", &["denomination", "raisonSociale", "raison_sociale"]),
+    optional_field("adresse", &["adresse", "address"]),
+    optional_field("codePostal", &["codePostal", "code_postal", "cp"]),
+    optional_field("ville", &["ville", "city"]),
+    optional_field("email", &["email", "mail", "courriel"]),
+    optional_field("telephone", &["telephone", "tel", "phone"]),
+];
+
+/// Table logique, table facultative (absente de certaines versions de
+/// GoldoCab, non signalee) et noms de table connus
+const TABLES: &[(&str, bool, &[&str])] = &[
+    ("clients", false, &["clients"]),
+    ("dossiers", false, &["dossiers"]),
+    ("items", false, &["items"]),
+    ("contacts", true, &["contacts", "parties", "intervenants"]),
+];
+
+fn table_fields(table: &str) -> &'static [FieldSpec] {
+    match table {
+        "clients" => CLIENT_FIELDS,
+        "dossiers" => DOSSIER_FIELDS,
+        "items" => ITEM_FIELDS,
+        "contacts" => CONTACT_FIELDS,
+        _ => &[],
+    }
+}
+
+/// Schema de la base GoldoCab detecte par introspection (`PRAGMA table_info`)
+pub struct GoldocabSchema {
+    /// Derniere migration GRDB appliquee, ou `PRAGMA user_version`
+    pub version: Option<String>,
+    /// Champs logiques attendus sans colonne correspondante (hors champs
+    /// facultatifs), ex. "clients.numeroSIREN"
+    pub missing_fields: Vec<String>,
+    /// table logique -> table reelle
+    tables: HashMap<String, String>,
+    /// table -> (champ logique -> colonne reelle)
+    columns: HashMap<String, HashMap<&'static str, String>>,
+}
+
+impl GoldocabSchema {
+    pub fn detect(conn: &rusqlite::Connection) -> Result<Self, String> {
+        let mut schema = GoldocabSchema {
+            version: detect_version(conn),
+            missing_fields: Vec::new(),
+            tables: HashMap::new(),
+            columns: HashMap::new(),
+        };
+
+        for (table, optional, names) in TABLES {
+            let mut found_table = None;
+            for name in names.iter() {
+                let actual = table_columns(conn, name)?;
+                if !actual.is_empty() {
+                    found_table = Some((name.to_string(), actual));
+                    break;
+                }
+            }
+
+            let Some((name, actual)) = found_table else {
+                if !optional {
+                    schema.missing_fields.push(table.to_string());
+                }
+                continue;
+            };
+
+            let mut mapped = HashMap::new();
+            for spec in table_fields(table) {
+                let found = spec.variants.iter().find_map(|variant| {
+                    actual.iter().find(|c| c.eq_ignore_ascii_case(variant))
+                });
+
+                match found {
+                    Some(column) => {
+                        mapped.insert(spec.name, column.clone());
+                    }
+                    None if spec.optional => {}
+                    None => schema.missing_fields.push(format!("{}.{}", table, spec.name)),
+                }
+            }
+
+            schema.tables.insert(table.to_string(), name);
+            schema.columns.insert(table.to_string(), mapped);
+        }
+
+        Ok(schema)
+    }
+
+    pub fn has_table(&self, table: &str) -> bool {
+        self.columns.contains_key(table)
+    }
+
+    /// Nom SQL de la table reelle correspondant a une table logique
+    pub fn table(&self, table: &str) -> String {
+        format!("\"{}\"", self.tables.get(table).map(|t| t.as_str()).unwrap_or(table))
+    }
+
+    pub fn has_field(&self, table: &str, field: &str) -> bool {
+        self.columns.get(table).map(|c| c.contains_key(field)).unwrap_or(false)
+    }
+
+    /// Expression SQL d'un champ logique : `alias."colonne"`, ou `NULL` si la
+    /// colonne est absente de cette version de GoldoCab
+    pub fn col(&self, alias: &str, table: &str, field: &str) -> String {
+        match self.columns.get(table).and_then(|c| c.get(field)) {
+            Some(column) if alias.is_empty() => format!("\"{}\"", column),
+            Some(column) => format!("{}.\"{}\"", alias, column),
+            None => "NULL".to_string(),
+        }
+    }
+
+    /// Verifie qu'une table et ses champs obligatoires sont presents
+    pub fn require(&self, table: &str) -> Result<(), String> {
+        if !self.has_table(table) {
+            return Err(format!("Schema GoldoCab non reconnu: table '{}' absente", table));
+        }
+
+        for spec in table_fields(table).iter().filter(|s| s.required) {
+            if !self.has_field(table, spec.name) {
+                return Err(format!(
+                    "Schema GoldoCab non reconnu: colonne obligatoire '{}.{}' absente",
+                    table, spec.name
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn table_columns(conn: &rusqlite::Connection, table: &str) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare(&format!("PRAGMA table_info(\"{}\")", table.replace('"', "")))
+        .map_err(|e| format!("Erreur introspection schema: {}", e))?;
+
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))
+        .map_err(|e| format!("Erreur introspection schema: {}", e))?
+        .filter_map(|c| c.ok())
+        .collect();
+
+    Ok(columns)
+}
+
+fn detect_version(conn: &rusqlite::Connection) -> Option<String> {
+    // GoldoCab gere ses migrations avec GRDB, qui les trace dans grdb_migrations
+    let migration: Option<String> = conn
+        .query_row(
+            "SELECT identifier FROM grdb_migrations ORDER BY rowid DESC LIMIT 1",
+            [],
+            |row| row.get(0),
+        )
+        .ok();
+
+    if migration.is_some() {
+        return migration;
+    }
+
+    conn.query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0))
+        .ok()
+        .filter(|v| *v > 0)
+        .map(|v| v.to_string())
+}
+
+// Lecture tolerante des valeurs : une colonne dont le type a change entre deux
+// versions (date stockee en REAL, booleen en TEXT...) ne doit pas faire echouer
+// la ligne entiere.
+
+pub fn row_text(row: &rusqlite::Row, idx: usize) -> Option<String> {
+    match row.get_ref(idx).ok()? {
+        ValueRef::Text(t) => Some(String::from_utf8_lossy(t).to_string()),
+        ValueRef::Integer(i) => Some(i.to_string()),
+        ValueRef::Real(f) => Some(f.to_string()),
+        ValueRef::Null | ValueRef::Blob(_) => None,
+    }
+}
+
+pub fn row_i64(row: &rusqlite::Row, idx: usize) -> Option<i64> {
+    match row.get_ref(idx).ok()? {
+        ValueRef::Integer(i) => Some(i),
+        ValueRef::Real(f) => Some(f as i64),
+        ValueRef::Text(t) => String::from_utf8_lossy(t).trim().parse().ok(),
+        ValueRef::Null | ValueRef::Blob(_) => None,
+    }
+}
+
+pub fn row_bool(row: &rusqlite::Row, idx: usize) -> Option<bool> {
+    match row.get_ref(idx).ok()? {
+        ValueRef::Integer(i) => Some(i != 0),
+        ValueRef::Real(f) => Some(f != 0.0),
+        ValueRef::Text(t) => match String::from_utf8_lossy(t).trim().to_lowercase().as_str() {
+            "1" | "true" | "oui" | "yes" => Some(true),
+            "0" | "false" | "non" | "no" => Some(false),
+            _ => None,
+        },
+        ValueRef::Null | ValueRef::Blob(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn optional_fields_are_not_reported_missing() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE clients (id INTEGER PRIMARY KEY, nom TEXT);
+             CREATE TABLE dossiers (id INTEGER PRIMARY KEY);
+             CREATE TABLE items (id INTEGER PRIMARY KEY);",
+        )
+        .unwrap();
+
+        let schema = GoldocabSchema::detect(&conn).unwrap();
+        assert!(schema.missing_fields.contains(&"clients.prenom".to_string()));
+        assert!(!schema.missing_fields.contains(&"clients.nom".to_string()));
+        assert!(!schema.missing_fields.iter().any(|f| f.ends_with(".dateModification")));
+        assert!(!schema.missing_fields.contains(&"dossiers.cheminDossier".to_string()));
+        assert!(!schema.missing_fields.iter().any(|f| f.starts_with("contacts")));
+    }
+}
@@ -0,0 +1,879 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use rusqlite::functions::FunctionFlags;
+use rusqlite::types::ValueRef;
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+
+use super::common::chrono_iso8601_now;
+use super::goldocab_agenda::parse_goldocab_date;
+use super::goldocab_db::{
+    build_client_display_name, client_select, dossier_select, map_client_row, map_dossier_row,
+    GoldocabClient, GoldocabDossier, CLIENT_COLUMNS,
+};
+use super::goldocab_schema::{row_bool, GoldocabSchema};
+
+/// Taille de page par defaut
+pub const DEFAULT_PAGE_SIZE: i64 = 20;
+
+/// Taille de page maximale acceptee
+const MAX_PAGE_SIZE: i64 = 500;
+
+// Poids de classement d'un terme de recherche
+const SCORE_NAME_PREFIX: u32 = 100;
+const SCORE_NAME_SUBSTRING: u32 = 50;
+const SCORE_OTHER_PREFIX: u32 = 20;
+const SCORE_OTHER_SUBSTRING: u32 = 10;
+
+/// Minuscules sans diacritiques : "Hélène" -> "helene", "Œuvre" -> "oeuvre"
+pub fn fold_text(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+
+    for c in text.nfd() {
+        match c {
+            // Diacritiques combinants issus de la decomposition NFD
+            '\u{0300}'..='\u{036F}' => {}
+            'œ' | 'Œ' => folded.push_str("oe"),
+            'æ' | 'Æ' => folded.push_str("ae"),
+            'ß' => folded.push_str("ss"),
+            c => folded.extend(c.to_lowercase()),
+        }
+    }
+
+    folded
+}
+
+/// Enregistre sur la connexion `citadelle_fold(x)`, pour comparer sans accents,
+/// et `citadelle_date_key(x)`, pour trier les dates GoldoCab en SQL ; fait une
+/// fois a l'ouverture de la base, les recherches supposent ces fonctions presentes
+pub fn register_search_functions(conn: &rusqlite::Connection) -> Result<(), String> {
+    let flags = FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC;
+
+    conn.create_scalar_function("citadelle_fold", 1, flags, |ctx| {
+        Ok(sql_text(ctx.get_raw(0)).map(|t| fold_text(&t)))
+    })
+    .map_err(|e| format!("Erreur enregistrement fonction de recherche: {}", e))?;
+
+    conn.create_scalar_function("citadelle_date_key", 1, flags, |ctx| {
+        Ok(sql_text(ctx.get_raw(0)).and_then(|t| date_key(Some(&t))))
+    })
+    .map_err(|e| format!("Erreur enregistrement fonction de recherche: {}", e))
+}
+
+fn sql_text(value: ValueRef) -> Option<String> {
+    match value {
+        ValueRef::Text(t) => Some(String::from_utf8_lossy(t).to_string()),
+        ValueRef::Integer(i) => Some(i.to_string()),
+        ValueRef::Real(f) => Some(f.to_string()),
+        ValueRef::Null | ValueRef::Blob(_) => None,
+    }
+}
+
+/// Decoupe une requete en termes normalises ("Hélène  Martin" -> ["helene", "martin"])
+pub fn tokenize_query(query: &str) -> Vec<String> {
+    fold_text(query)
+        .split(|c: char| !c.is_alphanumeric() && c != '@' && c != '/' && c != '.')
+        .map(|t| t.trim_matches('.').to_string())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+fn like_pattern(token: &str) -> String {
+    let escaped = token
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// Condition SQL : chaque terme doit apparaitre dans au moins une des colonnes
+fn token_conditions(columns: &[String], token_count: usize) -> String {
+    if token_count == 0 {
+        return "1".to_string();
+    }
+
+    (0..token_count)
+        .map(|i| {
+            let alternatives: Vec<String> = columns
+                .iter()
+                .map(|col| format!("citadelle_fold({}) LIKE ?{} ESCAPE '\\'", col, i + 1))
+                .collect();
+            format!("({})", alternatives.join(" OR "))
+        })
+        .collect::<Vec<_>>()
+        .join(" AND ")
+}
+
+fn starts_word(haystack: &str, token: &str) -> bool {
+    haystack
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| word.starts_with(token))
+}
+
+/// Score d'un terme : prefixe d'un mot du nom > sous-chaine du nom >
+/// prefixe ailleurs (email, ville...) > sous-chaine ailleurs
+fn token_score(token: &str, name_fields: &[String], other_fields: &[String]) -> u32 {
+    if name_fields.iter().any(|f| starts_word(f, token)) {
+        SCORE_NAME_PREFIX
+    } else if name_fields.iter().any(|f| f.contains(token)) {
+        SCORE_NAME_SUBSTRING
+    } else if other_fields.iter().any(|f| starts_word(f, token)) {
+        SCORE_OTHER_PREFIX
+    } else if other_fields.iter().any(|f| f.contains(token)) {
+        SCORE_OTHER_SUBSTRING
+    } else {
+        0
+    }
+}
+
+fn fold_all(values: &[&Option<String>]) -> Vec<String> {
+    values
+        .iter()
+        .filter_map(|v| v.as_deref())
+        .map(fold_text)
+        .collect()
+}
+
+fn rank_score(tokens: &[String], name_fields: &[String], other_fields: &[String]) -> u32 {
+    tokens
+        .iter()
+        .map(|t| token_score(t, name_fields, other_fields))
+        .sum()
+}
+
+/// Pagination, tri et filtres des listes GoldoCab
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct GoldocabListOptions {
+    #[serde(default)]
+    pub offset: Option<i64>,
+    #[serde(default)]
+    pub limit: Option<i64>,
+    /// "relevance" (defaut), "name", "last_activity" ou "audience_date"
+    #[serde(default)]
+    pub sort: Option<String>,
+    /// Dossiers favoris (ou clients ayant un dossier favori) en tete
+    #[serde(default)]
+    pub favorites_first: Option<bool>,
+    #[serde(default)]
+    pub statut_client: Option<Vec<String>>,
+    /// Filtres dossiers, ignores pour les clients
+    #[serde(default)]
+    pub statut_gestion: Option<Vec<String>>,
+    #[serde(default)]
+    pub etat: Option<Vec<String>>,
+    #[serde(default)]
+    pub type_dossier: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GoldocabPage<T> {
+    pub items: Vec<T>,
+    /// Nombre total de resultats, toutes pages confondues
+    pub total: i64,
+    pub offset: i64,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SortKey {
+    Relevance,
+    Name,
+    LastActivity,
+    AudienceDate,
+}
+
+fn parse_sort(sort: Option<&str>) -> Result<SortKey, String> {
+    match sort.map(str::trim).unwrap_or("") {
+        "" | "relevance" => Ok(SortKey::Relevance),
+        "name" => Ok(SortKey::Name),
+        "last_activity" => Ok(SortKey::LastActivity),
+        "audience_date" => Ok(SortKey::AudienceDate),
+        other => Err(format!("Tri GoldoCab inconnu: {}", other)),
+    }
+}
+
+/// Decalage et taille de la page demandee
+fn page_bounds(options: &GoldocabListOptions) -> (i64, i64) {
+    let offset = options.offset.unwrap_or(0).max(0);
+    let limit = options.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(0, MAX_PAGE_SIZE);
+    (offset, limit)
+}
+
+/// Une recherche par pertinence avec des termes est classee en Rust sur tous
+/// les resultats ; les autres tris et la pagination sont faits en SQL
+pub fn is_ranked(query: &str, options: &GoldocabListOptions) -> Result<bool, String> {
+    Ok(parse_sort(options.sort.as_deref())? == SortKey::Relevance && !tokenize_query(query).is_empty())
+}
+
+/// Options d'un classement, sans la pagination : toutes les pages d'une meme
+/// recherche partagent le classement mis en cache
+pub fn ranking_options(options: &GoldocabListOptions) -> GoldocabListOptions {
+    GoldocabListOptions {
+        offset: None,
+        limit: None,
+        ..options.clone()
+    }
+}
+
+/// Page d'un classement deja calcule : seuls les ids de la page sont relus
+pub fn ranked_page<T>(
+    ranked: &[i64],
+    options: &GoldocabListOptions,
+    fetch: impl FnOnce(&[i64]) -> Result<Vec<T>, String>,
+) -> Result<GoldocabPage<T>, String> {
+    let (offset, limit) = page_bounds(options);
+    let ids: Vec<i64> = ranked
+        .iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .copied()
+        .collect();
+
+    Ok(GoldocabPage {
+        items: fetch(&ids)?,
+        total: ranked.len() as i64,
+        offset,
+    })
+}
+
+/// Resultat candidat au classement par pertinence
+struct Ranked {
+    id: i64,
+    score: u32,
+    favorite: bool,
+}
+
+/// Trie (de maniere stable, l'ordre existant departage) par score decroissant
+fn rank(mut candidates: Vec<Ranked>, favorites_first: bool) -> Vec<i64> {
+    candidates.sort_by(|a, b| {
+        let favorites = if favorites_first {
+            b.favorite.cmp(&a.favorite)
+        } else {
+            Ordering::Equal
+        };
+        favorites.then_with(|| b.score.cmp(&a.score))
+    });
+
+    candidates.into_iter().map(|c| c.id).collect()
+}
+
+/// Date GoldoCab comparable : "AAAA-MM-JJ HH:MM"
+fn date_key(value: Option<&str>) -> Option<String> {
+    let (day, time) = parse_goldocab_date(value?)?;
+    Some(format!("{} {}", day, time.as_deref().unwrap_or("00:00")))
+}
+
+/// La plus recente de deux cles de date SQL, l'une ou l'autre pouvant etre NULL
+fn latest_sql(a: &str, b: &str) -> String {
+    format!("MAX(COALESCE({a}, {b}), COALESCE({b}, {a}))", a = a, b = b)
+}
+
+/// ORDER BY du tri demande sur la colonne `sort_key` ; `today` est le
+/// parametre SQL de la date du jour, utilise par le tri par audience
+fn sort_order(sort: SortKey, favorites_first: bool, today: &str) -> String {
+    let favorites = if favorites_first { "favorite DESC, " } else { "" };
+    let key = match sort {
+        SortKey::Relevance => "",
+        // Noms vides en dernier
+        SortKey::Name => "(sort_key IS NULL OR sort_key = '') ASC, sort_key ASC, ",
+        SortKey::LastActivity => "sort_key IS NULL ASC, sort_key DESC, ",
+        // Audiences a venir dans l'ordre chronologique, puis passees de la plus
+        // recente a la plus ancienne, absentes en dernier
+        SortKey::AudienceDate => {
+            return format!(
+                "{}sort_key IS NULL ASC, sort_key < {t} ASC, \
+                 CASE WHEN sort_key >= {t} THEN sort_key END ASC, sort_key DESC, ",
+                favorites,
+                t = today
+            );
+        }
+    };
+    format!("{}{}", favorites, key)
+}
+
+/// Condition SQL d'un filtre a valeurs multiples, compare sans accents ni casse ;
+/// les parametres sont ajoutes a la suite de `params`
+fn value_filter(column: String, values: &Option<Vec<String>>, params: &mut Vec<String>) -> Option<String> {
+    let values: Vec<String> = values
+        .as_deref()?
+        .iter()
+        .map(|v| fold_text(v.trim()))
+        .filter(|v| !v.is_empty())
+        .collect();
+
+    if values.is_empty() {
+        return None;
+    }
+
+    let placeholders: Vec<String> = values
+        .into_iter()
+        .map(|v| {
+            params.push(v);
+            format!("?{}", params.len())
+        })
+        .collect();
+
+    Some(format!("citadelle_fold({}) IN ({})", column, placeholders.join(", ")))
+}
+
+fn where_clause(mut conditions: Vec<String>) -> String {
+    conditions.retain(|c| c != "1");
+    if conditions.is_empty() {
+        "1".to_string()
+    } else {
+        conditions.join(" AND ")
+    }
+}
+
+/// Lignes relues par id, remises dans l'ordre des ids
+fn in_order<T>(ids: &[i64], items: Vec<T>, id: impl Fn(&T) -> i64) -> Vec<T> {
+    let mut by_id: HashMap<i64, T> = items.into_iter().map(|item| (id(&item), item)).collect();
+    ids.iter().filter_map(|i| by_id.remove(i)).collect()
+}
+
+fn id_placeholders(ids: &[i64]) -> String {
+    (1..=ids.len()).map(|i| format!("?{}", i)).collect::<Vec<_>>().join(", ")
+}
+
+/// Clause WHERE et parametres d'une recherche de clients, `None` si aucune
+/// colonne ne permet de chercher les termes
+fn client_filter(schema: &GoldocabSchema, tokens: &[String], options: &GoldocabListOptions) -> Option<(String, Vec<String>)> {
+    let col = |f: &str| schema.col("", "clients", f);
+    let searchable: Vec<String> = ["nom", "prenom", "denomination", "email", "ville", "telephone"]
+        .iter()
+        .filter(|f| schema.has_field("clients", f))
+        .map(|f| col(f))
+        .collect();
+
+    if searchable.is_empty() && !tokens.is_empty() {
+        return None;
+    }
+
+    let mut params: Vec<String> = tokens.iter().map(|t| like_pattern(t)).collect();
+    let mut conditions = vec![token_conditions(&searchable, tokens.len())];
+    conditions.extend(value_filter(col("statutClient"), &options.statut_client, &mut params));
+
+    Some((where_clause(conditions), params))
+}
+
+/// Agregat sur les dossiers du client (`{}` designe le champ), ou NULL : activite,
+/// audiences et favoris d'un client viennent de ses dossiers
+fn client_dossiers_aggregate(schema: &GoldocabSchema, field: &str, aggregate: &str) -> String {
+    let linked = schema.has_table("dossiers") && schema.has_field("dossiers", "clientID");
+    if linked && schema.has_field("dossiers", field) {
+        format!(
+            "(SELECT {} FROM dossiers sd WHERE {} = clients.id)",
+            aggregate.replace("{}", &schema.col("sd", "dossiers", field)),
+            schema.col("sd", "dossiers", "clientID"),
+        )
+    } else {
+        "NULL".to_string()
+    }
+}
+
+fn client_base_order(schema: &GoldocabSchema) -> String {
+    format!(
+        "COALESCE({}, {}, '') COLLATE NOCASE ASC, clients.id ASC",
+        schema.col("", "clients", "nom"),
+        schema.col("", "clients", "denomination"),
+    )
+}
+
+/// Ids de tous les clients trouves, du plus pertinent au moins pertinent
+pub fn rank_clients(
+    conn: &rusqlite::Connection,
+    schema: &GoldocabSchema,
+    query: &str,
+    options: &GoldocabListOptions,
+) -> Result<Vec<i64>, String> {
+    let tokens = tokenize_query(query);
+    let Some((filter, params)) = client_filter(schema, &tokens, options) else {
+        return Ok(Vec::new());
+    };
+
+    let sql = format!(
+        "SELECT {}, {} FROM clients WHERE {} ORDER BY {}",
+        client_select(schema, ""),
+        client_dossiers_aggregate(schema, "estFavori", "MAX(COALESCE({}, 0) <> 0)"),
+        filter,
+        client_base_order(schema),
+    );
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Erreur requete clients: {}", e))?;
+
+    let base = CLIENT_COLUMNS.len();
+    let rows = stmt
+        .query_map(rusqlite::params_from_iter(params.iter()), |row| {
+            Ok((map_client_row(row, 0)?, row_bool(row, base)))
+        })
+        .map_err(|e| format!("Erreur lecture clients: {}", e))?;
+
+    let mut candidates: Vec<(String, Ranked)> = rows
+        .flatten()
+        .map(|(client, favorite)| {
+            let names = fold_all(&[&client.nom, &client.prenom, &client.denomination]);
+            let others = fold_all(&[&client.email, &client.ville, &client.telephone]);
+            let display = build_client_display_name(&client.nom, &client.prenom, &client.denomination, &client.type_client)
+                .map(|n| fold_text(&n))
+                .unwrap_or_default();
+
+            (
+                display,
+                Ranked {
+                    id: client.id,
+                    score: rank_score(&tokens, &names, &others),
+                    favorite: favorite.unwrap_or(false),
+                },
+            )
+        })
+        .collect();
+
+    // A score egal, ordre alphabetique du nom affiche (le tri est stable)
+    candidates.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(rank(
+        candidates.into_iter().map(|(_, c)| c).collect(),
+        options.favorites_first.unwrap_or(false),
+    ))
+}
+
+/// Clients relus par id, dans l'ordre des ids
+pub fn clients_by_ids(
+    conn: &rusqlite::Connection,
+    schema: &GoldocabSchema,
+    ids: &[i64],
+) -> Result<Vec<GoldocabClient>, String> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let sql = format!(
+        "SELECT {} FROM clients WHERE id IN ({})",
+        client_select(schema, ""),
+        id_placeholders(ids),
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Erreur requete clients: {}", e))?;
+    let clients = stmt
+        .query_map(rusqlite::params_from_iter(ids.iter()), |row| map_client_row(row, 0))
+        .map_err(|e| format!("Erreur lecture clients: {}", e))?
+        .flatten()
+        .collect();
+
+    Ok(in_order(ids, clients, |c: &GoldocabClient| c.id))
+}
+
+/// Page de clients : classee par pertinence si la requete a des termes,
+/// sinon triee, comptee et decoupee en SQL
+pub fn search_clients(
+    conn: &rusqlite::Connection,
+    schema: &GoldocabSchema,
+    query: &str,
+    options: &GoldocabListOptions,
+) -> Result<GoldocabPage<GoldocabClient>, String> {
+    if is_ranked(query, options)? {
+        let ranked = rank_clients(conn, schema, query, options)?;
+        return ranked_page(&ranked, options, |ids| clients_by_ids(conn, schema, ids));
+    }
+
+    let sort = parse_sort(options.sort.as_deref())?;
+    let (offset, limit) = page_bounds(options);
+    let tokens = tokenize_query(query);
+    let Some((filter, mut params)) = client_filter(schema, &tokens, options) else {
+        return Ok(GoldocabPage { items: Vec::new(), total: 0, offset });
+    };
+
+    let total: i64 = conn
+        .query_row(
+            &format!("SELECT COUNT(*) FROM clients WHERE {}", filter),
+            rusqlite::params_from_iter(params.iter()),
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Erreur comptage clients: {}", e))?;
+
+    // Date du jour, parametre du seul tri par audience
+    let today = if sort == SortKey::AudienceDate {
+        params.push(date_key(Some(&chrono_iso8601_now())).unwrap_or_default());
+        format!("?{}", params.len())
+    } else {
+        String::new()
+    };
+
+    let col = |f: &str| schema.col("", "clients", f);
+    let sort_key = match sort {
+        SortKey::Relevance => "NULL".to_string(),
+        SortKey::Name => format!(
+            "citadelle_fold(CASE WHEN {t} = 'PersonneMorale' THEN {d} \
+             WHEN {p} IS NULL AND {n} IS NULL THEN {d} \
+             WHEN {p} IS NULL THEN {n} WHEN {n} IS NULL THEN {p} \
+             ELSE {p} || ' ' || {n} END)",
+            t = col("typeClient"),
+            d = col("denomination"),
+            p = col("prenom"),
+            n = col("nom"),
+        ),
+        SortKey::LastActivity => latest_sql(
+            &format!("citadelle_date_key({})", col("dateModification")),
+            &client_dossiers_aggregate(schema, "dateModification", "MAX(citadelle_date_key({}))"),
+        ),
+        // Audience a venir la plus proche, sinon la plus recente passee
+        SortKey::AudienceDate => format!(
+            "COALESCE({}, {})",
+            client_dossiers_aggregate(
+                schema,
+                "dateAudience",
+                &format!("MIN(CASE WHEN citadelle_date_key({{}}) >= {} THEN citadelle_date_key({{}}) END)", today),
+            ),
+            client_dossiers_aggregate(schema, "dateAudience", "MAX(citadelle_date_key({}))"),
+        ),
+    };
+
+    let sql = format!(
+        "SELECT {}, {} AS sort_key, {} AS favorite
+         FROM clients
+         WHERE {}
+         ORDER BY {}{}
+         LIMIT {} OFFSET {}",
+        client_select(schema, ""),
+        sort_key,
+        client_dossiers_aggregate(schema, "estFavori", "MAX(COALESCE({}, 0) <> 0)"),
+        filter,
+        sort_order(sort, options.favorites_first.unwrap_or(false), &today),
+        client_base_order(schema),
+        limit,
+        offset,
+    );
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Erreur requete clients: {}", e))?;
+    let items = stmt
+        .query_map(rusqlite::params_from_iter(params.iter()), |row| map_client_row(row, 0))
+        .map_err(|e| format!("Erreur lecture clients: {}", e))?
+        .flatten()
+        .collect();
+
+    Ok(GoldocabPage { items, total, offset })
+}
+
+/// Clause WHERE et parametres d'une recherche de dossiers, `None` si aucune
+/// colonne ne permet de chercher les termes
+fn dossier_filter(schema: &GoldocabSchema, tokens: &[String], options: &GoldocabListOptions) -> Option<(String, Vec<String>)> {
+    let d = |f: &str| schema.col("d", "dossiers", f);
+    let c = |f: &str| schema.col("c", "clients", f);
+
+    let mut searchable: Vec<String> = ["nom", "numeroRG", "juridiction"]
+        .iter()
+        .filter(|f| schema.has_field("dossiers", f))
+        .map(|f| d(f))
+        .collect();
+    searchable.extend(
+        ["nom", "prenom", "denomination"]
+            .iter()
+            .filter(|f| schema.has_field("clients", f))
+            .map(|f| c(f)),
+    );
+
+    if searchable.is_empty() && !tokens.is_empty() {
+        return None;
+    }
+
+    let mut params: Vec<String> = tokens.iter().map(|t| like_pattern(t)).collect();
+    let mut conditions = vec![token_conditions(&searchable, tokens.len())];
+    conditions.extend(value_filter(c("statutClient"), &options.statut_client, &mut params));
+    conditions.extend(value_filter(d("statutGestion"), &options.statut_gestion, &mut params));
+    conditions.extend(value_filter(d("etat"), &options.etat, &mut params));
+    conditions.extend(value_filter(d("typeDossier"), &options.type_dossier, &mut params));
+
+    Some((where_clause(conditions), params))
+}
+
+fn dossier_from(schema: &GoldocabSchema) -> String {
+    format!(
+        "dossiers d LEFT JOIN clients c ON {} = c.id",
+        schema.col("d", "dossiers", "clientID"),
+    )
+}
+
+fn dossier_base_order(schema: &GoldocabSchema) -> String {
+    format!("COALESCE({}, 0) DESC, d.id DESC", schema.col("d", "dossiers", "estFavori"))
+}
+
+/// Ids de tous les dossiers trouves, du plus pertinent au moins pertinent
+pub fn rank_dossiers(
+    conn: &rusqlite::Connection,
+    schema: &GoldocabSchema,
+    query: &str,
+    options: &GoldocabListOptions,
+) -> Result<Vec<i64>, String> {
+    let tokens = tokenize_query(query);
+    let Some((filter, params)) = dossier_filter(schema, &tokens, options) else {
+        return Ok(Vec::new());
+    };
+
+    let sql = format!(
+        "SELECT {} FROM {} WHERE {} ORDER BY {}",
+        dossier_select(schema, "d", "c"),
+        dossier_from(schema),
+        filter,
+        dossier_base_order(schema),
+    );
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Erreur requete dossiers: {}", e))?;
+    let rows = stmt
+        .query_map(rusqlite::params_from_iter(params.iter()), |row| map_dossier_row(row, 0))
+        .map_err(|e| format!("Erreur lecture dossiers: {}", e))?;
+
+    let candidates = rows
+        .flatten()
+        .map(|dossier| {
+            // Le nom du dossier, son numero RG et le nom du client priment sur la juridiction
+            let names = fold_all(&[&dossier.nom, &dossier.numero_rg, &dossier.client_name]);
+            let others = fold_all(&[&dossier.juridiction]);
+
+            Ranked {
+                id: dossier.id,
+                score: rank_score(&tokens, &names, &others),
+                favorite: dossier.est_favori.unwrap_or(false),
+            }
+        })
+        .collect();
+
+    Ok(rank(candidates, options.favorites_first.unwrap_or(false)))
+}
+
+/// Dossiers relus par id, dans l'ordre des ids
+pub fn dossiers_by_ids(
+    conn: &rusqlite::Connection,
+    schema: &GoldocabSchema,
+    ids: &[i64],
+) -> Result<Vec<GoldocabDossier>, String> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let sql = format!(
+        "SELECT {} FROM {} WHERE d.id IN ({})",
+        dossier_select(schema, "d", "c"),
+        dossier_from(schema),
+        id_placeholders(ids),
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Erreur requete dossiers: {}", e))?;
+    let dossiers = stmt
+        .query_map(rusqlite::params_from_iter(ids.iter()), |row| map_dossier_row(row, 0))
+        .map_err(|e| format!("Erreur lecture dossiers: {}", e))?
+        .flatten()
+        .collect();
+
+    Ok(in_order(ids, dossiers, |d: &GoldocabDossier| d.id))
+}
+
+/// Page de dossiers : classee par pertinence si la requete a des termes,
+/// sinon triee, comptee et decoupee en SQL
+pub fn search_dossiers(
+    conn: &rusqlite::Connection,
+    schema: &GoldocabSchema,
+    query: &str,
+    options: &GoldocabListOptions,
+) -> Result<GoldocabPage<GoldocabDossier>, String> {
+    if is_ranked(query, options)? {
+        let ranked = rank_dossiers(conn, schema, query, options)?;
+        return ranked_page(&ranked, options, |ids| dossiers_by_ids(conn, schema, ids));
+    }
+
+    let sort = parse_sort(options.sort.as_deref())?;
+    let (offset, limit) = page_bounds(options);
+    let tokens = tokenize_query(query);
+    let Some((filter, mut params)) = dossier_filter(schema, &tokens, options) else {
+        return Ok(GoldocabPage { items: Vec::new(), total: 0, offset });
+    };
+
+    let total: i64 = conn
+        .query_row(
+            &format!("SELECT COUNT(*) FROM {} WHERE {}", dossier_from(schema), filter),
+            rusqlite::params_from_iter(params.iter()),
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Erreur comptage dossiers: {}", e))?;
+
+    // Date du jour, parametre du seul tri par audience
+    let today = if sort == SortKey::AudienceDate {
+        params.push(date_key(Some(&chrono_iso8601_now())).unwrap_or_default());
+        format!("?{}", params.len())
+    } else {
+        String::new()
+    };
+
+    let d = |f: &str| schema.col("d", "dossiers", f);
+    let sort_key = match sort {
+        SortKey::Relevance => "NULL".to_string(),
+        SortKey::Name => format!("citadelle_fold({})", d("nom")),
+        // La derniere modification d'un item compte comme activite sur le dossier
+        SortKey::LastActivity => {
+            let items_modified = if schema.has_field("items", "dossierID") && schema.has_field("items", "dateModification") {
+                format!(
+                    "(SELECT MAX(citadelle_date_key({})) FROM items si WHERE {} = d.id)",
+                    schema.col("si", "items", "dateModification"),
+                    schema.col("si", "items", "dossierID"),
+                )
+            } else {
+                "NULL".to_string()
+            };
+            latest_sql(&format!("citadelle_date_key({})", d("dateModification")), &items_modified)
+        }
+        SortKey::AudienceDate => format!("citadelle_date_key({})", d("dateAudience")),
+    };
+
+    let sql = format!(
+        "SELECT {}, {} AS sort_key, COALESCE({}, 0) <> 0 AS favorite
+         FROM {}
+         WHERE {}
+         ORDER BY {}{}
+         LIMIT {} OFFSET {}",
+        dossier_select(schema, "d", "c"),
+        sort_key,
+        d("estFavori"),
+        dossier_from(schema),
+        filter,
+        sort_order(sort, options.favorites_first.unwrap_or(false), &today),
+        dossier_base_order(schema),
+        limit,
+        offset,
+    );
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Erreur requete dossiers: {}", e))?;
+    let items = stmt
+        .query_map(rusqlite::params_from_iter(params.iter()), |row| map_dossier_row(row, 0))
+        .map_err(|e| format!("Erreur lecture dossiers: {}", e))?
+        .flatten()
+        .collect();
+
+    Ok(GoldocabPage { items, total, offset })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fold_text_removes_case_and_diacritics() {
+        assert_eq!(fold_text("Hélène"), "helene");
+        assert_eq!(fold_text("ŒUVRE Lætitia"), "oeuvre laetitia");
+        assert_eq!(fold_text("Straße"), "strasse");
+        assert_eq!(fold_text("Ça coûte 10 €"), "ca coute 10 €");
+    }
+
+    #[test]
+    fn best_match_is_ranked_among_all_rows() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        register_search_functions(&conn).unwrap();
+        conn.execute_batch("CREATE TABLE clients (id INTEGER PRIMARY KEY, nom TEXT, email TEXT);")
+            .unwrap();
+        // Plus de lignes que l'ancienne limite SQL, toutes avant le meilleur
+        // resultat dans l'ordre alphabetique
+        for i in 0..600 {
+            conn.execute(
+                "INSERT INTO clients (nom, email) VALUES (?1, 'contact@dupont.fr')",
+                [format!("Aa{:03}", i)],
+            )
+            .unwrap();
+        }
+        conn.execute("INSERT INTO clients (nom) VALUES ('Dupont')", []).unwrap();
+
+        let schema = GoldocabSchema::detect(&conn).unwrap();
+        let options = GoldocabListOptions {
+            limit: Some(20),
+            ..Default::default()
+        };
+        let page = search_clients(&conn, &schema, "dupont", &options).unwrap();
+
+        assert_eq!(page.total, 601);
+        assert_eq!(page.items[0].nom.as_deref(), Some("Dupont"));
+    }
+
+    #[test]
+    fn sorted_pages_are_counted_and_cut_in_sql() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        register_search_functions(&conn).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE clients (id INTEGER PRIMARY KEY, nom TEXT, dateModification);
+             INSERT INTO clients (nom, dateModification) VALUES
+                ('Bernard', '2026-03-01T09:00:00'),
+                ('Éric', NULL),
+                ('', 1773311400),
+                ('Alain', '2026-01-15'),
+                ('Dupont', '2025-12-31T23:00:00Z');",
+        )
+        .unwrap();
+        let schema = GoldocabSchema::detect(&conn).unwrap();
+
+        let pages = |sort: &str| -> Vec<String> {
+            let mut names = Vec::new();
+            for offset in [0, 2, 4] {
+                let options = GoldocabListOptions {
+                    offset: Some(offset),
+                    limit: Some(2),
+                    sort: Some(sort.to_string()),
+                    ..Default::default()
+                };
+                let page = search_clients(&conn, &schema, "", &options).unwrap();
+                assert_eq!(page.total, 5);
+                assert_eq!(page.offset, offset);
+                names.extend(page.items.into_iter().map(|c| c.nom.unwrap_or_default()));
+            }
+            names
+        };
+
+        // Noms compares sans accents, nom vide en dernier
+        assert_eq!(pages("name"), ["Alain", "Bernard", "Dupont", "Éric", ""]);
+        // Epoch et date UTC lus comme les autres dates, sans date en dernier
+        assert_eq!(pages("last_activity"), ["", "Bernard", "Alain", "Dupont", "Éric"]);
+
+        let filtered = GoldocabListOptions {
+            limit: Some(1),
+            sort: Some("name".to_string()),
+            ..Default::default()
+        };
+        let page = search_clients(&conn, &schema, "", &filtered).unwrap();
+        assert_eq!(page.total, 5);
+        assert_eq!(page.items.len(), 1);
+    }
+
+    #[test]
+    fn ranked_pages_follow_the_ranking() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        register_search_functions(&conn).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE clients (id INTEGER PRIMARY KEY, nom TEXT, email TEXT);
+             INSERT INTO clients (nom, email) VALUES
+                ('Martin', 'dupont@exemple.fr'),
+                ('Dupontel', NULL),
+                ('Dupont', NULL),
+                ('Durand', NULL);",
+        )
+        .unwrap();
+        let schema = GoldocabSchema::detect(&conn).unwrap();
+
+        let ranking = GoldocabListOptions::default();
+        let ranked = rank_clients(&conn, &schema, "dupont", &ranking).unwrap();
+        assert_eq!(ranked, [3, 2, 1]);
+
+        let second = GoldocabListOptions {
+            offset: Some(1),
+            limit: Some(2),
+            ..Default::default()
+        };
+        let page = ranked_page(&ranked, &second, |ids| clients_by_ids(&conn, &schema, ids)).unwrap();
+        assert_eq!(page.total, 3);
+        let names: Vec<_> = page.items.iter().map(|c| c.nom.as_deref().unwrap()).collect();
+        assert_eq!(names, ["Dupontel", "Martin"]);
+    }
+}
@@ -0,0 +1,239 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use serde::Serialize;
+use tauri::Manager;
+
+use super::common::{chrono_iso8601_now, chrono_now};
+use super::goldocab_config::read_goldocab_settings;
+use super::goldocab_locks::{acquire_document_lock, refresh_document_lock, release_document_lock};
+use super::goldocab_sessions::{
+    get_goldocab_handoff_dir, read_session, write_session, GoldocabEditSession,
+};
+
+/// Sans heartbeat depuis ce delai, une session active est consideree abandonnee
+const DEFAULT_STALE_HOURS: u64 = 12;
+
+/// Les sessions terminees ou annulees restent ce nombre de jours dans 03_HANDOFF
+const DEFAULT_RETENTION_DAYS: u64 = 30;
+
+const ARCHIVE_DIR_NAME: &str = "archive";
+
+const SESSION_SUFFIX: &str = ".session.json";
+
+/// Rapport du passage de maintenance au demarrage
+pub const GOLDOCAB_SESSION_MAINTENANCE_EVENT: &str = "goldocab-session-maintenance";
+
+#[derive(Serialize, Clone, Default)]
+pub struct GoldocabSessionMaintenanceReport {
+    /// Sessions passees de "active" a "abandoned" lors de ce passage
+    pub abandoned: Vec<String>,
+    /// Sessions abandonnees dont la copie de travail peut etre recuperee
+    pub recoverable: Vec<GoldocabEditSession>,
+    pub archived: usize,
+    pub archive_dir: Option<String>,
+    /// Sessions laissees en l'etat ; le passage continue avec les suivantes
+    pub errors: Vec<String>,
+}
+
+fn session_ids(handoff_dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(handoff_dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            e.file_name()
+                .to_string_lossy()
+                .strip_suffix(SESSION_SUFFIX)
+                .map(str::to_string)
+        })
+        .collect()
+}
+
+/// Derniere activite connue : heartbeat, sinon creation
+fn last_seen(session: &GoldocabEditSession) -> Option<u64> {
+    session
+        .last_heartbeat
+        .as_deref()
+        .or(Some(session.created_at.as_str()))
+        .and_then(|t| t.parse().ok())
+}
+
+fn is_recoverable(session: &GoldocabEditSession) -> bool {
+    session.status == "abandoned"
+        && session.working_path != session.original_path
+        && Path::new(&session.working_path).is_file()
+}
+
+/// Tous les fichiers `<session_id>.*` du dossier d'echange (session, .done,
+/// copie de travail, instantane, fusion)
+fn session_files(handoff_dir: &Path, session_id: &str) -> Vec<PathBuf> {
+    let prefix = format!("{}.", session_id);
+    let Ok(entries) = fs::read_dir(handoff_dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().starts_with(&prefix))
+        .map(|e| e.path())
+        .collect()
+}
+
+fn older_than(path: &Path, age: Duration) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .map(|elapsed| elapsed > age)
+        .unwrap_or(false)
+}
+
+/// Marque les sessions inactives comme abandonnees et archive les sessions
+/// terminees ou annulees plus anciennes que la duree de retention, dans
+/// `03_HANDOFF/archive/AAAA-MM-JJ`
+pub fn run_session_maintenance() -> Result<GoldocabSessionMaintenanceReport, String> {
+    let settings = read_goldocab_settings()?;
+    let stale_secs = settings.session_stale_hours.unwrap_or(DEFAULT_STALE_HOURS) * 3600;
+    let retention = Duration::from_secs(
+        settings.session_retention_days.unwrap_or(DEFAULT_RETENTION_DAYS) * 86400,
+    );
+
+    let handoff_dir = get_goldocab_handoff_dir()?;
+    let now: u64 = chrono_now().parse().unwrap_or(0);
+    let mut report = GoldocabSessionMaintenanceReport::default();
+
+    for session_id in session_ids(&handoff_dir) {
+        let Ok(mut session) = read_session(&handoff_dir, &session_id) else {
+            continue;
+        };
+
+        match session.status.as_str() {
+            "active" => {
+                let stale = last_seen(&session)
+                    .map(|seen| now.saturating_sub(seen) > stale_secs)
+                    .unwrap_or(true);
+                if stale {
+                    session.status = "abandoned".to_string();
+                    if let Err(e) = write_session(&handoff_dir, &session) {
+                        report.errors.push(format!("{}: {}", session_id, e));
+                        continue;
+                    }
+                    // Le document redevient modifiable par GoldoCab
+                    release_document_lock(&handoff_dir, &session.original_path, &session_id);
+                    report.abandoned.push(session_id.clone());
+                }
+            }
+            "completed" | "cancelled" => {
+                let session_file = handoff_dir.join(format!("{}{}", session_id, SESSION_SUFFIX));
+                if !older_than(&session_file, retention) {
+                    continue;
+                }
+
+                let archive_dir = handoff_dir
+                    .join(ARCHIVE_DIR_NAME)
+                    .join(&chrono_iso8601_now()[..10]);
+                if let Err(e) = fs::create_dir_all(&archive_dir) {
+                    report.errors.push(format!("Failed to create archive directory: {}", e));
+                    continue;
+                }
+
+                let failures: Vec<String> = session_files(&handoff_dir, &session_id)
+                    .into_iter()
+                    .filter_map(|file| {
+                        let name = file.file_name()?.to_owned();
+                        fs::rename(&file, archive_dir.join(&name)).err().map(|e| {
+                            format!(
+                                "{}: failed to archive {}: {}",
+                                session_id,
+                                name.to_string_lossy(),
+                                e
+                            )
+                        })
+                    })
+                    .collect();
+
+                if failures.is_empty() {
+                    report.archived += 1;
+                    report.archive_dir = Some(archive_dir.to_string_lossy().to_string());
+                } else {
+                    report.errors.extend(failures);
+                }
+            }
+            _ => {}
+        }
+
+        if is_recoverable(&session) {
+            report.recoverable.push(session);
+        }
+    }
+
+    Ok(report)
+}
+
+/// Passage de maintenance au demarrage, hors du thread principal ; le rapport
+/// (y compris un echec complet) est emis vers le frontend
+pub fn start_goldocab_session_manager(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let report = run_session_maintenance().unwrap_or_else(|e| GoldocabSessionMaintenanceReport {
+            errors: vec![e],
+            ..Default::default()
+        });
+        let _ = app.emit_all(GOLDOCAB_SESSION_MAINTENANCE_EVENT, report);
+    });
+}
+
+#[tauri::command]
+pub async fn run_goldocab_session_maintenance() -> Result<GoldocabSessionMaintenanceReport, String> {
+    run_session_maintenance()
+}
+
+/// Signale que l'editeur a toujours la session ouverte
+#[tauri::command]
+pub async fn heartbeat_goldocab_edit_session(session_id: String) -> Result<(), String> {
+    let handoff_dir = get_goldocab_handoff_dir()?;
+    let mut session = read_session(&handoff_dir, &session_id)?;
+
+    if session.status != "active" {
+        return Err(format!("Session {} is {}", session_id, session.status));
+    }
+
+    refresh_document_lock(&handoff_dir, &session.original_path, &session_id)?;
+    session.last_heartbeat = Some(chrono_now());
+    write_session(&handoff_dir, &session)
+}
+
+/// Sessions abandonnees (plantage, fermeture forcee) dont la copie de travail existe encore
+#[tauri::command]
+pub async fn list_recoverable_goldocab_sessions() -> Result<Vec<GoldocabEditSession>, String> {
+    let handoff_dir = get_goldocab_handoff_dir()?;
+
+    Ok(session_ids(&handoff_dir)
+        .iter()
+        .filter_map(|id| read_session(&handoff_dir, id).ok())
+        .filter(is_recoverable)
+        .collect())
+}
+
+/// Reprend une session abandonnee sur sa copie de travail ; la terminer reste
+/// soumis a la detection de conflit avec l'original
+#[tauri::command]
+pub async fn recover_goldocab_edit_session(session_id: String) -> Result<GoldocabEditSession, String> {
+    let handoff_dir = get_goldocab_handoff_dir()?;
+    let mut session = read_session(&handoff_dir, &session_id)?;
+
+    if !is_recoverable(&session) {
+        return Err(format!("Session {} cannot be recovered", session_id));
+    }
+
+    // Le document a pu etre repris entre-temps, par GoldoCab ou une autre session
+    acquire_document_lock(&handoff_dir, &session.original_path, &session_id)?;
+
+    session.status = "active".to_string();
+    session.last_heartbeat = Some(chrono_now());
+    write_session(&handoff_dir, &session)?;
+
+    Ok(session)
+}
@@ -1,8 +1,11 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 
-use super::common::chrono_now;
+use super::common::{chrono_now, sha256_file, validate_path};
+use super::goldocab_audit::{record_audit, GoldocabAuditEntry};
+use super::goldocab_locks::{acquire_document_lock, release_document_lock};
+use super::goldocab_merge::merge_three_way;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct GoldocabEditSession {
@@ -13,6 +16,49 @@ pub struct GoldocabEditSession {
     pub dossier_name: Option<String>,
     pub created_at: String,
     pub status: String,
+    /// Empreinte de l'original a l'ouverture de la session (absente des anciennes sessions)
+    #[serde(default)]
+    pub original_sha256: Option<String>,
+    #[serde(default)]
+    pub original_size: Option<u64>,
+    /// Date de modification, millisecondes depuis epoch
+    #[serde(default)]
+    pub original_mtime: Option<u64>,
+    /// Copie de l'original, pour decrire les modifications
+    #[serde(default)]
+    pub base_path: Option<String>,
+    /// Secondes depuis epoch, rafraichi tant que l'editeur garde la session ouverte
+    #[serde(default)]
+    pub last_heartbeat: Option<String>,
+}
+
+/// Zone contigue qui differe entre l'original et le fichier final
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GoldocabChangedRange {
+    /// Position du premier octet different
+    pub offset: u64,
+    pub bytes_removed: u64,
+    pub bytes_added: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GoldocabChangeSummary {
+    pub original_sha256: String,
+    pub final_sha256: String,
+    pub original_size: u64,
+    pub final_size: u64,
+    pub mtime_changed: bool,
+    /// None si le contenu est identique ou la copie de l'original indisponible
+    pub changed_range: Option<GoldocabChangedRange>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GoldocabMergeResult {
+    pub session_id: String,
+    /// Copie de travail si la fusion s'est appliquee, sinon un fichier a part avec marqueurs de conflit
+    pub merged_path: String,
+    pub conflicts: usize,
+    pub applied: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -20,9 +66,222 @@ pub struct GoldocabSessionResult {
     pub session_id: String,
     pub final_path: String,
     pub was_modified: bool,
+    pub change_summary: Option<GoldocabChangeSummary>,
+}
+
+struct FileFingerprint {
+    sha256: String,
+    size: u64,
+    mtime: Option<u64>,
+}
+
+fn file_fingerprint(path: &Path) -> Result<FileFingerprint, String> {
+    let metadata = fs::metadata(path)
+        .map_err(|e| format!("Failed to read file metadata: {}", e))?;
+    let mtime = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64);
+
+    Ok(FileFingerprint {
+        sha256: sha256_file(path)?,
+        size: metadata.len(),
+        mtime,
+    })
+}
+
+/// Prefixe et suffixe communs retires, il reste la zone modifiee
+fn changed_range(original: &[u8], current: &[u8]) -> Option<GoldocabChangedRange> {
+    if original == current {
+        return None;
+    }
+
+    let prefix = original
+        .iter()
+        .zip(current.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let max_suffix = original.len().min(current.len()) - prefix;
+    let suffix = original
+        .iter()
+        .rev()
+        .zip(current.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    Some(GoldocabChangedRange {
+        offset: prefix as u64,
+        bytes_removed: (original.len() - prefix - suffix) as u64,
+        bytes_added: (current.len() - prefix - suffix) as u64,
+    })
+}
+
+fn change_summary(session: &GoldocabEditSession, final_path: &Path) -> Option<GoldocabChangeSummary> {
+    let original_sha256 = session.original_sha256.clone()?;
+    let current = file_fingerprint(final_path).ok()?;
+
+    let range = if current.sha256 == original_sha256 {
+        None
+    } else {
+        let base = session.base_path.as_ref().and_then(|p| fs::read(p).ok());
+        let final_content = fs::read(final_path).ok();
+        base.zip(final_content).and_then(|(b, f)| changed_range(&b, &f))
+    };
+
+    Some(GoldocabChangeSummary {
+        mtime_changed: current.mtime != session.original_mtime,
+        original_size: session.original_size.unwrap_or(0),
+        final_size: current.size,
+        changed_range: range,
+        original_sha256,
+        final_sha256: current.sha256,
+    })
+}
+
+/// Supprime la copie de l'original et la copie de travail (jamais l'original)
+fn remove_session_files(session: &GoldocabEditSession) {
+    if let Some(base) = &session.base_path {
+        let _ = fs::remove_file(base);
+    }
+    if session.working_path != session.original_path {
+        let _ = fs::remove_file(&session.working_path);
+    }
+}
+
+/// ".docx" pour "acte.docx", vide sans extension
+fn extension_suffix(path: &Path) -> String {
+    path.extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default()
+}
+
+fn working_file_name(session_id: &str, original: &Path) -> String {
+    format!("{}.work{}", session_id, extension_suffix(original))
+}
+
+pub fn read_session(handoff_dir: &Path, session_id: &str) -> Result<GoldocabEditSession, String> {
+    let session_file = handoff_dir.join(format!("{}.session.json", session_id));
+
+    if !session_file.exists() {
+        return Err(format!("Session not found: {}", session_id));
+    }
+
+    let session_content = fs::read_to_string(&session_file)
+        .map_err(|e| format!("Failed to read session: {}", e))?;
+
+    serde_json::from_str(&session_content)
+        .map_err(|e| format!("Failed to parse session: {}", e))
+}
+
+/// Session encore modifiable : une session annulee, terminee ou abandonnee ne
+/// doit plus ecrire sur l'original
+fn read_active_session(handoff_dir: &Path, session_id: &str) -> Result<GoldocabEditSession, String> {
+    let session = read_session(handoff_dir, session_id)?;
+    if session.status != "active" {
+        return Err(format!("Session {} is {}", session_id, session.status));
+    }
+    Ok(session)
+}
+
+pub fn write_session(handoff_dir: &Path, session: &GoldocabEditSession) -> Result<(), String> {
+    let session_file = handoff_dir.join(format!("{}.session.json", session.session_id));
+    fs::write(&session_file, serde_json::to_string_pretty(session).unwrap())
+        .map_err(|e| format!("Failed to update session: {}", e))
+}
+
+/// Refuse d'ecraser un original modifie depuis l'ouverture de la session
+fn check_original_unchanged(session: &GoldocabEditSession) -> Result<(), String> {
+    let Some(expected) = &session.original_sha256 else {
+        return Ok(());
+    };
+
+    let original = Path::new(&session.original_path);
+    let changed = match file_fingerprint(original) {
+        Ok(current) => &current.sha256 != expected,
+        Err(_) => true,
+    };
+
+    if changed {
+        return Err(format!(
+            "GOLDOCAB_CONFLICT: {} changed since session {} started; merge or save as another file",
+            session.original_path, session.session_id
+        ));
+    }
+
+    Ok(())
+}
+
+/// Copie par un fichier temporaire dans le dossier cible, puis renommage
+fn replace_file(source: &Path, target: &Path) -> Result<(), String> {
+    let dir = target.parent().ok_or("Invalid target path")?;
+    let tmp = dir.join(format!(
+        ".{}.citadelle-tmp",
+        target.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+    ));
+
+    fs::copy(source, &tmp).map_err(|e| format!("Failed to write file: {}", e))?;
+    fs::rename(&tmp, target).map_err(|e| {
+        let _ = fs::remove_file(&tmp);
+        format!("Failed to replace file: {}", e)
+    })
+}
+
+/// Marque la session terminee, ecrit le marqueur `.done` pour GoldoCab et
+/// supprime les fichiers propres a la session
+fn finish_session(
+    handoff_dir: &Path,
+    session: &mut GoldocabEditSession,
+    result_path: String,
+) -> Result<GoldocabSessionResult, String> {
+    session.status = "completed".to_string();
+
+    // Compare a l'empreinte prise a l'ouverture de la session
+    let summary = change_summary(session, Path::new(&result_path));
+    let was_modified = match &summary {
+        Some(summary) => summary.final_sha256 != summary.original_sha256,
+        // Sessions ouvertes avant l'enregistrement des empreintes
+        None => PathBuf::from(&result_path).exists(),
+    };
+
+    let done_file = handoff_dir.join(format!("{}.done", session.session_id));
+    let done_content = serde_json::json!({
+        "session_id": session.session_id,
+        "final_path": result_path,
+        "completed_at": chrono_now(),
+        "was_modified": was_modified,
+        "final_sha256": summary.as_ref().map(|s| s.final_sha256.clone()),
+    });
+
+    fs::write(&done_file, serde_json::to_string_pretty(&done_content).unwrap())
+        .map_err(|e| format!("Failed to write done marker: {}", e))?;
+
+    write_session(handoff_dir, session)?;
+    remove_session_files(session);
+    release_document_lock(handoff_dir, &session.original_path, &session.session_id);
+    let _ = fs::remove_file(merge_result_path(handoff_dir, session));
+
+    record_audit(GoldocabAuditEntry {
+        event: "session_completed".to_string(),
+        path: Some(session.original_path.clone()),
+        target_path: Some(result_path.clone()).filter(|p| *p != session.original_path),
+        sha256: summary.as_ref().map(|s| s.final_sha256.clone()),
+        dossier_id: session.dossier_id.clone(),
+        session_id: Some(session.session_id.clone()),
+        detail: Some(if was_modified { "modified" } else { "unchanged" }.to_string()),
+        ..Default::default()
+    });
+
+    Ok(GoldocabSessionResult {
+        session_id: session.session_id.clone(),
+        final_path: result_path,
+        was_modified,
+        change_summary: summary,
+    })
 }
 
-fn get_goldocab_handoff_dir() -> Result<PathBuf, String> {
+pub fn get_goldocab_handoff_dir() -> Result<PathBuf, String> {
     let home = dirs::home_dir().ok_or("Could not determine home directory")?;
     let handoff_dir = home.join("Documents").join("Cabinet").join("03_HANDOFF");
 
@@ -46,22 +305,64 @@ pub async fn start_goldocab_edit_session(
         return Err(format!("File not found: {}", file_path));
     }
 
-    let session_id = format!("{:x}", std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_millis());
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let handoff_dir = get_goldocab_handoff_dir()?;
+
+    // Echoue si Citadelle ou GoldoCab edite deja ce document
+    acquire_document_lock(&handoff_dir, &file_path, &session_id)?;
+
+    let session = create_session(&handoff_dir, &path, &session_id, dossier_id, dossier_name);
+    match &session {
+        Ok(session) => record_audit(GoldocabAuditEntry {
+            event: "session_started".to_string(),
+            path: Some(session.original_path.clone()),
+            target_path: Some(session.working_path.clone()),
+            sha256: session.original_sha256.clone(),
+            dossier_id: session.dossier_id.clone(),
+            session_id: Some(session.session_id.clone()),
+            ..Default::default()
+        }),
+        Err(_) => {
+            let _ = fs::remove_file(handoff_dir.join(format!("{}.base", session_id)));
+            let _ = fs::remove_file(handoff_dir.join(working_file_name(&session_id, &path)));
+            release_document_lock(&handoff_dir, &file_path, &session_id);
+        }
+    }
+    session
+}
+
+fn create_session(
+    handoff_dir: &Path,
+    path: &Path,
+    session_id: &str,
+    dossier_id: Option<String>,
+    dossier_name: Option<String>,
+) -> Result<GoldocabEditSession, String> {
+    let fingerprint = file_fingerprint(path)?;
+    let base_path = handoff_dir.join(format!("{}.base", session_id));
+    fs::copy(path, &base_path)
+        .map_err(|e| format!("Failed to snapshot original file: {}", e))?;
+
+    // Citadelle edite une copie privee ; l'original n'est remplace qu'a la fin
+    let working_path = handoff_dir.join(working_file_name(session_id, path));
+    fs::copy(path, &working_path)
+        .map_err(|e| format!("Failed to create working copy: {}", e))?;
 
     let session = GoldocabEditSession {
-        session_id: session_id.clone(),
-        original_path: file_path.clone(),
-        working_path: file_path.clone(),
+        session_id: session_id.to_string(),
+        original_path: path.to_string_lossy().to_string(),
+        working_path: working_path.to_string_lossy().to_string(),
         dossier_id,
         dossier_name,
         created_at: chrono_now(),
         status: "active".to_string(),
+        original_sha256: Some(fingerprint.sha256),
+        original_size: Some(fingerprint.size),
+        original_mtime: fingerprint.mtime,
+        base_path: Some(base_path.to_string_lossy().to_string()),
+        last_heartbeat: Some(chrono_now()),
     };
 
-    let handoff_dir = get_goldocab_handoff_dir()?;
     let session_file = handoff_dir.join(format!("{}.session.json", session_id));
 
     let session_json = serde_json::to_string_pretty(&session)
@@ -73,66 +374,179 @@ pub async fn start_goldocab_edit_session(
     Ok(session)
 }
 
+/// Resultat d'une fusion avec conflits, a relire avant de terminer la session
+fn merge_result_path(handoff_dir: &Path, session: &GoldocabEditSession) -> PathBuf {
+    handoff_dir.join(format!(
+        "{}.merge{}",
+        session.session_id,
+        extension_suffix(Path::new(&session.original_path))
+    ))
+}
+
+/// Fichier reporte sur l'original : la copie de travail par defaut, ou le
+/// resultat de fusion de la session ; aucun autre fichier n'est accepte
+fn checked_final_path(
+    handoff_dir: &Path,
+    session: &GoldocabEditSession,
+    final_path: Option<String>,
+) -> Result<String, String> {
+    let Some(final_path) = final_path else {
+        return Ok(session.working_path.clone());
+    };
+
+    let requested = validate_path(&final_path)?;
+    let allowed = [PathBuf::from(&session.working_path), merge_result_path(handoff_dir, session)];
+    if allowed.iter().any(|path| path.canonicalize().ok().as_ref() == Some(&requested)) {
+        Ok(requested.to_string_lossy().to_string())
+    } else {
+        Err(format!(
+            "{} is neither the working copy nor the merge result of session {}",
+            final_path, session.session_id
+        ))
+    }
+}
+
 #[tauri::command]
 pub async fn complete_goldocab_edit_session(
     session_id: String,
     final_path: Option<String>,
 ) -> Result<GoldocabSessionResult, String> {
     let handoff_dir = get_goldocab_handoff_dir()?;
-    let session_file = handoff_dir.join(format!("{}.session.json", &session_id));
+    let mut session = read_active_session(&handoff_dir, &session_id)?;
 
-    if !session_file.exists() {
-        return Err(format!("Session not found: {}", session_id));
+    let result_path = checked_final_path(&handoff_dir, &session, final_path)?;
+
+    // Sessions anterieures aux copies de travail : l'original etait edite directement
+    if session.working_path == session.original_path {
+        return finish_session(&handoff_dir, &mut session, result_path);
     }
 
-    let session_content = fs::read_to_string(&session_file)
-        .map_err(|e| format!("Failed to read session: {}", e))?;
+    check_original_unchanged(&session)?;
 
-    let mut session: GoldocabEditSession = serde_json::from_str(&session_content)
-        .map_err(|e| format!("Failed to parse session: {}", e))?;
+    let unchanged = match (&session.original_sha256, sha256_file(Path::new(&result_path))) {
+        (Some(expected), Ok(current)) => &current == expected,
+        _ => false,
+    };
 
-    session.status = "completed".to_string();
+    if !unchanged {
+        replace_file(Path::new(&result_path), Path::new(&session.original_path))?;
+    }
 
-    let result_path = final_path.unwrap_or(session.working_path.clone());
+    let original_path = session.original_path.clone();
+    finish_session(&handoff_dir, &mut session, original_path)
+}
 
-    let was_modified = PathBuf::from(&result_path).exists();
+/// Enregistre la copie de travail dans un autre fichier et termine la session
+/// sans toucher a l'original, par exemple apres un conflit
+#[tauri::command]
+pub async fn save_goldocab_session_as(
+    session_id: String,
+    target_path: String,
+) -> Result<GoldocabSessionResult, String> {
+    let target = validate_path(&target_path)?;
+    let handoff_dir = get_goldocab_handoff_dir()?;
+    let mut session = read_active_session(&handoff_dir, &session_id)?;
 
-    let done_file = handoff_dir.join(format!("{}.done", session_id));
-    let done_content = serde_json::json!({
-        "session_id": session_id,
-        "final_path": result_path,
-        "completed_at": chrono_now(),
-        "was_modified": was_modified
-    });
+    if Path::new(&session.original_path).canonicalize().ok().as_ref() == Some(&target) {
+        return Err("Use complete_goldocab_edit_session to write back to the original".to_string());
+    }
 
-    fs::write(&done_file, serde_json::to_string_pretty(&done_content).unwrap())
-        .map_err(|e| format!("Failed to write done marker: {}", e))?;
+    replace_file(Path::new(&session.working_path), &target)?;
+    finish_session(&handoff_dir, &mut session, target.to_string_lossy().to_string())
+}
 
-    fs::write(&session_file, serde_json::to_string_pretty(&session).unwrap())
-        .map_err(|e| format!("Failed to update session: {}", e))?;
+/// Fusion a trois de la copie de travail et de l'original modifie par GoldoCab.
+/// Sans conflit, la fusion remplace la copie de travail et la session repart de
+/// l'original actuel ; sinon le resultat, avec marqueurs de conflit, est ecrit
+/// a cote pour relecture
+#[tauri::command]
+pub async fn merge_goldocab_edit_session(session_id: String) -> Result<GoldocabMergeResult, String> {
+    let handoff_dir = get_goldocab_handoff_dir()?;
+    let mut session = read_active_session(&handoff_dir, &session_id)?;
 
-    Ok(GoldocabSessionResult {
+    if session.working_path == session.original_path {
+        return Err(format!("Session {} has no working copy to merge", session_id));
+    }
+
+    let read_text = |path: &str, label: &str| {
+        let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", label, e))?;
+        String::from_utf8(bytes)
+            .map_err(|_| format!("Three-way merge only supports text files ({} is binary)", label))
+    };
+
+    let base_path = session
+        .base_path
+        .clone()
+        .ok_or_else(|| format!("Session {} has no snapshot of the original", session_id))?;
+    let base = read_text(&base_path, "session snapshot")?;
+    let ours = read_text(&session.working_path, "working copy")?;
+    let theirs = read_text(&session.original_path, "original file")?;
+
+    let outcome = merge_three_way(&base, &ours, &theirs)?;
+
+    if outcome.conflicts > 0 {
+        let merged_path = merge_result_path(&handoff_dir, &session);
+        fs::write(&merged_path, &outcome.text)
+            .map_err(|e| format!("Failed to write merge result: {}", e))?;
+
+        return Ok(GoldocabMergeResult {
+            session_id,
+            merged_path: merged_path.to_string_lossy().to_string(),
+            conflicts: outcome.conflicts,
+            applied: false,
+        });
+    }
+
+    fs::write(&session.working_path, &outcome.text)
+        .map_err(|e| format!("Failed to update working copy: {}", e))?;
+
+    // La fusion integre deja les modifications de GoldoCab : elles deviennent la nouvelle base
+    let original = Path::new(&session.original_path);
+    let fingerprint = file_fingerprint(original)?;
+    fs::copy(original, &base_path)
+        .map_err(|e| format!("Failed to snapshot original file: {}", e))?;
+    session.original_sha256 = Some(fingerprint.sha256);
+    session.original_size = Some(fingerprint.size);
+    session.original_mtime = fingerprint.mtime;
+    write_session(&handoff_dir, &session)?;
+
+    Ok(GoldocabMergeResult {
         session_id,
-        final_path: result_path,
-        was_modified,
+        merged_path: session.working_path.clone(),
+        conflicts: 0,
+        applied: true,
     })
 }
 
+/// Annule une session, depuis l'editeur ou a la demande de GoldoCab
+pub fn cancel_session(handoff_dir: &Path, session_id: &str) -> Result<(), String> {
+    let mut session = read_session(handoff_dir, session_id)?;
+    session.status = "cancelled".to_string();
+    write_session(handoff_dir, &session)?;
+
+    // Rien a restaurer : l'original n'a jamais ete modifie
+    remove_session_files(&session);
+    release_document_lock(handoff_dir, &session.original_path, &session.session_id);
+    record_audit(GoldocabAuditEntry {
+        event: "session_cancelled".to_string(),
+        path: Some(session.original_path.clone()),
+        dossier_id: session.dossier_id.clone(),
+        session_id: Some(session.session_id.clone()),
+        ..Default::default()
+    });
+
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn cancel_goldocab_edit_session(session_id: String) -> Result<(), String> {
     let handoff_dir = get_goldocab_handoff_dir()?;
-    let session_file = handoff_dir.join(format!("{}.session.json", &session_id));
 
-    if session_file.exists() {
-        if let Ok(content) = fs::read_to_string(&session_file) {
-            if let Ok(mut session) = serde_json::from_str::<GoldocabEditSession>(&content) {
-                session.status = "cancelled".to_string();
-                let _ = fs::write(&session_file, serde_json::to_string_pretty(&session).unwrap());
-            }
-        }
+    // Session deja nettoyee : rien a annuler
+    if !handoff_dir.join(format!("{}.session.json", session_id)).exists() {
+        return Ok(());
     }
-
-    Ok(())
+    cancel_session(&handoff_dir, &session_id)
 }
 
 #[tauri::command]
@@ -177,45 +591,43 @@ pub async fn list_goldocab_sessions() -> Result<Vec<GoldocabEditSession>, String
     Ok(sessions)
 }
 
-#[tauri::command]
-pub async fn export_to_goldocab(
-    content: String,
-    file_name: String,
-    dossier_id: Option<String>,
-    document_type: Option<String>,
-) -> Result<String, String> {
-    let handoff_dir = get_goldocab_handoff_dir()?;
-
-    let output_path = handoff_dir.join(&file_name);
-
-    fs::write(&output_path, &content)
-        .map_err(|e| format!("Failed to write document: {}", e))?;
-
-    let meta_path = handoff_dir.join(format!("{}.meta.json", file_name));
-    let metadata = serde_json::json!({
-        "file_name": file_name,
-        "file_path": output_path.to_string_lossy(),
-        "dossier_id": dossier_id,
-        "document_type": document_type.unwrap_or("document".to_string()),
-        "created_at": chrono_now(),
-        "source": "citadelle"
-    });
-
-    fs::write(&meta_path, serde_json::to_string_pretty(&metadata).unwrap())
-        .map_err(|e| format!("Failed to write metadata: {}", e))?;
-
-    let goldocab_url = format!(
-        "goldocab://document/new?path={}&source=citadelle{}",
-        urlencoding::encode(&output_path.to_string_lossy()),
-        dossier_id.map(|id| format!("&dossierID={}", id)).unwrap_or_default()
-    );
-
-    #[cfg(target_os = "macos")]
-    {
-        let _ = std::process::Command::new("open")
-            .arg(&goldocab_url)
-            .spawn();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn final_path_is_limited_to_the_session_files() {
+        let handoff = std::env::temp_dir().join(format!("citadelle-sessions-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&handoff).unwrap();
+        let working = handoff.join("s1.docx");
+        let other = handoff.join("autre.docx");
+        fs::write(&working, "copie").unwrap();
+        fs::write(&other, "autre").unwrap();
+
+        let session = GoldocabEditSession {
+            session_id: "s1".to_string(),
+            original_path: "/tmp/acte.docx".to_string(),
+            working_path: working.to_string_lossy().to_string(),
+            dossier_id: None,
+            dossier_name: None,
+            created_at: String::new(),
+            status: "active".to_string(),
+            original_sha256: None,
+            original_size: None,
+            original_mtime: None,
+            base_path: None,
+            last_heartbeat: None,
+        };
+        let merged = merge_result_path(&handoff, &session);
+        fs::write(&merged, "fusion").unwrap();
+
+        let check = |path: &Path| checked_final_path(&handoff, &session, Some(path.to_string_lossy().to_string()));
+        assert_eq!(checked_final_path(&handoff, &session, None).unwrap(), session.working_path);
+        assert!(check(&working).is_ok());
+        assert!(check(&merged).is_ok());
+        assert!(check(&other).is_err());
+        assert!(check(&handoff.join("../s1.docx")).is_err());
+
+        fs::remove_dir_all(&handoff).unwrap();
     }
-
-    Ok(output_path.to_string_lossy().to_string())
 }
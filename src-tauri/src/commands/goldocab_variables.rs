@@ -0,0 +1,322 @@
+use std::collections::BTreeMap;
+use serde::Serialize;
+use tauri::State;
+
+use super::goldocab_agenda::parse_goldocab_date;
+use super::goldocab_connection::GoldocabState;
+use super::goldocab_db::{read_dossier_detail, GoldocabClient, GoldocabContact};
+
+const MOIS: [&str; 12] = [
+    "janvier", "février", "mars", "avril", "mai", "juin",
+    "juillet", "août", "septembre", "octobre", "novembre", "décembre",
+];
+
+/// Valeurs des variables `{{cle}}` d'un dossier, cles alignees sur
+/// src/data/variables/defaults.ts
+#[derive(Serialize)]
+pub struct GoldocabVariableMap {
+    pub dossier_id: i64,
+    pub values: BTreeMap<String, String>,
+    /// Cles connues pour lesquelles GoldoCab n'a pas de valeur
+    pub missing: Vec<String>,
+}
+
+/// "2026-03-12" -> "12 mars 2026", "2026-03-01" -> "1er mars 2026"
+pub fn format_date_fr(day: &str) -> Option<String> {
+    let year = day.get(0..4)?;
+    let month: usize = day.get(5..7)?.parse().ok()?;
+    let d: u32 = day.get(8..10)?.parse().ok()?;
+    let mois = MOIS.get(month.checked_sub(1)?)?;
+
+    let jour = if d == 1 { "1er".to_string() } else { d.to_string() };
+    Some(format!("{} {} {}", jour, mois, year))
+}
+
+/// "09:30" -> "9 h 30", "14:00" -> "14 h"
+fn format_time_fr(time: &str) -> Option<String> {
+    let hours: u32 = time.get(0..2)?.parse().ok()?;
+    let minutes: u32 = time.get(3..5)?.parse().ok()?;
+
+    Some(if minutes == 0 {
+        format!("{} h", hours)
+    } else {
+        format!("{} h {:02}", hours, minutes)
+    })
+}
+
+/// Civilite en toutes lettres et abregee (valeurs du select de l'editeur) :
+/// "madame" -> ("Madame", "Mme")
+fn normalize_civilite(civilite: &str) -> Option<(&'static str, &'static str)> {
+    let key = civilite.trim().trim_end_matches('.').to_lowercase();
+    match key.as_str() {
+        "m" | "mr" | "monsieur" => Some(("Monsieur", "M.")),
+        "mme" | "madame" | "mlle" | "mademoiselle" => Some(("Madame", "Mme")),
+        "me" | "maître" | "maitre" => Some(("Maître", "Me")),
+        "dr" | "docteur" => Some(("Docteur", "Dr")),
+        _ => None,
+    }
+}
+
+/// "123456789" -> "123 456 789" (les SIRET sont regroupes 3 3 3 5)
+fn format_siren(siren: &str) -> String {
+    let digits: String = siren.chars().filter(|c| c.is_ascii_digit()).collect();
+    if digits.len() != 9 && digits.len() != 14 {
+        return siren.trim().to_string();
+    }
+
+    let mut groups = vec![&digits[0..3], &digits[3..6], &digits[6..9]];
+    if digits.len() == 14 {
+        groups.push(&digits[9..14]);
+    }
+    groups.join(" ")
+}
+
+/// "Tribunal judiciaire de Lyon" -> "Lyon", "Cour d'appel d'Aix-en-Provence" -> "Aix-en-Provence"
+fn juridiction_ville(juridiction: &str) -> Option<String> {
+    let cut = [" de ", " d'", " d’"]
+        .iter()
+        .filter_map(|sep| juridiction.rfind(sep).map(|i| i + sep.len()))
+        .max()?;
+
+    let ville = juridiction[cut..].trim();
+    if ville.is_empty() {
+        None
+    } else {
+        Some(ville.to_string())
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_string)
+}
+
+/// Designation formelle d'une personne : "Madame Hélène MARTIN"
+fn formal_person_line(civilite: &Option<String>, prenom: &Option<String>, nom: &Option<String>) -> Option<String> {
+    let civilite = non_empty(civilite).map(|c| {
+        normalize_civilite(&c).map(|(long, _)| long.to_string()).unwrap_or(c)
+    });
+    let parts: Vec<String> = [civilite, non_empty(prenom), non_empty(nom).map(|n| n.to_uppercase())]
+        .into_iter()
+        .flatten()
+        .collect();
+
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" "))
+    }
+}
+
+/// "Société Générale" -> "La Société Générale", "Dupont SARL" -> "La société Dupont SARL"
+fn company_line(denomination: &str) -> String {
+    let denomination = denomination.trim();
+    if denomination.to_lowercase().starts_with("société") {
+        format!("La {}", denomination)
+    } else {
+        format!("La société {}", denomination)
+    }
+}
+
+/// Remplit les variables `client.*` ; renvoie vrai pour une personne morale
+fn client_values(client: &GoldocabClient, values: &mut BTreeMap<String, String>) -> bool {
+    let is_company = client.type_client.as_deref() == Some("PersonneMorale")
+        || (non_empty(&client.nom).is_none() && non_empty(&client.denomination).is_some());
+
+    let mut set = |key: &str, value: Option<String>| {
+        if let Some(v) = value {
+            values.insert(format!("client.{}", key), v);
+        }
+    };
+
+    set("adresse", non_empty(&client.adresse));
+    set("code_postal", non_empty(&client.code_postal));
+    set("ville", non_empty(&client.ville));
+    set("email", non_empty(&client.email));
+    set("telephone", non_empty(&client.telephone));
+    set("denomination", non_empty(&client.denomination));
+    set("forme_juridique", non_empty(&client.forme_juridique));
+    set("siren", non_empty(&client.numero_siren).map(|s| format_siren(&s)));
+
+    if is_company {
+        // Les modeles utilisent {{client.nom}} pour designer la partie
+        set("nom", non_empty(&client.denomination));
+
+        set("civilite", Some("La société".to_string()));
+
+        let mut line = company_line(client.denomination.as_deref().unwrap_or(""));
+        if let Some(forme) = non_empty(&client.forme_juridique) {
+            line.push_str(&format!(", {}", forme));
+        }
+        if let Some(siren) = non_empty(&client.numero_siren) {
+            line.push_str(&format!(", immatriculée sous le numéro SIREN {}", format_siren(&siren)));
+        }
+        set("designation", Some(line));
+        set("formule_appel", Some("Madame, Monsieur,".to_string()));
+        return true;
+    }
+
+    set("nom", non_empty(&client.nom));
+    set("prenom", non_empty(&client.prenom));
+
+    let civilite = non_empty(&client.civilite);
+    let normalized = civilite.as_deref().and_then(normalize_civilite);
+    set(
+        "civilite",
+        normalized.map(|(_, short)| short.to_string()).or(civilite.clone()),
+    );
+    set("civilite_longue", normalized.map(|(long, _)| long.to_string()).or(civilite));
+    set("designation", formal_person_line(&client.civilite, &client.prenom, &client.nom));
+    set(
+        "formule_appel",
+        Some(match normalized {
+            Some((long, _)) => format!("{},", long),
+            None => "Madame, Monsieur,".to_string(),
+        }),
+    );
+    false
+}
+
+fn adverse_values(adverse: &[GoldocabContact], contacts: &[GoldocabContact], values: &mut BTreeMap<String, String>) {
+    if let Some(party) = adverse.first() {
+        let mut set = |key: &str, value: Option<String>| {
+            if let Some(v) = value {
+                values.insert(format!("adverse.{}", key), v);
+            }
+        };
+
+        if non_empty(&party.nom).is_some() {
+            let civilite = non_empty(&party.civilite);
+            set(
+                "civilite",
+                civilite
+                    .as_deref()
+                    .and_then(normalize_civilite)
+                    .map(|(_, short)| short.to_string())
+                    .or(civilite.clone()),
+            );
+            set("nom", non_empty(&party.nom));
+            set("prenom", non_empty(&party.prenom));
+            set("designation", formal_person_line(&party.civilite, &party.prenom, &party.nom));
+        } else {
+            set("civilite", Some("La société".to_string()));
+            set("nom", non_empty(&party.denomination));
+            set("designation", non_empty(&party.denomination).map(|d| company_line(&d)));
+        }
+
+        set("adresse", non_empty(&party.adresse));
+        set("code_postal", non_empty(&party.code_postal));
+        set("ville", non_empty(&party.ville));
+    }
+
+    // Le conseil adverse est un intervenant, pas une partie
+    let counsel = contacts.iter().find(|c| {
+        c.role
+            .as_deref()
+            .map(|r| {
+                let r = r.to_lowercase();
+                r.contains("advers") && (r.contains("avocat") || r.contains("conseil"))
+            })
+            .unwrap_or(false)
+    });
+
+    if let Some(counsel) = counsel {
+        let name = formal_person_line(&Some("Maître".to_string()), &counsel.prenom, &counsel.nom)
+            .filter(|_| non_empty(&counsel.nom).is_some())
+            .or_else(|| counsel.display_name.clone());
+        if let Some(name) = name {
+            values.insert("adverse.avocat".to_string(), name);
+        }
+    }
+}
+
+/// Resout les variables de modele d'un dossier GoldoCab, mises en forme a la
+/// francaise ("12 mars 2026", "Madame Hélène MARTIN")
+#[tauri::command]
+pub async fn resolve_goldocab_variables(
+    state: State<'_, GoldocabState>,
+    dossier_id: i64,
+) -> Result<GoldocabVariableMap, String> {
+    let detail = state
+        .query(move |conn, schema| read_dossier_detail(conn, schema, dossier_id))
+        .await?
+        .ok_or_else(|| format!("Dossier GoldoCab introuvable: {}", dossier_id))?;
+
+    let mut values = BTreeMap::new();
+
+    let is_company = match &detail.client {
+        Some(client) => client_values(client, &mut values),
+        None => false,
+    };
+    adverse_values(&detail.adverse_parties, &detail.contacts, &mut values);
+
+    let dossier = &detail.dossier;
+    let mut set = |key: &str, value: Option<String>| {
+        if let Some(v) = value {
+            values.insert(key.to_string(), v);
+        }
+    };
+
+    set("dossier.reference", Some(dossier.id.to_string()));
+    set("dossier.rg", non_empty(&dossier.numero_rg));
+    set("dossier.objet", non_empty(&dossier.nom));
+    set("dossier.nature", non_empty(&dossier.type_dossier));
+    set("juridiction.nom", non_empty(&dossier.juridiction));
+    set(
+        "juridiction.ville",
+        non_empty(&dossier.juridiction).and_then(|j| juridiction_ville(&j)),
+    );
+
+    if let Some((day, time)) = dossier.date_audience.as_deref().and_then(parse_goldocab_date) {
+        let date = format_date_fr(&day);
+        let heure = time.as_deref().and_then(format_time_fr);
+
+        set(
+            "date.audience_complete",
+            match (&date, &heure) {
+                (Some(d), Some(h)) => Some(format!("{} à {}", d, h)),
+                _ => date.clone(),
+            },
+        );
+        // Variable de type 'date' dans l'editeur (<input type="date">) : ISO
+        set("date.audience", Some(day));
+        set("date.audience_texte", date);
+        set("date.audience_heure", heure);
+    }
+
+    let missing = [
+        "client.civilite", "client.nom", "client.prenom", "client.adresse",
+        "client.code_postal", "client.ville", "adverse.nom", "dossier.rg",
+        "juridiction.nom", "date.audience",
+    ]
+    .iter()
+    // Une personne morale n'a pas de prenom
+    .filter(|k| !(is_company && **k == "client.prenom"))
+    .filter(|k| !values.contains_key(**k))
+    .map(|k| k.to_string())
+    .collect();
+
+    Ok(GoldocabVariableMap {
+        dossier_id,
+        values,
+        missing,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_date_fr_writes_french_dates() {
+        assert_eq!(format_date_fr("2026-03-12").as_deref(), Some("12 mars 2026"));
+        assert_eq!(format_date_fr("2026-08-01").as_deref(), Some("1er août 2026"));
+        assert_eq!(format_date_fr("2026-12-05").as_deref(), Some("5 décembre 2026"));
+    }
+
+    #[test]
+    fn format_date_fr_rejects_malformed_days() {
+        assert_eq!(format_date_fr("2026-13-01"), None);
+        assert_eq!(format_date_fr("2026-00-10"), None);
+        assert_eq!(format_date_fr("2026-03"), None);
+    }
+}
@@ -0,0 +1,283 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use tauri::State;
+use uuid::Uuid;
+
+use super::common::chrono_iso8601_now;
+use super::goldocab_agenda::parse_goldocab_date;
+use super::goldocab_connection::GoldocabState;
+use super::goldocab_sessions::get_goldocab_handoff_dir;
+
+/// Version du format des demandes d'ecriture lues par GoldoCab
+pub const WRITE_REQUEST_SCHEMA_VERSION: u32 = 1;
+
+const REQUEST_SUFFIX: &str = ".request.json";
+
+/// Duree maximale d'une saisie de temps, en minutes
+const MAX_TIME_ENTRY_MINUTES: u32 = 24 * 60;
+
+/// Ecriture demandee a GoldoCab, la base restant ouverte en lecture seule
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GoldocabWriteAction {
+    /// Nouvel item tache dans le dossier
+    Task {
+        titre: String,
+        #[serde(default)]
+        contexte: Option<String>,
+        /// AAAA-MM-JJ, avec heure optionnelle
+        #[serde(default)]
+        date_echeance: Option<String>,
+        #[serde(default)]
+        urgence: Option<i64>,
+        #[serde(default)]
+        gtd_phase: Option<String>,
+    },
+    /// Temps passe sur le dossier (redaction, relecture...)
+    TimeEntry {
+        minutes: u32,
+        /// Jour de la prestation, AAAA-MM-JJ
+        date: String,
+        description: String,
+        /// Document concerne, s'il y en a un
+        #[serde(default)]
+        document_path: Option<String>,
+    },
+}
+
+/// Contenu d'un fichier `<request_id>.request.json` du dossier 03_HANDOFF
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GoldocabWriteRequest {
+    pub schema_version: u32,
+    pub request_id: String,
+    pub dossier_id: i64,
+    pub created_at: String,
+    pub source: String,
+    #[serde(flatten)]
+    pub action: GoldocabWriteAction,
+}
+
+/// Accuse de reception ecrit par GoldoCab dans `<request_id>.done`
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GoldocabWriteAck {
+    pub request_id: String,
+    /// "applied" ou "rejected"
+    pub status: String,
+    /// Item cree par GoldoCab, le cas echeant
+    #[serde(default)]
+    pub item_id: Option<i64>,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub processed_at: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GoldocabWriteStatus {
+    pub request: GoldocabWriteRequest,
+    /// "pending", "applied" ou "rejected"
+    pub status: String,
+    pub ack: Option<GoldocabWriteAck>,
+}
+
+fn request_path(dir: &Path, request_id: &str) -> PathBuf {
+    dir.join(format!("{}{}", request_id, REQUEST_SUFFIX))
+}
+
+fn done_path(dir: &Path, request_id: &str) -> PathBuf {
+    dir.join(format!("{}.done", request_id))
+}
+
+/// Les identifiants sont des UUID : refuse tout ce qui pourrait sortir du dossier
+fn validate_request_id(request_id: &str) -> Result<(), String> {
+    Uuid::parse_str(request_id)
+        .map(|_| ())
+        .map_err(|_| format!("Identifiant de demande invalide: {}", request_id))
+}
+
+/// Ecrit puis renomme, pour que GoldoCab ne lise jamais un fichier a moitie ecrit
+fn write_atomic(path: &Path, content: &str) -> Result<(), String> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, content).map_err(|e| format!("Erreur ecriture demande GoldoCab: {}", e))?;
+    fs::rename(&tmp, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp);
+        format!("Erreur ecriture demande GoldoCab: {}", e)
+    })
+}
+
+fn validate_action(action: &GoldocabWriteAction) -> Result<GoldocabWriteAction, String> {
+    let trimmed = |value: &Option<String>| {
+        value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_string)
+    };
+
+    match action {
+        GoldocabWriteAction::Task { titre, contexte, date_echeance, urgence, gtd_phase } => {
+            if titre.trim().is_empty() {
+                return Err("Le titre de la tache est obligatoire".to_string());
+            }
+            let date_echeance = trimmed(date_echeance);
+            if let Some(date) = &date_echeance {
+                parse_goldocab_date(date)
+                    .ok_or_else(|| format!("Date d'echeance invalide (attendu AAAA-MM-JJ): {}", date))?;
+            }
+
+            Ok(GoldocabWriteAction::Task {
+                titre: titre.trim().to_string(),
+                contexte: trimmed(contexte),
+                date_echeance,
+                urgence: *urgence,
+                gtd_phase: trimmed(gtd_phase),
+            })
+        }
+        GoldocabWriteAction::TimeEntry { minutes, date, description, document_path } => {
+            if *minutes == 0 || *minutes > MAX_TIME_ENTRY_MINUTES {
+                return Err(format!(
+                    "Duree invalide: {} minutes (entre 1 et {})",
+                    minutes, MAX_TIME_ENTRY_MINUTES
+                ));
+            }
+            match parse_goldocab_date(date) {
+                Some((_, None)) if date.trim().len() == 10 => {}
+                _ => return Err(format!("Date invalide (attendu AAAA-MM-JJ): {}", date)),
+            }
+            if description.trim().is_empty() {
+                return Err("La description du temps passe est obligatoire".to_string());
+            }
+
+            Ok(GoldocabWriteAction::TimeEntry {
+                minutes: *minutes,
+                date: date.trim().to_string(),
+                description: description.trim().to_string(),
+                document_path: trimmed(document_path),
+            })
+        }
+    }
+}
+
+fn read_ack(dir: &Path, request_id: &str) -> Option<GoldocabWriteAck> {
+    let content = fs::read_to_string(done_path(dir, request_id)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn read_status(dir: &Path, request_id: &str) -> Result<Option<GoldocabWriteStatus>, String> {
+    let path = request_path(dir, request_id);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Erreur lecture demande GoldoCab: {}", e))?;
+    let request: GoldocabWriteRequest = serde_json::from_str(&content)
+        .map_err(|e| format!("Demande GoldoCab illisible: {}", e))?;
+
+    let ack = read_ack(dir, request_id);
+    let status = match &ack {
+        Some(ack) if ack.status == "applied" => "applied",
+        Some(_) => "rejected",
+        None => "pending",
+    };
+
+    Ok(Some(GoldocabWriteStatus {
+        request,
+        status: status.to_string(),
+        ack,
+    }))
+}
+
+/// Depose une demande d'ecriture (tache ou temps passe) dans 03_HANDOFF ;
+/// GoldoCab l'applique puis repond dans `<request_id>.done`
+#[tauri::command]
+pub async fn submit_goldocab_write_request(
+    state: State<'_, GoldocabState>,
+    dossier_id: i64,
+    action: GoldocabWriteAction,
+) -> Result<GoldocabWriteStatus, String> {
+    let action = validate_action(&action)?;
+
+    // Verification best-effort : une base absente n'empeche pas de mettre en file
+    let exists = state
+        .query(move |conn, schema| {
+            schema.require("dossiers")?;
+            conn.query_row("SELECT COUNT(*) FROM dossiers WHERE id = ?1", [dossier_id], |row| {
+                row.get::<_, i64>(0)
+            })
+            .map_err(|e| format!("Erreur lecture dossier: {}", e))
+        })
+        .await;
+    if let Ok(0) = exists {
+        return Err(format!("Dossier GoldoCab introuvable: {}", dossier_id));
+    }
+
+    let request = GoldocabWriteRequest {
+        schema_version: WRITE_REQUEST_SCHEMA_VERSION,
+        request_id: Uuid::new_v4().to_string(),
+        dossier_id,
+        created_at: chrono_iso8601_now(),
+        source: "citadelle".to_string(),
+        action,
+    };
+
+    let handoff_dir = get_goldocab_handoff_dir()?;
+    let json = serde_json::to_string_pretty(&request)
+        .map_err(|e| format!("Erreur serialisation demande GoldoCab: {}", e))?;
+    write_atomic(&request_path(&handoff_dir, &request.request_id), &json)?;
+
+    Ok(GoldocabWriteStatus {
+        request,
+        status: "pending".to_string(),
+        ack: None,
+    })
+}
+
+#[tauri::command]
+pub async fn get_goldocab_write_status(request_id: String) -> Result<Option<GoldocabWriteStatus>, String> {
+    validate_request_id(&request_id)?;
+    let handoff_dir = get_goldocab_handoff_dir()?;
+    read_status(&handoff_dir, &request_id)
+}
+
+/// Demandes d'ecriture en attente ou acquittees, plus recentes d'abord
+#[tauri::command]
+pub async fn list_goldocab_write_requests() -> Result<Vec<GoldocabWriteStatus>, String> {
+    let handoff_dir = get_goldocab_handoff_dir()?;
+    let mut requests = Vec::new();
+
+    if let Ok(entries) = fs::read_dir(&handoff_dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(request_id) = name.strip_suffix(REQUEST_SUFFIX) else {
+                continue;
+            };
+            if validate_request_id(request_id).is_err() {
+                continue;
+            }
+            if let Ok(Some(status)) = read_status(&handoff_dir, request_id) {
+                requests.push(status);
+            }
+        }
+    }
+
+    requests.sort_by(|a, b| b.request.created_at.cmp(&a.request.created_at));
+    Ok(requests)
+}
+
+/// Supprime une demande et son accuse ; refuse tant que GoldoCab ne l'a pas traitee
+#[tauri::command]
+pub async fn clear_goldocab_write_request(request_id: String) -> Result<(), String> {
+    validate_request_id(&request_id)?;
+    let handoff_dir = get_goldocab_handoff_dir()?;
+
+    if read_ack(&handoff_dir, &request_id).is_none() && request_path(&handoff_dir, &request_id).exists() {
+        return Err(format!("Demande GoldoCab encore en attente: {}", request_id));
+    }
+
+    for path in [request_path(&handoff_dir, &request_id), done_path(&handoff_dir, &request_id)] {
+        if path.exists() {
+            fs::remove_file(&path)
+                .map_err(|e| format!("Erreur suppression demande GoldoCab: {}", e))?;
+        }
+    }
+
+    Ok(())
+}
@@ -2,23 +2,61 @@ pub mod common;
 pub mod filesystem;
 pub mod search;
 pub mod exhibits;
+pub mod exhibit_duplicates;
+pub mod exhibit_email;
+pub mod exhibit_thumbnails;
+pub mod exhibit_ledger;
 pub mod user_data;
 pub mod templates;
 pub mod styles;
 pub mod themes;
 pub mod export_templates;
+pub mod deep_link;
 pub mod goldocab_sessions;
+pub mod goldocab_merge;
+pub mod goldocab_session_manager;
+pub mod goldocab_locks;
+pub mod goldocab_config;
+pub mod goldocab_connection;
 pub mod goldocab_db;
+pub mod goldocab_schema;
+pub mod goldocab_search;
+pub mod goldocab_agenda;
+pub mod goldocab_variables;
+pub mod goldocab_monitor;
+pub mod goldocab_writeback;
+pub mod goldocab_notifier;
+pub mod goldocab_export;
+pub mod goldocab_audit;
+pub mod goldocab_inventory;
+pub mod goldocab_inbox;
 pub mod goldocab_notes;
 
 pub use filesystem::*;
 pub use search::*;
 pub use exhibits::*;
+pub use exhibit_duplicates::*;
+pub use exhibit_email::*;
+pub use exhibit_thumbnails::*;
+pub use exhibit_ledger::*;
 pub use user_data::*;
 pub use templates::*;
 pub use styles::*;
 pub use themes::*;
 pub use export_templates::*;
+pub use deep_link::*;
 pub use goldocab_sessions::*;
+pub use goldocab_session_manager::*;
+pub use goldocab_locks::*;
+pub use goldocab_config::*;
+pub use goldocab_connection::GoldocabState;
 pub use goldocab_db::*;
+pub use goldocab_agenda::*;
+pub use goldocab_variables::*;
+pub use goldocab_monitor::*;
+pub use goldocab_writeback::*;
+pub use goldocab_export::*;
+pub use goldocab_audit::*;
+pub use goldocab_inventory::*;
+pub use goldocab_inbox::*;
 pub use goldocab_notes::*;
@@ -7,10 +7,21 @@ mod commands;
 use tauri::Manager;
 
 fn main() {
+    // Une instance tourne deja : elle recoit le lien et celle-ci s'arrete
+    let startup_link = commands::deep_link_from_args(&std::env::args().collect::<Vec<_>>());
+    if let Some(url) = &startup_link {
+        if commands::forward_deep_link(url) {
+            return;
+        }
+    }
+
     let menu = menu::create_app_menu();
 
     tauri::Builder::default()
         .menu(menu)
+        .manage(commands::GoldocabState::new())
+        .manage(commands::DeepLinkState::new(startup_link))
+        .manage(commands::GoldocabInboxState::new())
         .on_menu_event(|event| {
             let window = event.window();
             let menu_id = event.menu_item_id();
@@ -22,17 +33,23 @@ fn main() {
                 window.open_devtools();
             }
 
-            // Handle deep links (citadelle:// URLs)
-            let args: Vec<String> = std::env::args().collect();
-            if args.len() > 1 {
-                let url = &args[1];
-                if url.starts_with("citadelle://") {
-                    if let Some(window) = app.get_window("main") {
-                        let url_clone = url.clone();
-                        let _ = window.emit("goldocab-open", &url_clone);
-                    }
-                }
+            // Liens citadelle:// des instances lancees ensuite ; celui du
+            // lancement attend flush_pending_deep_links. Sans serveur, une
+            // instance lancee ensuite ne peut transmettre son lien et le
+            // traite elle-meme : l'erreur est signalee au frontend
+            if let Err(error) = commands::start_deep_link_server(app.handle()) {
+                app.state::<commands::DeepLinkState>().report_server_error(error);
             }
+
+            // Rafraichit les pickers quand GoldoCab modifie sa base
+            commands::start_goldocab_monitor(app.handle());
+
+            // Sessions abandonnees et archivage du dossier d'echange
+            commands::start_goldocab_session_manager(app.handle());
+
+            // Demandes deposees par GoldoCab dans 03_HANDOFF/inbox ; celles
+            // acceptees avant l'ecoute du frontend attendent flush_pending_goldocab_requests
+            commands::start_goldocab_handoff_watcher(app.handle());
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -52,6 +69,16 @@ fn main() {
             commands::search_in_project,
             // Exhibit files (Pieces jointes)
             commands::list_exhibit_files,
+            commands::find_duplicate_exhibits,
+            commands::parse_email_exhibit,
+            commands::extract_email_attachments,
+            commands::render_email_exhibit_pdf,
+            commands::get_exhibit_thumbnail,
+            commands::prune_thumbnail_cache,
+            commands::record_exhibit_ledger,
+            commands::record_exhibit_rename,
+            commands::record_exhibit_stamp,
+            commands::verify_exhibit_ledger,
             // User data
             commands::get_user_data_path,
             commands::init_user_data_dir,
@@ -73,19 +100,47 @@ fn main() {
             commands::read_export_template,
             commands::save_export_template,
             commands::delete_export_template,
+            // Deep links
+            commands::flush_pending_deep_links,
             // GoldoCab integration
             commands::start_goldocab_edit_session,
             commands::complete_goldocab_edit_session,
             commands::cancel_goldocab_edit_session,
+            commands::merge_goldocab_edit_session,
+            commands::save_goldocab_session_as,
             commands::get_goldocab_edit_session,
             commands::list_goldocab_sessions,
+            commands::heartbeat_goldocab_edit_session,
+            commands::run_goldocab_session_maintenance,
+            commands::list_recoverable_goldocab_sessions,
+            commands::recover_goldocab_edit_session,
+            commands::get_goldocab_document_lock,
+            commands::flush_pending_goldocab_requests,
             commands::export_to_goldocab,
+            commands::submit_goldocab_write_request,
+            commands::get_goldocab_write_status,
+            commands::list_goldocab_write_requests,
+            commands::clear_goldocab_write_request,
+            commands::query_goldocab_audit_log,
+            commands::export_goldocab_audit_csv,
+            commands::get_goldocab_dossier_inventory,
             // GoldoCab database (read-only)
             commands::check_goldocab_status,
+            commands::test_goldocab_db_path,
+            commands::set_goldocab_db_path,
+            commands::clear_goldocab_db_path,
             commands::search_goldocab_clients,
             commands::search_goldocab_dossiers,
+            commands::search_goldocab_clients_page,
+            commands::search_goldocab_dossiers_page,
             commands::get_goldocab_client,
             commands::get_goldocab_dossier_items,
+            commands::get_goldocab_dossier,
+            commands::list_goldocab_client_dossiers,
+            commands::get_goldocab_dossier_contacts,
+            commands::get_goldocab_agenda,
+            commands::export_goldocab_agenda_ics,
+            commands::resolve_goldocab_variables,
             // GoldoCab notes (shared .md files)
             commands::list_goldocab_notes,
             commands::read_goldocab_note,
@@ -13,6 +13,14 @@ export const DEFAULT_VARIABLES: VariableDefinition[] = [
     defaultValue: 'M.',
     isBuiltin: true,
   },
+  {
+    key: 'client.civilite_longue',
+    label: 'Civilité du client (en toutes lettres)',
+    category: 'client',
+    type: 'text',
+    placeholder: 'Madame',
+    isBuiltin: true,
+  },
   {
     key: 'client.nom',
     label: 'Nom du client',
@@ -69,6 +77,46 @@ export const DEFAULT_VARIABLES: VariableDefinition[] = [
     placeholder: '01 23 45 67 89',
     isBuiltin: true,
   },
+  {
+    key: 'client.denomination',
+    label: 'Dénomination sociale',
+    category: 'client',
+    type: 'text',
+    placeholder: 'Dupont SARL',
+    isBuiltin: true,
+  },
+  {
+    key: 'client.forme_juridique',
+    label: 'Forme juridique',
+    category: 'client',
+    type: 'text',
+    placeholder: 'SARL',
+    isBuiltin: true,
+  },
+  {
+    key: 'client.siren',
+    label: 'SIREN du client',
+    category: 'client',
+    type: 'text',
+    placeholder: '123 456 789',
+    isBuiltin: true,
+  },
+  {
+    key: 'client.designation',
+    label: 'Désignation du client',
+    category: 'client',
+    type: 'text',
+    placeholder: 'Madame Jeanne DUPONT',
+    isBuiltin: true,
+  },
+  {
+    key: 'client.formule_appel',
+    label: 'Formule d\'appel',
+    category: 'client',
+    type: 'text',
+    placeholder: 'Madame,',
+    isBuiltin: true,
+  },
 
   // === PARTIE ADVERSE ===
   {
@@ -104,6 +152,30 @@ export const DEFAULT_VARIABLES: VariableDefinition[] = [
     placeholder: '2 avenue des Champs-Élysées',
     isBuiltin: true,
   },
+  {
+    key: 'adverse.code_postal',
+    label: 'Code postal partie adverse',
+    category: 'adverse',
+    type: 'text',
+    placeholder: '75008',
+    isBuiltin: true,
+  },
+  {
+    key: 'adverse.ville',
+    label: 'Ville partie adverse',
+    category: 'adverse',
+    type: 'text',
+    placeholder: 'Paris',
+    isBuiltin: true,
+  },
+  {
+    key: 'adverse.designation',
+    label: 'Désignation partie adverse',
+    category: 'adverse',
+    type: 'text',
+    placeholder: 'Monsieur Pierre MARTIN',
+    isBuiltin: true,
+  },
   {
     key: 'adverse.avocat',
     label: 'Avocat adverse',
@@ -306,6 +378,30 @@ export const DEFAULT_VARIABLES: VariableDefinition[] = [
     type: 'date',
     isBuiltin: true,
   },
+  {
+    key: 'date.audience_texte',
+    label: 'Date d\'audience (en toutes lettres)',
+    category: 'date',
+    type: 'text',
+    placeholder: '12 mars 2026',
+    isBuiltin: true,
+  },
+  {
+    key: 'date.audience_heure',
+    label: 'Heure d\'audience',
+    category: 'date',
+    type: 'text',
+    placeholder: '9 h 30',
+    isBuiltin: true,
+  },
+  {
+    key: 'date.audience_complete',
+    label: 'Date et heure d\'audience',
+    category: 'date',
+    type: 'text',
+    placeholder: '12 mars 2026 à 9 h 30',
+    isBuiltin: true,
+  },
   {
     key: 'date.echeance',
     label: 'Date d\'échéance',
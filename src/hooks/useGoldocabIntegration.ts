@@ -5,13 +5,27 @@
  * Gère les sessions d'édition, l'export vers GoldoCab, et les deep links.
  */
 
-import { useState, useEffect, useCallback } from 'react';
+import { useState, useEffect, useCallback, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/tauri';
 import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import { open } from '@tauri-apps/api/shell';
 import { useDocumentStore } from '../store/useDocumentStore';
+import { useGoldocabDataStore } from '../store/useGoldocabDataStore';
+import { useGoldocabNotesFilesStore } from '../store/useGoldocabNotesFilesStore';
 import { useFileOperations } from './useFileOperations';
+import type { NoteFileEntry } from '../store/useGoldocabNotesFilesStore';
 import { handleError } from '../lib/errorHandler';
+import type {
+  DeepLinkRejection,
+  DeepLinkRoute,
+  GoldocabAuditEntry,
+  GoldocabAuditExport,
+  GoldocabAuditQuery,
+  GoldocabChangeEvent,
+  GoldocabInboundRequest,
+  GoldocabWriteAction,
+  GoldocabWriteStatus,
+} from '../types/goldocab';
 
 // ============================================================================
 // Types
@@ -24,19 +38,117 @@ export interface GoldocabEditSession {
   dossier_id: string | null;
   dossier_name: string | null;
   created_at: string;
-  status: 'active' | 'completed' | 'cancelled';
+  status: 'active' | 'completed' | 'cancelled' | 'abandoned';
+  original_sha256: string | null;
+  original_size: number | null;
+  original_mtime: number | null; // ms since epoch
+  base_path: string | null;
+  last_heartbeat: string | null; // seconds since epoch
+}
+
+// Payload of the "goldocab-session-maintenance" event emitted at startup
+export interface GoldocabSessionMaintenanceReport {
+  abandoned: string[];
+  recoverable: GoldocabEditSession[];
+  archived: number;
+  archive_dir: string | null;
+  errors: string[]; // sessions left as they were
+}
+
+export interface GoldocabChangedRange {
+  offset: number;
+  bytes_removed: number;
+  bytes_added: number;
+}
+
+export interface GoldocabChangeSummary {
+  original_sha256: string;
+  final_sha256: string;
+  original_size: number;
+  final_size: number;
+  mtime_changed: boolean;
+  changed_range: GoldocabChangedRange | null;
 }
 
 export interface GoldocabSessionResult {
   session_id: string;
   final_path: string;
   was_modified: boolean;
+  change_summary: GoldocabChangeSummary | null;
+}
+
+export interface GoldocabMergeResult {
+  session_id: string;
+  merged_path: string; // working copy if applied, otherwise a file with conflict markers
+  conflicts: number;
+  applied: boolean;
+}
+
+export interface GoldocabNotification {
+  url: string;
+  method: 'url_handler' | 'notification_file';
+  notification_file: string | null; // written to 03_HANDOFF/notifications when no URL handler answered
+  error: string | null;
+}
+
+export interface GoldocabExportMetadata {
+  schema_version: number;
+  file_name: string; // after sanitising and de-duplication, e.g. "name (2).docx"
+  file_path: string;
+  requested_file_name: string | null;
+  dossier_id: string | null;
+  document_type: string;
+  content_kind: 'text' | 'binary';
+  mime_type: string | null;
+  size: number;
+  sha256: string;
+  created_at: string;
+  source: string;
+}
+
+export interface GoldocabExportResult {
+  path: string;
+  metadata_path: string;
+  metadata: GoldocabExportMetadata;
+  notification: GoldocabNotification | null;
+  notification_error: string | null;
+}
+
+export interface GoldocabDocumentLock {
+  version: number;
+  original_path: string;
+  owner: 'citadelle' | 'goldocab';
+  session_id: string;
+  pid: number | null;
+  acquired_at: number; // epoch seconds
+  heartbeat: number;
+}
+
+export interface GoldocabDossierProjectFile {
+  path: string;
+  name: string;
+  source: 'frontmatter' | 'metadata';
+  metadata_path: string | null;
 }
 
+export interface GoldocabDossierInventory {
+  dossier_id: string;
+  notes: NoteFileEntry[];
+  exports: GoldocabExportMetadata[];
+  sessions: GoldocabEditSession[];
+  project_files: GoldocabDossierProjectFile[];
+  warnings: string[];
+}
+
+// Keeps the session from being marked abandoned by the startup session manager
+const SESSION_HEARTBEAT_INTERVAL = 5 * 60_000;
+
 export interface GoldocabExportOptions {
   fileName: string;
   dossierId?: string;
   documentType?: string;
+  // DOCX/PDF bytes; exported instead of the document's content when set
+  binaryContent?: Uint8Array;
 }
 
 // ============================================================================
@@ -47,13 +159,20 @@ export function useGoldocabIntegration() {
   // State
   const [currentSession, setCurrentSession] = useState<GoldocabEditSession | null>(null);
   const [activeSessions, setActiveSessions] = useState<GoldocabEditSession[]>([]);
+  const [recoverableSessions, setRecoverableSessions] = useState<GoldocabEditSession[]>([]);
   const [isExporting, setIsExporting] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  // The original changed in GoldoCab during the session: merge or save as
+  const [hasConflict, setHasConflict] = useState(false);
 
   // Document store
   const getActiveDocument = useDocumentStore((state) => state.getActiveDocument);
   const getDocument = useDocumentStore((state) => state.getDocument);
   const activeDocument = getActiveDocument();
+  // Not memoized by useFileOperations: read through a ref so listeners stay registered
+  const { openFileFromPath } = useFileOperations();
+  const openFileRef = useRef(openFileFromPath);
+  openFileRef.current = openFileFromPath;
 
   // ============================================================================
   // Session Management
@@ -102,6 +221,60 @@ export function useGoldocabIntegration() {
         finalPath: finalPath || null,
       });
       setCurrentSession(null);
+      setHasConflict(false);
+      return result;
+    } catch (err) {
+      const errorMsg = err instanceof Error ? err.message : String(err);
+      setError(errorMsg);
+      setHasConflict(errorMsg.includes('GOLDOCAB_CONFLICT'));
+      handleError(err, 'GoldoCab', { silent: true });
+      return null;
+    }
+  }, [currentSession]);
+
+  /**
+   * Three-way merge of the working copy with the original changed by GoldoCab
+   */
+  const mergeEditSession = useCallback(async (): Promise<GoldocabMergeResult | null> => {
+    if (!currentSession) {
+      return null;
+    }
+
+    try {
+      setError(null);
+      const result = await invoke<GoldocabMergeResult>('merge_goldocab_edit_session', {
+        sessionId: currentSession.session_id,
+      });
+      if (result.applied) {
+        setHasConflict(false);
+      }
+      return result;
+    } catch (err) {
+      const errorMsg = err instanceof Error ? err.message : String(err);
+      setError(errorMsg);
+      handleError(err, 'GoldoCab', { silent: true });
+      return null;
+    }
+  }, [currentSession]);
+
+  /**
+   * Save the working copy to another file, leaving the original untouched
+   */
+  const saveSessionAs = useCallback(async (
+    targetPath: string
+  ): Promise<GoldocabSessionResult | null> => {
+    if (!currentSession) {
+      return null;
+    }
+
+    try {
+      setError(null);
+      const result = await invoke<GoldocabSessionResult>('save_goldocab_session_as', {
+        sessionId: currentSession.session_id,
+        targetPath,
+      });
+      setCurrentSession(null);
+      setHasConflict(false);
       return result;
     } catch (err) {
       const errorMsg = err instanceof Error ? err.message : String(err);
@@ -146,6 +319,66 @@ export function useGoldocabIntegration() {
     }
   }, []);
 
+  /**
+   * Sessions abandoned after a crash whose working copy can be reopened
+   */
+  const refreshRecoverableSessions = useCallback(async (): Promise<void> => {
+    try {
+      const sessions = await invoke<GoldocabEditSession[]>('list_recoverable_goldocab_sessions');
+      setRecoverableSessions(sessions);
+    } catch (err) {
+      handleError(err, 'GoldoCab', { silent: true });
+    }
+  }, []);
+
+  /**
+   * Resume an abandoned session on its working copy
+   */
+  const recoverSession = useCallback(async (
+    sessionId: string
+  ): Promise<GoldocabEditSession | null> => {
+    try {
+      setError(null);
+      const session = await invoke<GoldocabEditSession>('recover_goldocab_edit_session', {
+        sessionId,
+      });
+      setCurrentSession(session);
+      setRecoverableSessions((sessions) => sessions.filter((s) => s.session_id !== sessionId));
+      return session;
+    } catch (err) {
+      const errorMsg = err instanceof Error ? err.message : String(err);
+      setError(errorMsg);
+      handleError(err, 'GoldoCab', { silent: true });
+      return null;
+    }
+  }, []);
+
+  /**
+   * Discard an abandoned session and its working copy
+   */
+  const discardSession = useCallback(async (sessionId: string): Promise<void> => {
+    try {
+      await invoke('cancel_goldocab_edit_session', { sessionId });
+      setRecoverableSessions((sessions) => sessions.filter((s) => s.session_id !== sessionId));
+    } catch (err) {
+      handleError(err, 'GoldoCab', { silent: true });
+    }
+  }, []);
+
+  // Heartbeat while a session is open
+  useEffect(() => {
+    if (!currentSession) return;
+
+    const sessionId = currentSession.session_id;
+    const timer = setInterval(() => {
+      invoke('heartbeat_goldocab_edit_session', { sessionId }).catch((err) =>
+        handleError(err, 'GoldoCab', { silent: true })
+      );
+    }, SESSION_HEARTBEAT_INTERVAL);
+
+    return () => clearInterval(timer);
+  }, [currentSession]);
+
   // ============================================================================
   // Export to GoldoCab
   // ============================================================================
@@ -169,18 +402,24 @@ export function useGoldocabIntegration() {
       const doc = getDocument(activeDocument.id);
       const content = doc?.content;
 
-      if (!content) {
+      if (!content && !options.binaryContent) {
         throw new Error('Could not get document content');
       }
 
-      const exportPath = await invoke<string>('export_to_goldocab', {
-        content: JSON.stringify(content),
+      const result = await invoke<GoldocabExportResult>('export_to_goldocab', {
+        content: options.binaryContent ? null : JSON.stringify(content),
+        binaryContent: options.binaryContent ? Array.from(options.binaryContent) : null,
         fileName: options.fileName,
         dossierId: options.dossierId || null,
         documentType: options.documentType || null,
       });
 
-      return exportPath;
+      // The document is exported either way; GoldoCab just may not know yet
+      if (result.notification_error) {
+        handleError(result.notification_error, 'GoldoCab', { toastType: 'warning' });
+      }
+
+      return result.path;
     } catch (err) {
       const errorMsg = err instanceof Error ? err.message : String(err);
       setError(errorMsg);
@@ -223,56 +462,184 @@ export function useGoldocabIntegration() {
   // Deep Link Handling
   // ============================================================================
 
+  useEffect(() => {
+    const unlisteners: UnlistenFn[] = [];
+
+    // Routes are parsed and validated by the Rust deep-link router
+    const handleRoute = async (route: DeepLinkRoute) => {
+      try {
+        if (route.route === 'open') {
+          if (route.mode === 'note') {
+            // Mode note: ouvrir directement le fichier .md dans l'editeur
+            await openFileRef.current(route.path);
+          } else {
+            // Mode document: session d'edition classique
+            const session = await startEditSession(
+              route.path,
+              route.dossier_id || undefined,
+              route.dossier_name || undefined
+            );
+            // Edits go to the session's working copy, not GoldoCab's file
+            if (session) await openFileRef.current(session.working_path);
+          }
+        } else if (route.route === 'session') {
+          const session = await invoke<GoldocabEditSession | null>('get_goldocab_edit_session', {
+            sessionId: route.session_id,
+          });
+          if (!session) return;
+          const resumed =
+            session.status === 'abandoned' ? await recoverSession(session.session_id) : session;
+          if (resumed?.status === 'active') {
+            setCurrentSession(resumed);
+            await openFileRef.current(resumed.working_path);
+          }
+        } else if (route.route === 'note') {
+          const notesStore = useGoldocabNotesFilesStore.getState();
+          await notesStore.loadNotes();
+          const note = useGoldocabNotesFilesStore
+            .getState()
+            .notes.find((n) => n.id === route.note_id);
+          if (!note) {
+            handleError(`Note introuvable: ${route.note_id}`, 'GoldoCab');
+            return;
+          }
+          await openFileRef.current(note.path);
+        }
+      } catch (err) {
+        handleError(err, 'GoldoCab');
+      }
+    };
+
+    const setupDeepLinkListeners = async () => {
+      for (const name of ['deep-link-open', 'deep-link-session', 'deep-link-note']) {
+        unlisteners.push(await listen<DeepLinkRoute>(name, (event) => handleRoute(event.payload)));
+      }
+      unlisteners.push(
+        await listen<DeepLinkRejection>('deep-link-rejected', (event) => {
+          handleError(`Lien invalide (${event.payload.error})`, 'GoldoCab');
+        })
+      );
+      // Without the local server, links clicked later open in a second window
+      unlisteners.push(
+        await listen<string>('deep-link-unavailable', (event) => {
+          handleError(`Liens citadelle:// indisponibles (${event.payload})`, 'GoldoCab', {
+            toastType: 'warning',
+          });
+        })
+      );
+      // Links received before the listeners were ready, including the launch link
+      await invoke<number>('flush_pending_deep_links');
+    };
+
+    setupDeepLinkListeners();
+
+    return () => {
+      unlisteners.forEach((unlisten) => unlisten());
+    };
+  }, [startEditSession, recoverSession]);
+
+  // ============================================================================
+  // Database Change Monitoring
+  // ============================================================================
+
   useEffect(() => {
     let unlisten: UnlistenFn | null = null;
 
-    const setupDeepLinkListener = async () => {
-      // Listen for deep link events from Tauri
-      unlisten = await listen<string>('goldocab-open', async (event) => {
-        console.log('[GoldoCab] Deep link received:', event.payload);
+    const setupChangeListener = async () => {
+      // Emis par le moniteur Rust quand GoldoCab modifie sa base
+      unlisten = await listen<GoldocabChangeEvent>('goldocab-changed', (event) => {
+        useGoldocabDataStore.getState().handleGoldocabChange(event.payload);
+      });
+    };
+
+    setupChangeListener();
+
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
+
+  // ============================================================================
+  // GoldoCab Requests (03_HANDOFF/inbox)
+  // ============================================================================
 
+  useEffect(() => {
+    let unlisten: UnlistenFn | null = null;
+
+    const setupRequestListener = async () => {
+      // Already validated and acknowledged by the Rust handoff watcher
+      unlisten = await listen<GoldocabInboundRequest>('goldocab-request', async (event) => {
+        const request = event.payload;
         try {
-          // Parse the deep link URL
-          const url = new URL(event.payload);
-          const params = new URLSearchParams(url.search);
-
-          const filePath = params.get('path');
-          const dossierId = params.get('dossierId');
-          const dossierName = params.get('dossierName');
-          const mode = params.get('mode');
-
-          if (filePath) {
-            if (mode === 'note') {
-              // Mode note: ouvrir directement le fichier .md dans l'editeur
-              console.log('[GoldoCab] Opening note file:', filePath);
-              const { openFileFromPath } = useFileOperations();
-              await openFileFromPath(filePath);
-            } else {
-              // Mode document: session d'edition classique
-              await startEditSession(filePath, dossierId || undefined, dossierName || undefined);
-              const content = await invoke<string>('read_file', { path: filePath });
-              console.log('[GoldoCab] File loaded, content length:', content.length);
-            }
+          if (request.kind === 'open_file') {
+            const session = await startEditSession(
+              request.path,
+              request.dossier_id || undefined,
+              request.dossier_name || undefined
+            );
+            // Same as a citadelle://open link: edit the working copy
+            if (session) await openFileRef.current(session.working_path);
+          } else if (request.kind === 'cancel_session') {
+            // The backend has already cancelled it; only drop it from the UI
+            setCurrentSession((session) =>
+              session?.session_id === request.session_id ? null : session
+            );
+            refreshSessions();
           }
         } catch (err) {
           handleError(err, 'GoldoCab');
         }
       });
+      // Requests accepted before the listener was ready, e.g. already in the inbox at launch
+      await invoke<number>('flush_pending_goldocab_requests');
     };
 
-    setupDeepLinkListener();
+    setupRequestListener();
 
     return () => {
       if (unlisten) {
         unlisten();
       }
     };
-  }, [startEditSession]);
+  }, [startEditSession, refreshSessions]);
+
+  // ============================================================================
+  // Startup Session Maintenance
+  // ============================================================================
+
+  useEffect(() => {
+    let unlisten: UnlistenFn | null = null;
+
+    const setupMaintenanceListener = async () => {
+      // The report may arrive before this listener; the mount refresh below covers that case
+      unlisten = await listen<GoldocabSessionMaintenanceReport>(
+        'goldocab-session-maintenance',
+        (event) => {
+          const report = event.payload;
+          setRecoverableSessions(report.recoverable);
+          if (report.errors.length > 0) {
+            handleError(report.errors.join('\n'), 'GoldoCab', { toastType: 'warning' });
+          }
+        }
+      );
+    };
+
+    setupMaintenanceListener();
+
+    return () => {
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, []);
 
   // Refresh sessions on mount
   useEffect(() => {
     refreshSessions();
-  }, [refreshSessions]);
+    refreshRecoverableSessions();
+  }, [refreshSessions, refreshRecoverableSessions]);
 
   // ============================================================================
   // Return
@@ -282,16 +649,23 @@ export function useGoldocabIntegration() {
     // State
     currentSession,
     activeSessions,
+    recoverableSessions,
     isExporting,
     error,
+    hasConflict,
     hasActiveSession: currentSession !== null,
     isGoldocabSession: currentSession !== null,
 
     // Session management
     startEditSession,
     completeEditSession,
+    mergeEditSession,
+    saveSessionAs,
     cancelEditSession,
     refreshSessions,
+    refreshRecoverableSessions,
+    recoverSession,
+    discardSession,
 
     // Export
     exportToGoldocab,
@@ -356,4 +730,59 @@ export async function notifyGoldocabNoteModified(filePath: string, noteId: strin
   }
 }
 
+/**
+ * Ask GoldoCab to create a task or log time on a dossier (applied asynchronously)
+ */
+export async function submitGoldocabWriteRequest(
+  dossierId: number,
+  action: GoldocabWriteAction
+): Promise<GoldocabWriteStatus> {
+  return invoke<GoldocabWriteStatus>('submit_goldocab_write_request', { dossierId, action });
+}
+
+/**
+ * Read back GoldoCab's acknowledgement for a write request
+ */
+export async function getGoldocabWriteStatus(requestId: string): Promise<GoldocabWriteStatus | null> {
+  return invoke<GoldocabWriteStatus | null>('get_goldocab_write_status', { requestId });
+}
+
+/**
+ * Who is currently editing a document, if anyone (Citadelle or GoldoCab)
+ */
+export async function getGoldocabDocumentLock(path: string): Promise<GoldocabDocumentLock | null> {
+  return invoke<GoldocabDocumentLock | null>('get_goldocab_document_lock', { path });
+}
+
+/**
+ * Everything Citadelle knows about a dossier: notes, handoff exports, active
+ * sessions and, when a project is open, the project files that reference it
+ */
+export async function getGoldocabDossierInventory(
+  dossierId: string,
+  projectRoot?: string
+): Promise<GoldocabDossierInventory> {
+  return invoke<GoldocabDossierInventory>('get_goldocab_dossier_inventory', {
+    dossierId,
+    projectRoot: projectRoot ?? null,
+  });
+}
+
+/**
+ * Audit trail of handoff activity, newest first
+ */
+export async function queryGoldocabAuditLog(query?: GoldocabAuditQuery): Promise<GoldocabAuditEntry[]> {
+  return invoke<GoldocabAuditEntry[]>('query_goldocab_audit_log', { query: query ?? null });
+}
+
+/**
+ * Export the (filtered) audit trail as CSV, in chronological order
+ */
+export async function exportGoldocabAuditCsv(
+  outputPath: string,
+  query?: GoldocabAuditQuery
+): Promise<GoldocabAuditExport> {
+  return invoke<GoldocabAuditExport>('export_goldocab_audit_csv', { query: query ?? null, outputPath });
+}
+
 export default useGoldocabIntegration;
@@ -3,9 +3,12 @@ import { create } from 'zustand'
 import { persist } from 'zustand/middleware'
 import { invoke } from '@tauri-apps/api/tauri'
 import type {
+  GoldocabChangeEvent,
   GoldocabClient,
   GoldocabDossier,
   GoldocabItem,
+  GoldocabListOptions,
+  GoldocabPage,
   GoldocabStatus,
   LinkedDossier,
 } from '../types/goldocab'
@@ -28,6 +31,24 @@ function setCache(key: string, data: unknown) {
   cache.set(key, { data, timestamp: Date.now() })
 }
 
+// Prefixes de cache dependant de chaque table GoldoCab
+// (les dossiers affichent le nom du client, d'ou "dossiers:" pour clients)
+const CACHE_PREFIXES_BY_TABLE: Record<string, string[]> = {
+  clients: ['clients:', 'client:', 'dossiers:'],
+  dossiers: ['dossiers:'],
+  items: ['items:'],
+  contacts: [],
+}
+
+function invalidateCacheForTables(tables: string[]) {
+  const prefixes = tables.flatMap((t) => CACHE_PREFIXES_BY_TABLE[t] ?? [])
+  for (const key of [...cache.keys()]) {
+    if (prefixes.some((p) => key.startsWith(p))) {
+      cache.delete(key)
+    }
+  }
+}
+
 interface GoldocabDataStore {
   // Statut
   isAvailable: boolean
@@ -38,6 +59,8 @@ interface GoldocabDataStore {
   dossierResults: GoldocabDossier[]
   isSearching: boolean
   searchError: string | null
+  lastClientQuery: string
+  lastDossierQuery: string
 
   // Dossier lie par document (persiste)
   linkedDossiers: Record<string, LinkedDossier>
@@ -49,6 +72,8 @@ interface GoldocabDataStore {
   checkStatus: () => Promise<boolean>
   searchClients: (query: string) => Promise<GoldocabClient[]>
   searchDossiers: (query: string) => Promise<GoldocabDossier[]>
+  fetchClientPage: (query: string, options?: GoldocabListOptions) => Promise<GoldocabPage<GoldocabClient>>
+  fetchDossierPage: (query: string, options?: GoldocabListOptions) => Promise<GoldocabPage<GoldocabDossier>>
   getClient: (id: number) => Promise<GoldocabClient | null>
   getDossierItems: (dossierId: number) => Promise<GoldocabItem[]>
 
@@ -60,6 +85,9 @@ interface GoldocabDataStore {
   // Refresh
   refreshLinkedDossierIfStale: (documentId: string) => Promise<GoldocabDossier | null>
 
+  // Modifications de la base par GoldoCab (evenement goldocab-changed)
+  handleGoldocabChange: (event: GoldocabChangeEvent) => Promise<void>
+
   // Cache
   clearCache: () => void
   clearSearchResults: () => void
@@ -74,6 +102,8 @@ export const useGoldocabDataStore = create<GoldocabDataStore>()(
       dossierResults: [],
       isSearching: false,
       searchError: null,
+      lastClientQuery: '',
+      lastDossierQuery: '',
       linkedDossiers: {},
       lastFetchedAt: {},
 
@@ -89,6 +119,7 @@ export const useGoldocabDataStore = create<GoldocabDataStore>()(
       },
 
       searchClients: async (query: string) => {
+        set({ lastClientQuery: query })
         if (!query.trim()) {
           set({ clientResults: [] })
           return []
@@ -122,6 +153,7 @@ export const useGoldocabDataStore = create<GoldocabDataStore>()(
       },
 
       searchDossiers: async (query: string) => {
+        set({ lastDossierQuery: query })
         if (!query.trim()) {
           set({ dossierResults: [] })
           return []
@@ -154,6 +186,35 @@ export const useGoldocabDataStore = create<GoldocabDataStore>()(
         }
       },
 
+      // Pages de liste : le backend garde deja les resultats tant que la base ne change pas
+      fetchClientPage: async (query: string, options?: GoldocabListOptions) => {
+        try {
+          return await invoke<GoldocabPage<GoldocabClient>>('search_goldocab_clients_page', {
+            query,
+            options: options ?? null,
+          })
+        } catch (e) {
+          if (String(e).includes('GOLDOCAB_UNAVAILABLE')) {
+            set({ isAvailable: false })
+          }
+          throw e
+        }
+      },
+
+      fetchDossierPage: async (query: string, options?: GoldocabListOptions) => {
+        try {
+          return await invoke<GoldocabPage<GoldocabDossier>>('search_goldocab_dossiers_page', {
+            query,
+            options: options ?? null,
+          })
+        } catch (e) {
+          if (String(e).includes('GOLDOCAB_UNAVAILABLE')) {
+            set({ isAvailable: false })
+          }
+          throw e
+        }
+      },
+
       getClient: async (id: number) => {
         const cacheKey = `client:${id}`
         const cached = getCached<GoldocabClient>(cacheKey)
@@ -253,6 +314,32 @@ export const useGoldocabDataStore = create<GoldocabDataStore>()(
         return null
       },
 
+      handleGoldocabChange: async (event) => {
+        if (!event.available || event.reopened) {
+          cache.clear()
+          set({ isAvailable: event.available, lastChecked: Date.now(), lastFetchedAt: {} })
+        } else {
+          invalidateCacheForTables(event.tables)
+          if (event.tables.includes('items')) {
+            set({ lastFetchedAt: {} })
+          }
+        }
+
+        if (!event.available) return
+
+        // Relancer uniquement les recherches affichees touchees par la modification
+        const { lastClientQuery, lastDossierQuery, clientResults, dossierResults } = get()
+        if (event.tables.includes('clients') && clientResults.length > 0) {
+          await get().searchClients(lastClientQuery)
+        }
+        if (
+          (event.tables.includes('dossiers') || event.tables.includes('clients')) &&
+          dossierResults.length > 0
+        ) {
+          await get().searchDossiers(lastDossierQuery)
+        }
+      },
+
       clearCache: () => {
         cache.clear()
       },
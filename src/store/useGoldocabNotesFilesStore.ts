@@ -3,6 +3,7 @@
 
 import { create } from 'zustand'
 import { invoke } from '@tauri-apps/api/tauri'
+import type { GoldocabNotification } from '../hooks/useGoldocabIntegration'
 
 export interface NoteFileEntry {
   path: string
@@ -34,6 +35,13 @@ interface NoteFileContent {
   body: string
 }
 
+// Note ecrite sur disque ; notification_error si GoldoCab n'a pu etre prevenu
+interface GoldocabNoteResult {
+  path: string
+  notification: GoldocabNotification | null
+  notification_error: string | null
+}
+
 interface GoldocabNotesFilesStore {
   notes: NoteFileEntry[]
   folders: string[]
@@ -54,6 +62,7 @@ interface GoldocabNotesFilesStore {
     clientId?: string
     tags?: string[]
   }) => Promise<string | null>
+  saveNote: (path: string, body: string, frontmatter?: NoteFileContent['frontmatter']) => Promise<boolean>
   deleteNote: (path: string) => Promise<boolean>
   refresh: () => Promise<void>
   clearError: () => void
@@ -103,7 +112,7 @@ export const useGoldocabNotesFilesStore = create<GoldocabNotesFilesStore>()(
     createNote: async (opts) => {
       set({ isLoading: true, error: null })
       try {
-        const path = await invoke<string>('create_goldocab_note', {
+        const result = await invoke<GoldocabNoteResult>('create_goldocab_note', {
           title: opts.title,
           content: opts.content,
           folder: opts.folder || null,
@@ -113,14 +122,37 @@ export const useGoldocabNotesFilesStore = create<GoldocabNotesFilesStore>()(
         })
         // Recharger la liste
         await get().loadNotes()
-        set({ isLoading: false })
-        return path
+        set({
+          isLoading: false,
+          error: result.notification_error
+            ? `Note creee, GoldoCab non prevenu: ${result.notification_error}`
+            : null,
+        })
+        return result.path
       } catch (error) {
         set({ isLoading: false, error: `Erreur creation note: ${error}` })
         return null
       }
     },
 
+    saveNote: async (path, body, frontmatter) => {
+      try {
+        const result = await invoke<GoldocabNoteResult>('save_goldocab_note', {
+          path,
+          body,
+          frontmatterJson: frontmatter ? JSON.stringify(frontmatter) : null,
+        })
+        await get().loadNotes()
+        if (result.notification_error) {
+          set({ error: `Note enregistree, GoldoCab non prevenu: ${result.notification_error}` })
+        }
+        return true
+      } catch (error) {
+        set({ error: `Erreur enregistrement note: ${error}` })
+        return false
+      }
+    },
+
     deleteNote: async (path) => {
       try {
         await invoke('delete_goldocab_note', { path })
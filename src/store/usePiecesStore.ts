@@ -124,6 +124,16 @@ const getBaseName = (filename: string): string => {
   return filename.replace(PIECE_PREFIX_REGEX, '')
 }
 
+// Tracer un renommage dans le registre de tracabilite du dossier (best effort).
+// A attendre avant le renommage suivant : le registre est rejoue dans l'ordre des lignes
+const recordLedgerRename = async (oldPath: string, newPath: string) => {
+  try {
+    await invoke('record_exhibit_rename', { oldPath, newPath })
+  } catch (error) {
+    console.warn('Ledger rename not recorded:', error)
+  }
+}
+
 // ============================================================================
 // Algorithme de renumerotation en deux passes (Phase 2)
 // ============================================================================
@@ -140,6 +150,8 @@ interface RenameOp {
  * Passe 1: rename vers noms temporaires __TEMP_<ts>_N
  * Passe 2: rename depuis temporaires vers noms finaux
  * Rollback si passe 1 echoue.
+ * Chaque etape est tracee dans le registre, noms temporaires compris : sinon
+ * un echange de noms (A <-> B) intervertirait l'historique des deux pieces.
  */
 async function safeReorderRename(
   operations: Array<{ file: FileItem; newNumber: number }>
@@ -162,6 +174,7 @@ async function safeReorderRename(
 
     try {
       await invoke('rename_item', { oldPath: file.path, newPath: tempPath })
+      await recordLedgerRename(file.path, tempPath)
       pass1Done.push({ oldPath: file.path, tempPath })
       finalOps.push({ oldPath: tempPath, newPath: finalPath, oldName: file.name, newName: finalName })
     } catch (error) {
@@ -170,6 +183,7 @@ async function safeReorderRename(
       for (const done of pass1Done.reverse()) {
         try {
           await invoke('rename_item', { oldPath: done.tempPath, newPath: done.oldPath })
+          await recordLedgerRename(done.tempPath, done.oldPath)
         } catch (rollbackErr) {
           console.error('Rollback failed:', rollbackErr)
         }
@@ -184,6 +198,7 @@ async function safeReorderRename(
     try {
       await invoke('rename_item', { oldPath: op.oldPath, newPath: op.newPath })
       renamedFiles.push(op)
+      await recordLedgerRename(op.oldPath, op.newPath)
     } catch (error) {
       console.error(`Rename pass 2 failed for ${op.oldPath}:`, error)
       // Situation critique - fichiers en __TEMP sur disque
@@ -334,6 +349,7 @@ export const usePiecesStore = create<PiecesStore>()(
 
         try {
           await invoke('rename_item', { oldPath: file.path, newPath })
+          await recordLedgerRename(file.path, newPath)
 
           // Create metadata entry for the newly classified piece
           const docState = getDocumentState(docId)
@@ -404,6 +420,7 @@ export const usePiecesStore = create<PiecesStore>()(
 
         try {
           await invoke('rename_item', { oldPath: file.path, newPath })
+          await recordLedgerRename(file.path, newPath)
 
           // Remove metadata for this piece
           const docState = getDocumentState(docId)
@@ -49,6 +49,16 @@ interface StampStore {
   stampAndCopyAll: (files: FileItem[], outputFolder: string) => Promise<string[]>
 }
 
+// Tracer le tampon dans le registre de tracabilite des pieces (best effort).
+// Attendu avant de passer a la piece suivante, pour garder l'ordre des lignes
+const recordLedgerStamp = async (sourcePath: string, outputPath: string) => {
+  try {
+    await invoke('record_exhibit_stamp', { sourcePath, outputPath })
+  } catch (error) {
+    console.warn('Ledger stamp not recorded:', error)
+  }
+}
+
 export const useStampStore = create<StampStore>()(
   persist(
     (set, get) => ({
@@ -112,6 +122,7 @@ export const useStampStore = create<StampStore>()(
 
           try {
             await invoke('copy_file', { source: file.path, destination: outputPath })
+            await recordLedgerStamp(file.path, outputPath)
             return outputPath
           } catch (error) {
             console.error('Failed to copy file:', error)
@@ -142,6 +153,7 @@ export const useStampStore = create<StampStore>()(
             content: Array.from(stampedBytes),
           })
 
+          await recordLedgerStamp(file.path, outputPath)
           set({ lastOutputFolder: outputFolder })
           return outputPath
         } catch (error) {
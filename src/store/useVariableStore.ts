@@ -1,5 +1,7 @@
 import { create } from 'zustand'
 import { persist } from 'zustand/middleware'
+import { invoke } from '@tauri-apps/api/tauri'
+import type { GoldocabVariableMap } from '../types/goldocab'
 import type { VariableDefinition, VariableCategory } from '../types/editor-features'
 import { DEFAULT_VARIABLES } from '../data/variables/defaults'
 
@@ -46,6 +48,9 @@ interface VariableStore {
 
   // Synchronisation avec le profil avocat
   syncFromLawyerProfile: (profile: Record<string, unknown>) => void
+
+  // Remplissage depuis un dossier GoldoCab (valeurs deja mises en forme)
+  fillFromGoldocabDossier: (documentId: string, dossierId: number) => Promise<GoldocabVariableMap>
 }
 
 // Formater une date en français
@@ -168,12 +173,9 @@ export const useVariableStore = create<VariableStore>()(
 
         // Formater selon le type
         if (def?.type === 'date' && value) {
-          try {
-            const date = new Date(value)
-            return formatDateFr(date)
-          } catch {
-            return value
-          }
+          // Les valeurs deja redigees ("12 mars 2026") sont gardees telles quelles
+          const date = new Date(value)
+          return isNaN(date.getTime()) ? value : formatDateFr(date)
         }
 
         if (def?.type === 'number' && value) {
@@ -276,6 +278,17 @@ export const useVariableStore = create<VariableStore>()(
           }
         }
       },
+
+      fillFromGoldocabDossier: async (documentId, dossierId) => {
+        const result = await invoke<GoldocabVariableMap>('resolve_goldocab_variables', { dossierId })
+        const { setDocumentValue } = get()
+
+        for (const [key, value] of Object.entries(result.values)) {
+          setDocumentValue(documentId, key, value)
+        }
+
+        return result
+      },
     }),
     {
       name: 'citadelle-variables',
@@ -15,6 +15,7 @@ export interface GoldocabClient {
   profession: string | null
   forme_juridique: string | null
   numero_siren: string | null
+  adresse: string | null
 }
 
 export interface GoldocabDossier {
@@ -32,6 +33,51 @@ export interface GoldocabDossier {
   est_favori: boolean | null
 }
 
+export interface GoldocabContact {
+  id: number
+  dossier_id: number | null
+  role: string | null // ex. "Partie adverse", "Avocat adverse", "Expert"
+  civilite: string | null
+  nom: string | null
+  prenom: string | null
+  denomination: string | null
+  adresse: string | null
+  code_postal: string | null
+  ville: string | null
+  email: string | null
+  telephone: string | null
+  display_name: string | null
+  is_adverse: boolean
+}
+
+export interface GoldocabDossierDetail {
+  dossier: GoldocabDossier
+  client: GoldocabClient | null
+  adverse_parties: GoldocabContact[]
+  contacts: GoldocabContact[] // autres intervenants
+  document_folder: string | null
+  document_folder_exists: boolean
+}
+
+export type GoldocabSort = 'relevance' | 'name' | 'last_activity' | 'audience_date'
+
+export interface GoldocabListOptions {
+  offset?: number | null
+  limit?: number | null
+  sort?: GoldocabSort | null
+  favorites_first?: boolean | null
+  statut_client?: string[] | null
+  statut_gestion?: string[] | null // dossiers uniquement
+  etat?: string[] | null // dossiers uniquement
+  type_dossier?: string[] | null // dossiers uniquement
+}
+
+export interface GoldocabPage<T> {
+  items: T[]
+  total: number
+  offset: number // page suivante : offset + items.length, tant que < total
+}
+
 export interface GoldocabItem {
   id: number
   titre: string | null
@@ -44,11 +90,181 @@ export interface GoldocabItem {
   en_cours: boolean | null
 }
 
+export interface GoldocabAgendaEntry {
+  kind: 'audience' | 'echeance'
+  date: string // AAAA-MM-JJ
+  time: string | null // HH:MM, null pour une journee entiere
+  title: string
+  dossier_id: number | null
+  dossier_name: string | null
+  client_name: string | null
+  juridiction: string | null
+  numero_rg: string | null
+  item_id: number | null
+  urgence: number | null
+  gtd_phase: string | null
+}
+
+export interface GoldocabAgendaDay {
+  date: string
+  entries: GoldocabAgendaEntry[]
+}
+
+export interface GoldocabAgendaFilters {
+  min_urgency?: number | null // echeances uniquement
+  gtd_phases?: string[] | null // echeances uniquement
+  include_hearings?: boolean
+  include_items?: boolean
+}
+
+export interface GoldocabAgendaExport {
+  path: string
+  event_count: number
+}
+
+export interface GoldocabVariableMap {
+  dossier_id: number
+  values: Record<string, string> // ex. { "date.audience": "2026-03-12", "date.audience_texte": "12 mars 2026" }
+  missing: string[] // cles sans valeur dans GoldoCab
+}
+
+// Payload de l'evenement Tauri "goldocab-changed"
+export interface GoldocabChangeEvent {
+  tables: Array<'clients' | 'dossiers' | 'items' | 'contacts'>
+  available: boolean
+  reopened: boolean // base rouverte (fichier remplace ou chemin modifie)
+}
+
+// Demandes d'ecriture deposees dans 03_HANDOFF, appliquees par GoldoCab
+export type GoldocabWriteAction =
+  | {
+      kind: 'task'
+      titre: string
+      contexte?: string | null
+      date_echeance?: string | null // AAAA-MM-JJ
+      urgence?: number | null
+      gtd_phase?: string | null
+    }
+  | {
+      kind: 'time_entry'
+      minutes: number
+      date: string // AAAA-MM-JJ
+      description: string
+      document_path?: string | null
+    }
+
+export type GoldocabWriteRequest = GoldocabWriteAction & {
+  schema_version: number
+  request_id: string
+  dossier_id: number
+  created_at: string
+  source: string
+}
+
+export interface GoldocabWriteAck {
+  request_id: string
+  status: 'applied' | 'rejected'
+  item_id: number | null
+  message: string | null
+  processed_at: string | null
+}
+
+export interface GoldocabWriteStatus {
+  request: GoldocabWriteRequest
+  status: 'pending' | 'applied' | 'rejected'
+  ack: GoldocabWriteAck | null
+}
+
+// Demandes deposees par GoldoCab dans 03_HANDOFF/inbox (evenement goldocab-request)
+export type GoldocabInboundAction =
+  | {
+      kind: 'open_file'
+      path: string
+      dossier_id?: string | null
+      dossier_name?: string | null
+    }
+  | {
+      kind: 'cancel_session'
+      session_id: string
+      reason?: string | null
+    }
+
+export type GoldocabInboundRequest = GoldocabInboundAction & {
+  schema_version: number
+  request_id: string
+  created_at: string
+}
+
+// Liens citadelle:// valides, un evenement par route (deep-link-open, -session, -note)
+export type DeepLinkRoute =
+  | {
+      route: 'open'
+      path: string
+      dossier_id: string | null
+      dossier_name: string | null
+      mode: 'document' | 'note'
+    }
+  | { route: 'session'; session_id: string }
+  | { route: 'note'; note_id: string }
+
+export interface DeepLinkRejection {
+  url: string
+  error: string
+}
+
+export type GoldocabAuditEvent =
+  | 'session_started'
+  | 'session_completed'
+  | 'session_cancelled'
+  | 'export'
+  | 'note_created'
+  | 'note_saved'
+  | 'note_deleted'
+
+// Une ligne du journal ~/.citadelle/goldocab-audit.jsonl
+export interface GoldocabAuditEntry {
+  event: GoldocabAuditEvent
+  timestamp: string // ISO 8601 UTC
+  path: string | null
+  target_path: string | null
+  sha256: string | null
+  dossier_id: string | null
+  session_id: string | null
+  detail: string | null
+}
+
+export interface GoldocabAuditQuery {
+  dossier_id?: string | null
+  from?: string | null // AAAA-MM-JJ ou ISO 8601, inclus
+  to?: string | null
+  event?: GoldocabAuditEvent | null
+  limit?: number | null
+}
+
+export interface GoldocabAuditExport {
+  path: string
+  entry_count: number
+}
+
 export interface GoldocabStatus {
   available: boolean
   database_path: string
+  path_source: 'settings' | 'environment' | 'default'
+  client_count: number | null
+  dossier_count: number | null
+  schema_version: string | null
+  missing_fields: string[] // ex. "clients.numeroSIREN"
+  settings_error: string | null // ~/.citadelle/goldocab.json illisible, ignore
+}
+
+export interface GoldocabPathTest {
+  path: string
+  exists: boolean
+  readable: boolean
+  is_goldocab: boolean
   client_count: number | null
   dossier_count: number | null
+  error: string | null
 }
 
 export interface LinkedDossier {